@@ -1,29 +1,255 @@
-//! Sound effect cues for Quiz mode (Issue #73).
+//! Sound effect cues (Issue #73, #48).
 //!
 //! All effects are **synthesized at runtime** as PCM samples; no asset
 //! files ship with the binary. The synthesizer is intentionally tiny
-//! (sine + linear envelope) so the cargo footprint of `rodio` is the
-//! only cost we pay for sound.
+//! (one oscillator + linear envelope) so the cargo footprint of `rodio`
+//! is the only cost we pay for sound.
+//!
+//! A cue is a short score of [`Step`]s; the [`SoundTheme`] picked in
+//! `settings.yaml` decides the waveform and envelope each note is
+//! rendered with, and may mute the keystroke tick altogether.
+//!
+//! Loudness is a [`Mix`]: the cue volume (already scaled by the master
+//! volume), a further scale for the typing clicks, a runtime mute, and
+//! ducking while speech is playing so the clicks never talk over a
+//! Listening prompt (#49).
 //!
 //! If audio output is unavailable (no device, sandboxed environment),
-//! `CueEngine::new` falls back to a silent stub. Quiz mode is fully
+//! `CueEngine::new` falls back to a silent stub. Every mode is fully
 //! playable without sound — the cues are decoration, not signal.
 
 use rodio::buffer::SamplesBuffer;
 use rodio::{OutputStream, OutputStreamHandle, Sink};
+use serde::{Deserialize, Serialize};
 
-const SAMPLE_RATE: u32 = 44_100;
+pub const SAMPLE_RATE: u32 = 44_100;
 
-/// One of the canned cues a Quiz session can fire. Issue #73 names the
-/// "ダダン" question reveal, the "ピンポーン" correct cue, the "ブブー"
-/// wrong cue, and the muted typing / mistype clicks.
-#[derive(Debug, Clone, Copy)]
+/// One of the canned cues. Issue #73 names the Quiz set: the "ダダン"
+/// question reveal, the "ピンポーン" correct cue, the "ブブー" wrong cue,
+/// and the muted typing / mistype clicks. #48 adds the countdown, combo
+/// and battle cues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Cue {
     QuestionReveal,
     Correct,
     Wrong,
     Keystroke,
     Mistype,
+    /// Each of the last seconds of a Timed question's countdown.
+    CountdownTick,
+    /// A correct answer that extends a streak to [`COMBO_MIN`] or more.
+    Combo,
+    /// Listening: the prompt (one enemy) answered correctly.
+    EnemyDefeat,
+    #[allow(dead_code)] // fired by the RPG run loop (#32-#37)
+    EnemyHit,
+    #[allow(dead_code)] // fired by the RPG run loop (#32-#37)
+    LevelUp,
+    #[allow(dead_code)] // fired by the RPG run loop (#32-#37)
+    BossAppear,
+    #[allow(dead_code)] // fired by Time Attack 25, which isn't built yet
+    PanelCapture,
+}
+
+// Only `render-cues` and the tests walk the whole set by name.
+#[allow(dead_code)]
+impl Cue {
+    pub const ALL: [Self; 12] = [
+        Self::QuestionReveal,
+        Self::Correct,
+        Self::Wrong,
+        Self::Keystroke,
+        Self::Mistype,
+        Self::CountdownTick,
+        Self::Combo,
+        Self::EnemyDefeat,
+        Self::EnemyHit,
+        Self::LevelUp,
+        Self::BossAppear,
+        Self::PanelCapture,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::QuestionReveal => "question-reveal",
+            Self::Correct => "correct",
+            Self::Wrong => "wrong",
+            Self::Keystroke => "keystroke",
+            Self::Mistype => "mistype",
+            Self::CountdownTick => "countdown-tick",
+            Self::Combo => "combo",
+            Self::EnemyDefeat => "enemy-defeat",
+            Self::EnemyHit => "enemy-hit",
+            Self::LevelUp => "level-up",
+            Self::BossAppear => "boss-appear",
+            Self::PanelCapture => "panel-capture",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+}
+
+/// Correct answers in a row before each one also plays [`Cue::Combo`].
+pub const COMBO_MIN: u32 = 3;
+
+/// One step of a cue's score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    /// `freq` Hz for `duration_s` seconds at peak amplitude `gain`.
+    Tone {
+        freq: f32,
+        duration_s: f32,
+        gain: f32,
+    },
+    Rest {
+        duration_s: f32,
+    },
+}
+
+/// Oscillator shape a theme renders its notes with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wave {
+    Sine,
+    Square,
+    Triangle,
+}
+
+impl Wave {
+    #[allow(dead_code)] // printed by `render-cues`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sine => "sine",
+            Self::Square => "square",
+            Self::Triangle => "triangle",
+        }
+    }
+
+    /// Amplitude in `-1.0..=1.0` at `phase` cycles into the wave.
+    fn sample(self, phase: f32) -> f32 {
+        match self {
+            Self::Sine => (2.0 * std::f32::consts::PI * phase).sin(),
+            Self::Square if phase.fract() < 0.5 => 1.0,
+            Self::Square => -1.0,
+            Self::Triangle => 4.0 * (phase.fract() - 0.5).abs() - 1.0,
+        }
+    }
+}
+
+/// How a theme renders every note: waveform, envelope and loudness.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Voicing {
+    pub wave: Wave,
+    /// Attack lasts `1 / attack_div` of the note, release `1 / release_div`.
+    pub attack_div: usize,
+    pub release_div: usize,
+    /// Scales each note's own gain; square waves are louder at the same
+    /// peak, so `chip` turns down.
+    pub gain: f32,
+}
+
+/// The cue palette (`sound_theme` in `settings.yaml`). Every theme plays
+/// the same scores, so a cue keeps its meaning whichever one is picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SoundTheme {
+    /// Sine tones, as the cues have always sounded.
+    #[default]
+    Classic,
+    /// Square-wave retro chip sound with snappy envelopes.
+    Chip,
+    /// Triangle waves with slow swells, turned down.
+    Soft,
+    /// Classic, without the per-keystroke tick.
+    SilentKeys,
+}
+
+impl SoundTheme {
+    pub const ALL: [Self; 4] = [Self::Classic, Self::Chip, Self::Soft, Self::SilentKeys];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::Chip => "chip",
+            Self::Soft => "soft",
+            Self::SilentKeys => "silent-keys",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    pub fn voicing(self) -> Voicing {
+        match self {
+            Self::Classic | Self::SilentKeys => Voicing {
+                wave: Wave::Sine,
+                attack_div: 20,
+                release_div: 8,
+                gain: 1.0,
+            },
+            Self::Chip => Voicing {
+                wave: Wave::Square,
+                attack_div: 100,
+                release_div: 12,
+                gain: 0.45,
+            },
+            Self::Soft => Voicing {
+                wave: Wave::Triangle,
+                attack_div: 4,
+                release_div: 3,
+                gain: 0.6,
+            },
+        }
+    }
+
+    /// Whether this theme plays nothing for `cue`.
+    pub fn mutes(self, cue: Cue) -> bool {
+        self == Self::SilentKeys && cue == Cue::Keystroke
+    }
+}
+
+/// Gain of every cue played while speech is coming out.
+pub const DUCK_GAIN: f32 = 0.25;
+
+/// How loud each cue plays right now.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mix {
+    /// Gain applied to every cue, `0.0..=1.0`.
+    pub volume: f32,
+    /// Extra scale for [`Cue::Keystroke`] and [`Cue::Mistype`].
+    pub keystroke: f32,
+    pub muted: bool,
+    /// Speech is playing; everything drops to [`DUCK_GAIN`].
+    pub ducked: bool,
+}
+
+impl Default for Mix {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            keystroke: 1.0,
+            muted: false,
+            ducked: false,
+        }
+    }
+}
+
+impl Mix {
+    pub fn gain(&self, cue: Cue) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+        let mut gain = self.volume;
+        if matches!(cue, Cue::Keystroke | Cue::Mistype) {
+            gain *= self.keystroke;
+        }
+        if self.ducked {
+            gain *= DUCK_GAIN;
+        }
+        gain
+    }
 }
 
 /// Owns the rodio output stream used to play synthesized cues. Each
@@ -34,9 +260,10 @@ pub struct CueEngine {
     /// Held to keep the audio device open. The handle below is what
     /// playback actually goes through; `_stream` is kept to anchor the
     /// device's lifetime to the engine's.
-    #[allow(dead_code)]
     _stream: OutputStream,
     handle: OutputStreamHandle,
+    mix: Mix,
+    theme: SoundTheme,
 }
 
 impl CueEngine {
@@ -48,52 +275,155 @@ impl CueEngine {
         Some(Self {
             _stream: stream,
             handle,
+            mix: Mix::default(),
+            theme: SoundTheme::Classic,
         })
     }
 
+    /// Scale every cue by `volume` (clamped to `0.0..=1.0`).
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.mix.volume = volume.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Scale the keystroke and mistype clicks by `scale` on top of the
+    /// cue volume (clamped to `0.0..=1.0`).
+    pub fn with_keystroke_volume(mut self, scale: f32) -> Self {
+        self.mix.keystroke = scale.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Flip the mute and return whether the cues are now muted.
+    pub fn toggle_mute(&mut self) -> bool {
+        self.mix.muted = !self.mix.muted;
+        self.mix.muted
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.mix.muted
+    }
+
+    /// Duck every cue while speech is playing.
+    pub fn set_ducked(&mut self, ducked: bool) {
+        self.mix.ducked = ducked;
+    }
+
+    pub fn with_theme(mut self, theme: SoundTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     /// Fire-and-forget playback. Drops silently if a sink can't be
     /// created — sound is decorative, not load-bearing.
     pub fn play(&self, cue: Cue) {
-        let samples = synthesize(cue);
+        let gain = self.mix.gain(cue);
+        if gain <= 0.0 {
+            return;
+        }
+        let samples = synthesize(cue, self.theme);
+        if samples.is_empty() {
+            return;
+        }
         let buffer = SamplesBuffer::new(1, SAMPLE_RATE, samples);
         if let Ok(sink) = Sink::try_new(&self.handle) {
+            sink.set_volume(gain);
             sink.append(buffer);
             sink.detach();
         }
     }
 }
 
-/// Render `cue` to a mono PCM buffer at `SAMPLE_RATE`. Each cue mixes a
-/// sequence of tone segments with a linear attack/decay envelope so the
-/// output reads as a clean cue rather than a square click.
-fn synthesize(cue: Cue) -> Vec<f32> {
+const fn tone_step(freq: f32, duration_s: f32, gain: f32) -> Step {
+    Step::Tone {
+        freq,
+        duration_s,
+        gain,
+    }
+}
+
+const fn rest(duration_s: f32) -> Step {
+    Step::Rest { duration_s }
+}
+
+/// The notes of `cue`, whatever the theme.
+pub fn score(cue: Cue) -> Vec<Step> {
     match cue {
         // "ダダン" — two short low-mid hits, second slightly higher.
-        Cue::QuestionReveal => mix_segments(&[
-            tone(330.0, 0.10, 0.35),
-            silence(0.05),
-            tone(440.0, 0.18, 0.40),
-        ]),
+        Cue::QuestionReveal => vec![
+            tone_step(330.0, 0.10, 0.35),
+            rest(0.05),
+            tone_step(440.0, 0.18, 0.40),
+        ],
         // "ピンポーン" — bright two-note major-third descending arpeggio.
-        Cue::Correct => mix_segments(&[
-            tone(880.0, 0.18, 0.32),
-            silence(0.04),
-            tone(660.0, 0.30, 0.32),
-        ]),
+        Cue::Correct => vec![
+            tone_step(880.0, 0.18, 0.32),
+            rest(0.04),
+            tone_step(660.0, 0.30, 0.32),
+        ],
         // "ブブー" — low buzz, two pulses on the same low pitch.
-        Cue::Wrong => mix_segments(&[
-            tone(180.0, 0.18, 0.40),
-            silence(0.05),
-            tone(180.0, 0.20, 0.40),
-        ]),
+        Cue::Wrong => vec![
+            tone_step(180.0, 0.18, 0.40),
+            rest(0.05),
+            tone_step(180.0, 0.20, 0.40),
+        ],
         // Quiet typing tick — short, mid-high, low amplitude.
-        Cue::Keystroke => tone(720.0, 0.025, 0.05),
+        Cue::Keystroke => vec![tone_step(720.0, 0.025, 0.05)],
         // Mistype — slightly lower, slightly louder, shorter than "wrong"
         // so it doesn't overshadow the question feedback.
-        Cue::Mistype => tone(220.0, 0.05, 0.12),
+        Cue::Mistype => vec![tone_step(220.0, 0.05, 0.12)],
+        // Clock tick — high and dry, well under the keystroke's length
+        // so it never masks typing.
+        Cue::CountdownTick => vec![tone_step(1_000.0, 0.03, 0.15)],
+        // A quick upward fifth layered over the "ピンポーン".
+        Cue::Combo => vec![tone_step(990.0, 0.05, 0.22), tone_step(1_480.0, 0.09, 0.22)],
+        // Short thud with a bright edge.
+        Cue::EnemyHit => vec![tone_step(260.0, 0.04, 0.35), tone_step(520.0, 0.05, 0.25)],
+        // Falling run down to a low landing.
+        Cue::EnemyDefeat => vec![
+            tone_step(660.0, 0.07, 0.30),
+            tone_step(440.0, 0.07, 0.30),
+            tone_step(330.0, 0.07, 0.30),
+            tone_step(220.0, 0.20, 0.32),
+        ],
+        // Rising C-major arpeggio ending on the octave.
+        Cue::LevelUp => vec![
+            tone_step(523.25, 0.08, 0.28),
+            tone_step(659.25, 0.08, 0.28),
+            tone_step(783.99, 0.08, 0.28),
+            tone_step(1_046.5, 0.22, 0.30),
+        ],
+        // Low minor second, held — the loudest cue, it happens once a run.
+        Cue::BossAppear => vec![
+            tone_step(110.0, 0.25, 0.45),
+            rest(0.05),
+            tone_step(116.54, 0.40, 0.45),
+        ],
+        // Two-note blip up a fifth.
+        Cue::PanelCapture => vec![tone_step(587.33, 0.06, 0.28), tone_step(880.0, 0.12, 0.28)],
     }
 }
 
+/// Render `cue` as `theme` voices it: a mono PCM buffer at
+/// `SAMPLE_RATE`, empty when the theme mutes the cue.
+pub fn synthesize(cue: Cue, theme: SoundTheme) -> Vec<f32> {
+    if theme.mutes(cue) {
+        return Vec::new();
+    }
+    let voicing = theme.voicing();
+    let segments: Vec<Vec<f32>> = score(cue)
+        .into_iter()
+        .map(|step| match step {
+            Step::Tone {
+                freq,
+                duration_s,
+                gain,
+            } => tone(freq, duration_s, gain, &voicing),
+            Step::Rest { duration_s } => silence(duration_s),
+        })
+        .collect();
+    mix_segments(&segments)
+}
+
 fn mix_segments(segments: &[Vec<f32>]) -> Vec<f32> {
     let total: usize = segments.iter().map(|s| s.len()).sum();
     let mut out = Vec::with_capacity(total);
@@ -108,13 +438,22 @@ fn silence(duration_s: f32) -> Vec<f32> {
     vec![0.0; samples]
 }
 
-/// One tone segment: sine wave at `freq` Hz, `duration_s` seconds, peak
-/// amplitude `gain` in `[0.0, 1.0]`. A short attack/decay envelope avoids
-/// the click that a hard-edged buffer would otherwise produce.
-fn tone(freq: f32, duration_s: f32, gain: f32) -> Vec<f32> {
+/// Sample counts of a `duration_s` note under `voicing`:
+/// `(total, attack, release)`.
+pub fn envelope(duration_s: f32, voicing: &Voicing) -> (usize, usize, usize) {
     let total = (duration_s * SAMPLE_RATE as f32) as usize;
-    let attack = (total / 20).max(1);
-    let release = (total / 8).max(1);
+    let attack = (total / voicing.attack_div).max(1);
+    let release = (total / voicing.release_div).max(1);
+    (total, attack, release)
+}
+
+/// One tone segment: `voicing`'s wave at `freq` Hz, `duration_s`
+/// seconds, peak amplitude `gain * voicing.gain` in `[0.0, 1.0]`. A short
+/// attack/decay envelope avoids the click that a hard-edged buffer would
+/// otherwise produce.
+fn tone(freq: f32, duration_s: f32, gain: f32, voicing: &Voicing) -> Vec<f32> {
+    let (total, attack, release) = envelope(duration_s, voicing);
+    let gain = gain * voicing.gain;
     let mut buf = Vec::with_capacity(total);
     for i in 0..total {
         let t = i as f32 / SAMPLE_RATE as f32;
@@ -125,7 +464,7 @@ fn tone(freq: f32, duration_s: f32, gain: f32) -> Vec<f32> {
         } else {
             1.0
         };
-        let s = (2.0 * std::f32::consts::PI * freq * t).sin() * gain * env;
+        let s = voicing.wave.sample(freq * t) * gain * env;
         buf.push(s);
     }
     buf
@@ -135,9 +474,20 @@ fn tone(freq: f32, duration_s: f32, gain: f32) -> Vec<f32> {
 mod tests {
     use super::*;
 
+    const CLASSIC: Voicing = Voicing {
+        wave: Wave::Sine,
+        attack_div: 20,
+        release_div: 8,
+        gain: 1.0,
+    };
+
+    fn peak(buf: &[f32]) -> f32 {
+        buf.iter().map(|s| s.abs()).fold(0.0_f32, f32::max)
+    }
+
     #[test]
     fn tone_sample_count_matches_duration() {
-        let buf = tone(440.0, 0.10, 0.5);
+        let buf = tone(440.0, 0.10, 0.5, &CLASSIC);
         let expected = (0.10 * SAMPLE_RATE as f32) as usize;
         assert_eq!(buf.len(), expected);
     }
@@ -145,7 +495,7 @@ mod tests {
     #[test]
     fn tone_amplitude_bounded_by_gain() {
         let gain = 0.3;
-        let buf = tone(440.0, 0.05, gain);
+        let buf = tone(440.0, 0.05, gain, &CLASSIC);
         let max = buf.iter().cloned().fold(0.0_f32, f32::max);
         let min = buf.iter().cloned().fold(0.0_f32, f32::min);
         assert!(max <= gain + f32::EPSILON);
@@ -156,7 +506,7 @@ mod tests {
     fn tone_envelope_grows_during_attack_and_settles_in_sustain() {
         // 200 ms tone gives a wide enough attack window to sample the
         // envelope reliably without sine-wave interference.
-        let buf = tone(440.0, 0.20, 0.5);
+        let buf = tone(440.0, 0.20, 0.5, &CLASSIC);
         let attack = (buf.len() / 20).max(1);
         // Compare the early part of the attack against the late part of
         // the attack; the envelope must have ramped up.
@@ -195,7 +545,7 @@ mod tests {
 
     #[test]
     fn synthesize_question_reveal_is_two_segments_with_a_gap() {
-        let buf = synthesize(Cue::QuestionReveal);
+        let buf = synthesize(Cue::QuestionReveal, SoundTheme::Classic);
         // 0.10 + 0.05 + 0.18 seconds total at SAMPLE_RATE.
         let expected = ((0.10 + 0.05 + 0.18) * SAMPLE_RATE as f32) as usize;
         // Allow a 1-sample rounding tolerance from three independent casts.
@@ -209,7 +559,7 @@ mod tests {
 
     #[test]
     fn synthesize_keystroke_is_short_and_quiet() {
-        let buf = synthesize(Cue::Keystroke);
+        let buf = synthesize(Cue::Keystroke, SoundTheme::Classic);
         let expected = (0.025 * SAMPLE_RATE as f32) as usize;
         assert_eq!(buf.len(), expected);
         let peak = buf.iter().map(|s| s.abs()).fold(0.0_f32, f32::max);
@@ -218,8 +568,8 @@ mod tests {
 
     #[test]
     fn synthesize_mistype_is_louder_than_keystroke() {
-        let key = synthesize(Cue::Keystroke);
-        let miss = synthesize(Cue::Mistype);
+        let key = synthesize(Cue::Keystroke, SoundTheme::Classic);
+        let miss = synthesize(Cue::Mistype, SoundTheme::Classic);
         let key_peak = key.iter().map(|s| s.abs()).fold(0.0_f32, f32::max);
         let miss_peak = miss.iter().map(|s| s.abs()).fold(0.0_f32, f32::max);
         assert!(
@@ -227,4 +577,79 @@ mod tests {
             "mistype peak {miss_peak} should exceed keystroke peak {key_peak}"
         );
     }
+
+    #[test]
+    fn classic_voicing_is_the_classic_theme() {
+        assert_eq!(SoundTheme::Classic.voicing(), CLASSIC);
+        assert_eq!(SoundTheme::default(), SoundTheme::Classic);
+    }
+
+    #[test]
+    fn every_cue_sounds_in_every_theme_except_muted_ticks() {
+        for theme in SoundTheme::ALL {
+            for cue in Cue::ALL {
+                let buf = synthesize(cue, theme);
+                if theme.mutes(cue) {
+                    assert!(buf.is_empty(), "{theme:?} {cue:?}");
+                    continue;
+                }
+                assert!(!buf.is_empty(), "{theme:?} {cue:?}");
+                let p = peak(&buf);
+                assert!(p > 0.0 && p <= 1.0, "{theme:?} {cue:?} peak {p}");
+            }
+        }
+        assert!(SoundTheme::SilentKeys.mutes(Cue::Keystroke));
+        assert!(!SoundTheme::SilentKeys.mutes(Cue::Mistype));
+    }
+
+    #[test]
+    fn themes_share_a_score_but_not_a_sound() {
+        let classic = synthesize(Cue::Correct, SoundTheme::Classic);
+        let chip = synthesize(Cue::Correct, SoundTheme::Chip);
+        let soft = synthesize(Cue::Correct, SoundTheme::Soft);
+        assert_eq!(classic.len(), chip.len());
+        assert_eq!(classic.len(), soft.len());
+        assert_ne!(classic, chip);
+        assert!(peak(&soft) < peak(&classic), "soft is quieter");
+        // A square wave sits at its peak for most of the sustain.
+        let loud = chip.iter().filter(|s| s.abs() > 0.9 * peak(&chip)).count();
+        assert!(loud > chip.len() / 2, "{loud} of {}", chip.len());
+    }
+
+    #[test]
+    fn cue_names_round_trip() {
+        for cue in Cue::ALL {
+            assert_eq!(Cue::from_name(cue.name()), Some(cue));
+        }
+        assert_eq!(Cue::from_name("fanfare"), None);
+    }
+
+    #[test]
+    fn sound_theme_names_round_trip() {
+        for theme in SoundTheme::ALL {
+            assert_eq!(SoundTheme::from_name(theme.name()), Some(theme));
+            let yaml = serde_yaml::to_string(&theme).expect("yaml");
+            assert_eq!(yaml.trim(), theme.name());
+        }
+        assert_eq!(SoundTheme::from_name("loud"), None);
+    }
+
+    #[test]
+    fn mix_scales_clicks_ducks_under_speech_and_mutes() {
+        let mut mix = Mix {
+            volume: 0.8,
+            keystroke: 0.5,
+            ..Mix::default()
+        };
+        assert_eq!(mix.gain(Cue::Correct), 0.8);
+        assert_eq!(mix.gain(Cue::Keystroke), 0.4);
+        assert_eq!(mix.gain(Cue::Mistype), 0.4);
+
+        mix.ducked = true;
+        assert_eq!(mix.gain(Cue::Correct), 0.8 * DUCK_GAIN);
+        assert_eq!(mix.gain(Cue::Keystroke), 0.4 * DUCK_GAIN);
+
+        mix.muted = true;
+        assert_eq!(mix.gain(Cue::Correct), 0.0);
+    }
 }
@@ -0,0 +1,349 @@
+//! Local speech engines run as child processes (#41).
+//!
+//! For machines without a speech daemon, type-globe can shell out to an
+//! installed synthesiser and play the WAV it writes. Each engine is
+//! driven the same way: text in (argument or stdin), a WAV file out.
+//!
+//! | engine      | program      | languages | needs                          |
+//! |-------------|--------------|-----------|--------------------------------|
+//! | `espeak-ng` | `espeak-ng`  | ja, en    | —                              |
+//! | `open-jtalk`| `open_jtalk` | ja        | naist-jdic, an `.htsvoice`     |
+//! | `piper`     | `piper`      | per model | an `.onnx` model (`speech_model`) |
+//!
+//! Kept free of the rest of the crate (only `types::Language`) so the
+//! `render-listening` tool can share it.
+
+use crate::types::Language;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+
+/// Debian / Ubuntu install locations of the Open JTalk dictionary and
+/// the bundled voice. `speech_model` replaces the voice.
+pub const OPEN_JTALK_DIC: &str = "/var/lib/mecab/dic/open-jtalk/naist-jdic";
+pub const OPEN_JTALK_VOICE: &str =
+    "/usr/share/hts-voice/nitech-jp-atr503-m001/nitech_jp_atr503_m001.htsvoice";
+
+/// espeak-ng's default speed in words per minute.
+const ESPEAK_WPM: f32 = 175.0;
+/// espeak-ng's default pitch on its 0-99 scale.
+const ESPEAK_PITCH: f32 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalEngine {
+    EspeakNg,
+    OpenJtalk,
+    Piper,
+}
+
+/// What to hand an engine besides the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngineOpts {
+    /// Multiplier on the engine's normal speed, as `tts_rate`.
+    pub rate: f32,
+    /// Multiplier on the engine's normal pitch, as `tts_pitch`. Piper has
+    /// no pitch control and ignores it.
+    pub pitch: f32,
+    /// Piper model / Open JTalk voice file.
+    pub model: Option<String>,
+}
+
+impl Default for EngineOpts {
+    fn default() -> Self {
+        Self {
+            rate: 1.0,
+            pitch: 1.0,
+            model: None,
+        }
+    }
+}
+
+/// One engine invocation, built before anything runs so tests can
+/// check it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    pub program: &'static str,
+    pub args: Vec<String>,
+    /// Text written to the child's stdin, for engines that read it there.
+    pub stdin: Option<String>,
+}
+
+impl LocalEngine {
+    pub const ALL: [Self; 3] = [Self::EspeakNg, Self::OpenJtalk, Self::Piper];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::EspeakNg => "espeak-ng",
+            Self::OpenJtalk => "open-jtalk",
+            Self::Piper => "piper",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.name() == name)
+    }
+
+    pub fn program(self) -> &'static str {
+        match self {
+            Self::EspeakNg => "espeak-ng",
+            Self::OpenJtalk => "open_jtalk",
+            Self::Piper => "piper",
+        }
+    }
+
+    /// Whether the program is on `PATH`.
+    pub fn is_installed(self) -> bool {
+        let Some(path) = std::env::var_os("PATH") else {
+            return false;
+        };
+        std::env::split_paths(&path).any(|dir| dir.join(self.program()).is_file())
+    }
+
+    /// The command that renders `text` to `out`. Fails when the engine
+    /// can't speak `lang` or is missing a model it needs.
+    pub fn invocation(
+        self,
+        text: &str,
+        lang: &Language,
+        opts: &EngineOpts,
+        out: &Path,
+    ) -> Result<Invocation, String> {
+        let out = out.to_string_lossy().into_owned();
+        let rate = opts.rate.clamp(0.5, 2.0);
+        let pitch = opts.pitch.clamp(0.5, 2.0);
+        let invocation = match self {
+            Self::EspeakNg => Invocation {
+                program: self.program(),
+                args: vec![
+                    "-v".into(),
+                    lang.code().into(),
+                    "-s".into(),
+                    format!("{}", (ESPEAK_WPM * rate).round()),
+                    "-p".into(),
+                    format!("{}", (ESPEAK_PITCH * pitch).round().min(99.0)),
+                    "-w".into(),
+                    out,
+                    text.into(),
+                ],
+                stdin: None,
+            },
+            Self::OpenJtalk => {
+                if !matches!(lang, Language::Japanese) {
+                    return Err("open-jtalk only speaks Japanese".into());
+                }
+                let voice = opts.model.as_deref().unwrap_or(OPEN_JTALK_VOICE);
+                Invocation {
+                    program: self.program(),
+                    args: vec![
+                        "-x".into(),
+                        OPEN_JTALK_DIC.into(),
+                        "-m".into(),
+                        voice.into(),
+                        "-r".into(),
+                        format!("{rate}"),
+                        // Additional half-tones: one octave per doubling.
+                        "-fm".into(),
+                        format!("{}", (12.0 * pitch.log2()).round()),
+                        "-ow".into(),
+                        out,
+                    ],
+                    stdin: Some(text.into()),
+                }
+            }
+            Self::Piper => {
+                let model = opts
+                    .model
+                    .as_deref()
+                    .ok_or("piper needs a voice model (set speech_model)")?;
+                Invocation {
+                    program: self.program(),
+                    args: vec![
+                        "--model".into(),
+                        model.into(),
+                        "--output_file".into(),
+                        out,
+                        // Piper stretches phoneme length, so slower is larger.
+                        "--length_scale".into(),
+                        format!("{}", 1.0 / rate),
+                    ],
+                    stdin: Some(text.into()),
+                }
+            }
+        };
+        Ok(invocation)
+    }
+
+    /// Render `text` to the WAV file `out`, waiting for the engine.
+    #[allow(dead_code)] // used by `render-listening`
+    pub fn render(
+        self,
+        text: &str,
+        lang: &Language,
+        opts: &EngineOpts,
+        out: &Path,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.invocation(text, lang, opts, out)?.spawn()?.wait()
+    }
+}
+
+impl Invocation {
+    /// Start the engine without waiting for it.
+    pub fn spawn(self) -> Result<Rendering, Box<dyn std::error::Error>> {
+        let mut child = Command::new(self.program)
+            .args(&self.args)
+            .stdin(if self.stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("{}: {e}", self.program))?;
+        if let (Some(text), Some(mut stdin)) = (self.stdin, child.stdin.take()) {
+            stdin.write_all(text.as_bytes())?;
+        }
+        Ok(Rendering {
+            program: self.program,
+            child,
+        })
+    }
+}
+
+/// An engine run in flight, from [`Invocation::spawn`].
+pub struct Rendering {
+    program: &'static str,
+    child: Child,
+}
+
+impl Rendering {
+    /// `Ok(false)` while the engine runs, `Ok(true)` once it has written
+    /// its file. Never blocks.
+    pub fn try_finish(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+        match self.child.try_wait()? {
+            Some(status) => self.check(status).map(|()| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Block until the engine exits.
+    pub fn wait(mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let status = self.child.wait()?;
+        self.check(status)
+    }
+
+    /// Stop the engine and reap it.
+    pub fn kill(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+
+    fn check(&mut self, status: ExitStatus) -> Result<(), Box<dyn std::error::Error>> {
+        if status.success() {
+            return Ok(());
+        }
+        let mut stderr = String::new();
+        if let Some(mut pipe) = self.child.stderr.take() {
+            let _ = pipe.read_to_string(&mut stderr);
+        }
+        Err(format!("{} exited with {status}: {}", self.program, stderr.trim()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn espeak_takes_the_text_as_an_argument() {
+        let opts = EngineOpts {
+            rate: 0.5,
+            pitch: 2.0,
+            model: None,
+        };
+        let inv = LocalEngine::EspeakNg
+            .invocation(
+                "りんご",
+                &Language::Japanese,
+                &opts,
+                Path::new("/tmp/a.wav"),
+            )
+            .expect("invocation");
+        assert_eq!(
+            inv.args,
+            [
+                "-v",
+                "ja",
+                "-s",
+                "88",
+                "-p",
+                "99",
+                "-w",
+                "/tmp/a.wav",
+                "りんご"
+            ]
+        );
+        assert_eq!(inv.stdin, None);
+    }
+
+    #[test]
+    fn open_jtalk_is_japanese_only_and_reads_stdin() {
+        let out = Path::new("/tmp/a.wav");
+        let opts = EngineOpts::default();
+        assert!(LocalEngine::OpenJtalk
+            .invocation("apple", &Language::English, &opts, out)
+            .is_err());
+        let inv = LocalEngine::OpenJtalk
+            .invocation("りんご", &Language::Japanese, &opts, out)
+            .expect("invocation");
+        assert_eq!(inv.stdin.as_deref(), Some("りんご"));
+        assert!(inv.args.contains(&OPEN_JTALK_VOICE.to_string()));
+        let low = EngineOpts { pitch: 0.5, ..opts };
+        let inv = LocalEngine::OpenJtalk
+            .invocation("りんご", &Language::Japanese, &low, out)
+            .expect("invocation");
+        assert!(inv.args.windows(2).any(|w| w == ["-fm", "-12"]));
+    }
+
+    #[test]
+    fn piper_needs_a_model() {
+        let out = Path::new("/tmp/a.wav");
+        assert!(LocalEngine::Piper
+            .invocation("apple", &Language::English, &EngineOpts::default(), out)
+            .is_err());
+        let opts = EngineOpts {
+            rate: 2.0,
+            pitch: 1.0,
+            model: Some("en_US-amy.onnx".into()),
+        };
+        let inv = LocalEngine::Piper
+            .invocation("apple", &Language::English, &opts, out)
+            .expect("invocation");
+        assert_eq!(inv.args[..2], ["--model", "en_US-amy.onnx"]);
+        assert_eq!(inv.args.last().map(String::as_str), Some("0.5"));
+    }
+
+    fn sh(script: &str) -> Invocation {
+        Invocation {
+            program: "sh",
+            args: vec!["-c".into(), script.into()],
+            stdin: None,
+        }
+    }
+
+    #[test]
+    fn a_spawned_rendering_is_polled_to_its_exit() {
+        let mut rendering = sh("sleep 0.2").spawn().expect("spawn");
+        assert!(!rendering.try_finish().expect("running"));
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !rendering.try_finish().expect("exits cleanly") {
+            assert!(std::time::Instant::now() < deadline, "engine never exited");
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let failed = sh("echo no voice >&2; exit 3").spawn().expect("spawn");
+        let err = failed.wait().expect_err("exit 3").to_string();
+        assert!(err.starts_with("sh exited with"), "{err}");
+        assert!(err.ends_with("no voice"), "{err}");
+    }
+}
@@ -0,0 +1,103 @@
+//! `manifest.yaml` for pre-rendered Listening audio (#42).
+//!
+//! `render-listening` writes one per language directory
+//! (`<audio_dir>/<lang>/manifest.yaml`) next to the files it renders. The
+//! `files` speech backend reads it to find a prompt's file and to refuse
+//! audio rendered from a `text_reading` that has since changed, so an
+//! edited prompt falls through to live speech instead of saying the old
+//! text.
+//!
+//! ```yaml
+//! language: ja
+//! entries:
+//! - id: l-ja-001
+//!   file: l-ja-001.wav
+//!   text_reading: あめ
+//!   engine: espeak-ng
+//!   rate: 1.0
+//!   duration_ms: 412
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+pub const MANIFEST_FILE: &str = "manifest.yaml";
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub language: String,
+    pub entries: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub id: String,
+    /// Relative to the manifest's directory.
+    pub file: String,
+    /// The text the file says; compared against the prompt at playback.
+    pub text_reading: String,
+    pub engine: String,
+    pub rate: f32,
+    pub duration_ms: u64,
+}
+
+impl Manifest {
+    /// Read `<dir>/manifest.yaml`; `None` when there isn't one.
+    pub fn load(dir: &Path) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        let path = dir.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let manifest = serde_yaml::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(Some(manifest))
+    }
+
+    #[allow(dead_code)] // written by `render-listening`
+    pub fn save(&self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(MANIFEST_FILE), serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn entry(&self, id: &str) -> Option<&ManifestEntry> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_then_load_round_trips() {
+        let dir = std::env::temp_dir().join("type_globe_test_manifest");
+        let manifest = Manifest {
+            language: "ja".into(),
+            entries: vec![ManifestEntry {
+                id: "l-ja-001".into(),
+                file: "l-ja-001.wav".into(),
+                text_reading: "あめ".into(),
+                engine: "espeak-ng".into(),
+                rate: 1.0,
+                duration_ms: 412,
+            }],
+        };
+        manifest.save(&dir).expect("save");
+        let loaded = Manifest::load(&dir).expect("load").expect("present");
+        assert_eq!(loaded, manifest);
+        assert_eq!(loaded.entry("l-ja-001").map(|e| e.duration_ms), Some(412));
+        assert!(loaded.entry("l-ja-002").is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_manifest_is_none() {
+        assert!(
+            Manifest::load(Path::new("/nonexistent/type-globe/audio/ja"))
+                .expect("load")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Recording speech backend for headless tests (#45).
+//!
+//! [`MockSpeech`] plays nothing. It writes every `speak` into a
+//! [`SpeechLog`] the test keeps a handle to, and reports `Begin` / `End`
+//! like a real backend: each utterance "lasts" a set number of
+//! `poll_events` calls, so a `ListenUI` run loop sees the audio start
+//! and finish between key presses without speech-dispatcher or a sound
+//! card.
+
+use crate::audio::speech::{PlayWatch, SpeechBackend, SpeechEvent, Utterance};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Instant;
+
+/// One recorded `speak` call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spoken {
+    pub id: String,
+    pub text: String,
+    /// Language code, `ja` / `en`.
+    pub language: String,
+    pub rate: f32,
+    pub at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct Recording {
+    spoken: Vec<Spoken>,
+    stops: usize,
+    failing: bool,
+    /// Polls left before the current utterance ends; `None` when
+    /// nothing is playing.
+    remaining: Option<u32>,
+    watch: PlayWatch,
+}
+
+/// The test's side of a [`MockSpeech`].
+#[derive(Debug, Clone, Default)]
+pub struct SpeechLog(Rc<RefCell<Recording>>);
+
+impl SpeechLog {
+    pub fn spoken(&self) -> Vec<Spoken> {
+        self.0.borrow().spoken.clone()
+    }
+
+    pub fn stops(&self) -> usize {
+        self.0.borrow().stops
+    }
+
+    /// Make every later `speak` fail, like a daemon that went away.
+    pub fn set_failing(&self, failing: bool) {
+        self.0.borrow_mut().failing = failing;
+    }
+
+    /// End the current utterance on the next poll.
+    pub fn finish(&self) {
+        let mut recording = self.0.borrow_mut();
+        if recording.remaining.is_some() {
+            recording.remaining = Some(0);
+        }
+    }
+}
+
+pub struct MockSpeech {
+    log: SpeechLog,
+    /// Polls each utterance lasts; `None` plays until [`SpeechLog::finish`].
+    length: Option<u32>,
+}
+
+impl MockSpeech {
+    /// A backend whose utterances end on the second poll: `Begin` on the
+    /// first, `End` on the next.
+    pub fn new() -> (Self, SpeechLog) {
+        let log = SpeechLog::default();
+        let mock = Self {
+            log: log.clone(),
+            length: Some(2),
+        };
+        (mock, log)
+    }
+
+    pub fn with_length(mut self, polls: Option<u32>) -> Self {
+        self.length = polls;
+        self
+    }
+}
+
+impl SpeechBackend for MockSpeech {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn speak(&mut self, utterance: &Utterance) -> Result<(), Box<dyn std::error::Error>> {
+        let mut recording = self.log.0.borrow_mut();
+        if recording.failing {
+            return Err("mock: speech failed".into());
+        }
+        recording.spoken.push(Spoken {
+            id: utterance.id.to_string(),
+            text: utterance.text.to_string(),
+            language: utterance.language.code().to_string(),
+            rate: utterance.rate,
+            at: Instant::now(),
+        });
+        recording.remaining = Some(self.length.unwrap_or(u32::MAX));
+        recording.watch.started();
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut recording = self.log.0.borrow_mut();
+        recording.stops += 1;
+        recording.remaining = None;
+        recording.watch.stopped();
+        Ok(())
+    }
+
+    fn is_speaking(&self) -> bool {
+        self.log.0.borrow().remaining.is_some()
+    }
+
+    fn poll_events(&mut self) -> Vec<SpeechEvent> {
+        let mut recording = self.log.0.borrow_mut();
+        recording.remaining = match recording.remaining {
+            Some(left) if left > 1 => Some(left - 1),
+            _ => None,
+        };
+        let playing = recording.remaining.is_some();
+        recording.watch.poll(playing)
+    }
+}
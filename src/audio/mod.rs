@@ -1,8 +1,14 @@
-//! Audio output. Hosts the speech-dispatcher TTS wrapper (#28) and the
-//! synthesized sound-effect cues used by Quiz mode (#73).
+//! Audio output. Hosts the speech backends for Listening (#28, #41) and
+//! the synthesized sound-effect cues and their themes (#73, #48).
 
 pub mod cues;
+pub mod engine;
+pub mod manifest;
+#[cfg(test)]
+pub mod mock;
+pub mod speech;
 pub mod tts;
+pub mod wav;
 
-pub use cues::{Cue, CueEngine};
-pub use tts::TtsEngine;
+pub use cues::{Cue, CueEngine, SoundTheme, COMBO_MIN};
+pub use speech::{SpeechBackend, SpeechEvent, Utterance, SLOW_RATE};
@@ -0,0 +1,641 @@
+//! Pluggable speech output for Listening (#41).
+//!
+//! [`SpeechBackend`] is what `ListenUI` talks to. Three kinds exist:
+//!
+//! - `files`: pre-rendered WAVs in `<data_dir>/audio/<lang>/<id>.wav`,
+//!   keyed by `ListeningPrompt::id` (or as listed in that directory's
+//!   `manifest.yaml`), played through rodio;
+//! - `tts`: the `tts` crate ([`TtsEngine`], speech-dispatcher on Linux);
+//! - `espeak-ng` / `open-jtalk` / `piper`: a local engine run as a child
+//!   process ([`LocalEngine`]), whose WAV output is played through rodio.
+//!
+//! `speech:` in `settings.yaml` lists the kinds in the order they are
+//! tried. [`open`] drops the ones that can't start on this machine and
+//! chains the rest, so a prompt missing from the `files` directory still
+//! falls through to the next backend.
+
+use crate::audio::engine::{EngineOpts, LocalEngine, Rendering};
+use crate::audio::manifest::Manifest;
+use crate::audio::tts::TtsEngine;
+use crate::audio::wav;
+use crate::types::Language;
+use rodio::buffer::SamplesBuffer;
+use rodio::{OutputStream, OutputStreamHandle, Sink};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// One thing to say. `id` names the prompt for backends that look audio
+/// up instead of synthesising it.
+#[derive(Debug, Clone, Copy)]
+pub struct Utterance<'a> {
+    pub id: &'a str,
+    pub text: &'a str,
+    pub language: &'a Language,
+    /// Multiplier on the configured rate: 1.0 normally, [`SLOW_RATE`] for
+    /// Listening's slow replay.
+    pub rate: f32,
+}
+
+/// Rate of the slow replay key. Synthesising backends stretch the
+/// speech; `files` plays the clip slower (and so lower).
+pub const SLOW_RATE: f32 = 0.6;
+
+pub trait SpeechBackend {
+    /// Short name for messages and the stealth log.
+    fn name(&self) -> &'static str;
+
+    /// Start speaking, interrupting whatever is in flight, so a
+    /// Space-replay (#30) never queues the same prompt twice.
+    fn speak(&mut self, utterance: &Utterance) -> Result<(), Box<dyn std::error::Error>>;
+
+    fn stop(&mut self) -> Result<(), Box<dyn std::error::Error>>;
+
+    fn is_speaking(&self) -> bool;
+
+    /// Begin / end of the current utterance since the last call, oldest
+    /// first. `ListenUI` polls once per frame. An interrupted utterance
+    /// (`stop`, or a new `speak`) never reports `End`.
+    fn poll_events(&mut self) -> Vec<SpeechEvent>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeechEvent {
+    /// Audio started coming out.
+    Begin,
+    /// The utterance played to its end.
+    End,
+}
+
+/// Begin / end bookkeeping for backends that can only be asked whether
+/// they are still playing: `Begin` when playback starts, `End` on the
+/// first poll that finds it silent.
+#[derive(Debug, Default)]
+pub struct PlayWatch {
+    events: Vec<SpeechEvent>,
+    playing: bool,
+}
+
+impl PlayWatch {
+    /// A new utterance started; anything unpolled from the last one is
+    /// dropped.
+    pub fn started(&mut self) {
+        self.events = vec![SpeechEvent::Begin];
+        self.playing = true;
+    }
+
+    pub fn stopped(&mut self) {
+        self.events.clear();
+        self.playing = false;
+    }
+
+    pub fn poll(&mut self, still_playing: bool) -> Vec<SpeechEvent> {
+        if self.playing && !still_playing {
+            self.playing = false;
+            self.events.push(SpeechEvent::End);
+        }
+        std::mem::take(&mut self.events)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpeechKind {
+    Files,
+    Tts,
+    EspeakNg,
+    OpenJtalk,
+    Piper,
+}
+
+impl SpeechKind {
+    /// Also the default fallback order: identical pre-rendered audio
+    /// first, then the platform TTS, then whatever engine is installed.
+    pub const ALL: [Self; 5] = [
+        Self::Files,
+        Self::Tts,
+        Self::EspeakNg,
+        Self::OpenJtalk,
+        Self::Piper,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Files => "files",
+            Self::Tts => "tts",
+            Self::EspeakNg => "espeak-ng",
+            Self::OpenJtalk => "open-jtalk",
+            Self::Piper => "piper",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.name() == name)
+    }
+
+    /// The local engine behind this kind; the names are shared.
+    fn engine(self) -> Option<LocalEngine> {
+        LocalEngine::from_name(self.name())
+    }
+}
+
+/// Everything [`open`] needs, gathered from `Settings` and `Config`.
+#[derive(Debug, Clone)]
+pub struct SpeechOptions {
+    pub order: Vec<SpeechKind>,
+    /// Root of the pre-rendered audio (`<data_dir>/audio`).
+    pub audio_dir: PathBuf,
+    /// TTS voice names by language code.
+    pub voices: HashMap<String, String>,
+    pub rate: f32,
+    pub pitch: f32,
+    /// Speech gain in `0.0..=1.0`.
+    pub volume: f32,
+    pub model: Option<String>,
+}
+
+/// Start every backend in `options.order` that can run here and chain
+/// them. Fails, listing why each one was skipped, when none can.
+pub fn open(options: &SpeechOptions) -> Result<SpeechChain, String> {
+    let mut backends: Vec<Box<dyn SpeechBackend>> = Vec::new();
+    let mut skipped = Vec::new();
+    for kind in &options.order {
+        let started: Result<Box<dyn SpeechBackend>, String> = match kind.engine() {
+            Some(engine) => LocalBackend::new(
+                engine,
+                EngineOpts {
+                    rate: options.rate,
+                    pitch: options.pitch,
+                    model: options.model.clone(),
+                },
+                options.volume,
+            )
+            .map(|b| Box::new(b) as Box<dyn SpeechBackend>),
+            None if *kind == SpeechKind::Files => {
+                PrerenderedBackend::new(options.audio_dir.clone(), options.volume)
+                    .map(|b| Box::new(b) as Box<dyn SpeechBackend>)
+            }
+            None => TtsEngine::new()
+                .map(|t| {
+                    Box::new(
+                        t.with_voices(options.voices.clone())
+                            .with_rate(options.rate)
+                            .with_pitch(options.pitch)
+                            .with_volume(options.volume),
+                    ) as Box<dyn SpeechBackend>
+                })
+                .map_err(|e| e.to_string()),
+        };
+        match started {
+            Ok(backend) => backends.push(backend),
+            Err(e) => skipped.push(format!("{}: {e}", kind.name())),
+        }
+    }
+    if backends.is_empty() {
+        return Err(if skipped.is_empty() {
+            "no speech backend configured (`speech:` is empty)".into()
+        } else {
+            format!("no speech backend could start ({})", skipped.join("; "))
+        });
+    }
+    Ok(SpeechChain::new(backends))
+}
+
+/// Backends tried in order on every utterance; the first that accepts
+/// it becomes the active one until the next `speak`.
+pub struct SpeechChain {
+    backends: Vec<Box<dyn SpeechBackend>>,
+    active: Option<usize>,
+}
+
+impl SpeechChain {
+    pub fn new(backends: Vec<Box<dyn SpeechBackend>>) -> Self {
+        Self {
+            backends,
+            active: None,
+        }
+    }
+}
+
+impl SpeechBackend for SpeechChain {
+    fn name(&self) -> &'static str {
+        self.active
+            .or((!self.backends.is_empty()).then_some(0))
+            .map_or("none", |i| self.backends[i].name())
+    }
+
+    fn speak(&mut self, utterance: &Utterance) -> Result<(), Box<dyn std::error::Error>> {
+        let _ = self.stop();
+        let mut failures = Vec::new();
+        for (i, backend) in self.backends.iter_mut().enumerate() {
+            match backend.speak(utterance) {
+                Ok(()) => {
+                    self.active = Some(i);
+                    return Ok(());
+                }
+                Err(e) => failures.push(format!("{}: {e}", backend.name())),
+            }
+        }
+        self.active = None;
+        Err(failures.join("; ").into())
+    }
+
+    fn stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        match self.active.and_then(|i| self.backends.get_mut(i)) {
+            Some(backend) => backend.stop(),
+            None => Ok(()),
+        }
+    }
+
+    fn is_speaking(&self) -> bool {
+        self.active
+            .and_then(|i| self.backends.get(i))
+            .is_some_and(|backend| backend.is_speaking())
+    }
+
+    fn poll_events(&mut self) -> Vec<SpeechEvent> {
+        match self.active.and_then(|i| self.backends.get_mut(i)) {
+            Some(backend) => backend.poll_events(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// rodio output shared by the file-playing backends. One sink at a
+/// time: a new clip replaces the old one.
+struct Playback {
+    /// Keeps the device open for as long as the backend lives.
+    _stream: OutputStream,
+    handle: OutputStreamHandle,
+    sink: Option<Sink>,
+    volume: f32,
+    watch: PlayWatch,
+}
+
+impl Playback {
+    fn new(volume: f32) -> Result<Self, String> {
+        let (stream, handle) =
+            OutputStream::try_default().map_err(|e| format!("no audio output: {e}"))?;
+        Ok(Self {
+            _stream: stream,
+            handle,
+            sink: None,
+            volume,
+            watch: PlayWatch::default(),
+        })
+    }
+
+    /// Play `pcm` at `speed` (1.0 = as recorded).
+    fn play(&mut self, pcm: wav::Pcm, speed: f32) -> Result<(), Box<dyn std::error::Error>> {
+        self.stop();
+        let sink = Sink::try_new(&self.handle)?;
+        sink.set_volume(self.volume);
+        sink.set_speed(speed);
+        sink.append(SamplesBuffer::new(
+            pcm.channels,
+            pcm.sample_rate,
+            pcm.samples,
+        ));
+        self.sink = Some(sink);
+        self.watch.started();
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        if let Some(sink) = self.sink.take() {
+            sink.stop();
+        }
+        self.watch.stopped();
+    }
+
+    fn is_playing(&self) -> bool {
+        self.sink.as_ref().is_some_and(|sink| !sink.empty())
+    }
+
+    fn poll_events(&mut self) -> Vec<SpeechEvent> {
+        let playing = self.is_playing();
+        self.watch.poll(playing)
+    }
+}
+
+/// `files`: `<audio_dir>/<lang>/<id>.wav`, as written by
+/// `render-listening` or recorded by hand. WAV only: rodio is built
+/// without its decoders, so an OGG clip fails to load and the chain
+/// falls through to the next backend.
+pub struct PrerenderedBackend {
+    dir: PathBuf,
+    /// Each language's manifest, read on first use.
+    manifests: HashMap<String, Option<Manifest>>,
+    playback: Playback,
+}
+
+impl PrerenderedBackend {
+    pub fn new(dir: PathBuf, volume: f32) -> Result<Self, String> {
+        if !dir.is_dir() {
+            return Err(format!("{} does not exist", dir.display()));
+        }
+        Ok(Self {
+            dir,
+            manifests: HashMap::new(),
+            playback: Playback::new(volume)?,
+        })
+    }
+
+    fn manifest(&mut self, lang: &Language) -> Result<Option<&Manifest>, String> {
+        let code = lang.code().to_string();
+        if !self.manifests.contains_key(&code) {
+            let loaded = Manifest::load(&self.dir.join(&code)).map_err(|e| e.to_string())?;
+            self.manifests.insert(code.clone(), loaded);
+        }
+        Ok(self.manifests[&code].as_ref())
+    }
+}
+
+/// The file the `files` backend plays for `utterance`: the manifest's
+/// entry when there is one (refused if it was rendered from other text),
+/// else `<dir>/<lang>/<id>.wav`.
+pub fn prerendered_file(
+    dir: &Path,
+    utterance: &Utterance,
+    manifest: Option<&Manifest>,
+) -> Result<PathBuf, String> {
+    let lang_dir = dir.join(utterance.language.code());
+    match manifest.and_then(|m| m.entry(utterance.id)) {
+        Some(entry) if entry.text_reading != utterance.text => Err(format!(
+            "{}: rendered from \"{}\", prompt is now \"{}\"",
+            utterance.id, entry.text_reading, utterance.text
+        )),
+        Some(entry) => Ok(lang_dir.join(&entry.file)),
+        None => Ok(lang_dir.join(format!("{}.wav", utterance.id))),
+    }
+}
+
+impl SpeechBackend for PrerenderedBackend {
+    fn name(&self) -> &'static str {
+        SpeechKind::Files.name()
+    }
+
+    fn speak(&mut self, utterance: &Utterance) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = self.dir.clone();
+        let path = prerendered_file(&dir, utterance, self.manifest(utterance.language)?)?;
+        let bytes = std::fs::read(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let pcm = wav::decode(&bytes).map_err(|e| format!("{}: {e}", path.display()))?;
+        self.playback.play(pcm, utterance.rate)
+    }
+
+    fn stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.playback.stop();
+        Ok(())
+    }
+
+    fn is_speaking(&self) -> bool {
+        self.playback.is_playing()
+    }
+
+    fn poll_events(&mut self) -> Vec<SpeechEvent> {
+        self.playback.poll_events()
+    }
+}
+
+/// `espeak-ng` / `open-jtalk` / `piper`. `speak` only starts the child
+/// process; polling picks up its WAV and starts playback, so a slow
+/// piper or Open JTalk never stalls the UI thread. An engine that fails
+/// after starting stays silent, and the Listening gate's start timeout
+/// opens typing.
+pub struct LocalBackend {
+    engine: LocalEngine,
+    opts: EngineOpts,
+    scratch: PathBuf,
+    /// The engine run whose output plays next.
+    pending: Option<Rendering>,
+    playback: Playback,
+}
+
+impl LocalBackend {
+    pub fn new(engine: LocalEngine, opts: EngineOpts, volume: f32) -> Result<Self, String> {
+        if !engine.is_installed() {
+            return Err(format!("`{}` not found on PATH", engine.program()));
+        }
+        let scratch = std::env::temp_dir().join(format!(
+            "type-globe-{}-{}.wav",
+            engine.name(),
+            std::process::id()
+        ));
+        Ok(Self {
+            engine,
+            opts,
+            scratch,
+            pending: None,
+            playback: Playback::new(volume)?,
+        })
+    }
+
+    /// Play the WAV the finished engine run wrote.
+    fn play_scratch(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let pcm = wav::decode(&std::fs::read(&self.scratch)?)?;
+        self.playback.play(pcm, 1.0)
+    }
+}
+
+impl SpeechBackend for LocalBackend {
+    fn name(&self) -> &'static str {
+        self.engine.name()
+    }
+
+    fn speak(&mut self, utterance: &Utterance) -> Result<(), Box<dyn std::error::Error>> {
+        self.stop()?;
+        let opts = EngineOpts {
+            rate: self.opts.rate * utterance.rate,
+            ..self.opts.clone()
+        };
+        let invocation =
+            self.engine
+                .invocation(utterance.text, utterance.language, &opts, &self.scratch)?;
+        self.pending = Some(invocation.spawn()?);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(rendering) = self.pending.take() {
+            rendering.kill();
+        }
+        self.playback.stop();
+        Ok(())
+    }
+
+    fn is_speaking(&self) -> bool {
+        self.pending.is_some() || self.playback.is_playing()
+    }
+
+    fn poll_events(&mut self) -> Vec<SpeechEvent> {
+        if let Some(rendering) = self.pending.as_mut() {
+            match rendering.try_finish() {
+                Ok(false) => return Vec::new(),
+                Ok(true) => {
+                    self.pending = None;
+                    if self.play_scratch().is_err() {
+                        return Vec::new();
+                    }
+                }
+                Err(_) => {
+                    self.pending = None;
+                    return Vec::new();
+                }
+            }
+        }
+        self.playback.poll_events()
+    }
+}
+
+impl Drop for LocalBackend {
+    fn drop(&mut self) {
+        if let Some(rendering) = self.pending.take() {
+            rendering.kill();
+        }
+        let _ = std::fs::remove_file(&self.scratch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Says yes to the ids it knows, like a `files` directory would.
+    struct Known(&'static [&'static str], &'static str);
+
+    impl SpeechBackend for Known {
+        fn name(&self) -> &'static str {
+            self.1
+        }
+
+        fn speak(&mut self, utterance: &Utterance) -> Result<(), Box<dyn std::error::Error>> {
+            if self.0.contains(&utterance.id) {
+                Ok(())
+            } else {
+                Err(format!("no {}", utterance.id).into())
+            }
+        }
+
+        fn stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+            Ok(())
+        }
+
+        fn is_speaking(&self) -> bool {
+            false
+        }
+
+        fn poll_events(&mut self) -> Vec<SpeechEvent> {
+            Vec::new()
+        }
+    }
+
+    fn say(chain: &mut SpeechChain, id: &str) -> Result<(), String> {
+        let utterance = Utterance {
+            id,
+            text: id,
+            language: &Language::Japanese,
+            rate: 1.0,
+        };
+        chain.speak(&utterance).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn chain_falls_through_to_the_next_backend() {
+        let mut chain = SpeechChain::new(vec![
+            Box::new(Known(&["l001"], "files")),
+            Box::new(Known(&["l001", "l002"], "tts")),
+        ]);
+        say(&mut chain, "l001").expect("files has it");
+        assert_eq!(chain.name(), "files");
+        say(&mut chain, "l002").expect("tts has it");
+        assert_eq!(chain.name(), "tts");
+        assert_eq!(
+            say(&mut chain, "l003"),
+            Err("files: no l003; tts: no l003".to_string())
+        );
+    }
+
+    #[test]
+    fn open_reports_every_skipped_backend() {
+        let options = SpeechOptions {
+            order: vec![SpeechKind::Files],
+            audio_dir: PathBuf::from("/nonexistent/type-globe/audio"),
+            voices: HashMap::new(),
+            rate: 1.0,
+            pitch: 1.0,
+            volume: 1.0,
+            model: None,
+        };
+        let err = open(&options).err().expect("no backend");
+        assert!(err.contains("files: /nonexistent"), "{err}");
+        let empty = SpeechOptions {
+            order: Vec::new(),
+            ..options
+        };
+        assert!(open(&empty).is_err());
+    }
+
+    #[test]
+    fn prerendered_files_are_keyed_by_language_and_id() {
+        use crate::audio::manifest::ManifestEntry;
+
+        let dir = Path::new("/data/audio");
+        let utterance = Utterance {
+            id: "l001",
+            text: "apple",
+            language: &Language::English,
+            rate: 1.0,
+        };
+        assert_eq!(
+            prerendered_file(dir, &utterance, None),
+            Ok(PathBuf::from("/data/audio/en/l001.wav"))
+        );
+
+        let mut manifest = Manifest {
+            language: "en".into(),
+            entries: vec![ManifestEntry {
+                id: "l001".into(),
+                file: "apple.wav".into(),
+                text_reading: "apple".into(),
+                engine: "espeak-ng".into(),
+                rate: 1.0,
+                duration_ms: 400,
+            }],
+        };
+        assert_eq!(
+            prerendered_file(dir, &utterance, Some(&manifest)),
+            Ok(PathBuf::from("/data/audio/en/apple.wav"))
+        );
+        manifest.entries[0].text_reading = "an apple".into();
+        assert!(prerendered_file(dir, &utterance, Some(&manifest)).is_err());
+    }
+
+    #[test]
+    fn play_watch_reports_end_only_for_finished_audio() {
+        use SpeechEvent::{Begin, End};
+
+        let mut watch = PlayWatch::default();
+        assert_eq!(watch.poll(false), []);
+        watch.started();
+        assert_eq!(watch.poll(true), [Begin]);
+        assert_eq!(watch.poll(true), []);
+        assert_eq!(watch.poll(false), [End]);
+        assert_eq!(watch.poll(false), []);
+
+        // Interrupted: neither the old Begin nor an End comes through.
+        watch.started();
+        watch.stopped();
+        assert_eq!(watch.poll(false), []);
+        // A replay before the first poll reports one Begin, not two.
+        watch.started();
+        watch.started();
+        assert_eq!(watch.poll(false), [Begin, End]);
+    }
+
+    #[test]
+    fn kind_names_round_trip() {
+        for kind in SpeechKind::ALL {
+            assert_eq!(SpeechKind::from_name(kind.name()), Some(kind));
+        }
+    }
+}
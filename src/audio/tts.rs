@@ -10,19 +10,47 @@
 //! 3. Expose `speak` / `stop` / `is_speaking` in terms type-globe needs;
 //!    every call interrupts whatever is currently speaking, so
 //!    `Space`-replay (#30) just calls `speak` again.
+//! 4. Turn the backend's utterance begin / end callbacks into
+//!    [`SpeechEvent`]s (#44). Backends without callbacks fall back to
+//!    polling `is_speaking`.
 //!
 //! Initialisation may legitimately fail on systems without a TTS daemon
 //! running (most often a Linux box without `speech-dispatcher`); the
-//! caller surfaces that to the player as "audio unavailable, listening
-//! mode disabled" rather than crashing the whole binary.
+//! speech chain (`audio::speech`, #41) then moves on to the next backend
+//! in `speech:` order.
 
+use crate::audio::speech::{PlayWatch, SpeechBackend, SpeechEvent, SpeechKind, Utterance};
 use crate::types::Language;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver};
 use tts::{Tts, Voice};
 
 /// Run-scoped TTS handle. Kept on the main thread (the underlying `Tts`
 /// wraps an `Rc` and is therefore `!Send`).
 pub struct TtsEngine {
     inner: Tts,
+    /// Voice names from `settings.yaml` by language code (`tts_voice_ja`,
+    /// falling back to `tts_voice`). Preferred over the by-language pick
+    /// when the backend has a voice of that name.
+    voices: HashMap<String, String>,
+    /// `tts_rate`; each utterance's own rate multiplies it.
+    rate: f32,
+    progress: Progress,
+}
+
+/// Where begin / end come from.
+enum Progress {
+    /// Sent by the backend's callbacks, which run on its own thread.
+    Callbacks(Receiver<SpeechEvent>),
+    Polled(PlayWatch),
+}
+
+/// One installed voice, as `type-globe voices` lists it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceInfo {
+    pub name: String,
+    /// BCP 47 tag, e.g. `ja-JP`.
+    pub language: String,
 }
 
 impl TtsEngine {
@@ -30,51 +58,193 @@ impl TtsEngine {
     /// initialised (e.g. Linux without speech-dispatcher running).
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
         let inner = Tts::default()?;
-        Ok(Self { inner })
+        let progress = if inner.supported_features().utterance_callbacks {
+            let (tx, rx) = mpsc::channel();
+            let begin = tx.clone();
+            inner.on_utterance_begin(Some(Box::new(move |_| {
+                let _ = begin.send(SpeechEvent::Begin);
+            })))?;
+            inner.on_utterance_end(Some(Box::new(move |_| {
+                let _ = tx.send(SpeechEvent::End);
+            })))?;
+            Progress::Callbacks(rx)
+        } else {
+            Progress::Polled(PlayWatch::default())
+        };
+        Ok(Self {
+            inner,
+            voices: HashMap::new(),
+            rate: 1.0,
+            progress,
+        })
+    }
+
+    /// Prefer the installed voice named for each language code
+    /// (case-insensitive).
+    pub fn with_voices(mut self, voices: HashMap<String, String>) -> Self {
+        self.voices = voices;
+        self
+    }
+
+    /// Speak at `multiplier` (0.5–2.0, 1.0 = normal) of the backend's
+    /// rate. Best-effort like voice selection.
+    pub fn with_rate(mut self, multiplier: f32) -> Self {
+        self.rate = multiplier;
+        self
+    }
+
+    /// Pitch multiplier (0.5–2.0, 1.0 = normal), mapped like the rate.
+    /// Ignored by backends without pitch control.
+    pub fn with_pitch(mut self, multiplier: f32) -> Self {
+        if self.inner.supported_features().pitch {
+            let pitch = backend_scale(
+                multiplier,
+                self.inner.min_pitch(),
+                self.inner.normal_pitch(),
+                self.inner.max_pitch(),
+            );
+            let _ = self.inner.set_pitch(pitch);
+        }
+        self
+    }
+
+    /// Volume in `0.0..=1.0` of the backend's range.
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        if self.inner.supported_features().volume {
+            let (min, max) = (self.inner.min_volume(), self.inner.max_volume());
+            let _ = self
+                .inner
+                .set_volume(min + (max - min) * volume.clamp(0.0, 1.0));
+        }
+        self
+    }
+
+    /// Every installed voice, for `lang` only when given.
+    pub fn voices(
+        &self,
+        lang: Option<&Language>,
+    ) -> Result<Vec<VoiceInfo>, Box<dyn std::error::Error>> {
+        Ok(self
+            .inner
+            .voices()?
+            .into_iter()
+            .filter(|v| lang.map_or(true, |l| v.language().primary_language() == l.code()))
+            .map(|v| VoiceInfo {
+                name: v.name(),
+                language: v.language().to_string(),
+            })
+            .collect())
+    }
+
+    /// Name of the voice `speak` would use for `lang`; `None` means the
+    /// system default.
+    pub fn chosen_voice(&self, lang: &Language) -> Option<String> {
+        let preferred = self.voices.get(lang.code()).map(String::as_str);
+        pick_voice(&self.inner, preferred, lang).map(|v| v.name())
     }
 
-    /// Speak `text` using a voice that matches `lang`. If no matching
-    /// voice is installed the system default voice is used — better to
-    /// hear the prompt in the wrong accent than to fall silent.
+    /// Forget events from the utterance being replaced.
+    fn discard_events(&mut self) {
+        match &mut self.progress {
+            Progress::Callbacks(rx) => while rx.try_recv().is_ok() {},
+            Progress::Polled(watch) => watch.stopped(),
+        }
+    }
+}
+
+impl SpeechBackend for TtsEngine {
+    fn name(&self) -> &'static str {
+        SpeechKind::Tts.name()
+    }
+
+    /// Speak the text using a voice that matches the language. If no
+    /// matching voice is installed the system default voice is used —
+    /// better to hear the prompt in the wrong accent than to fall silent.
     ///
     /// `interrupt = true` so a Space-mash replay flow (#30) does not
     /// queue identical utterances; each call replaces the in-flight one.
-    pub fn speak(&mut self, text: &str, lang: &Language) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(voice) = pick_voice(&self.inner, lang) {
+    fn speak(&mut self, utterance: &Utterance) -> Result<(), Box<dyn std::error::Error>> {
+        let preferred = self.voices.get(utterance.language.code());
+        if let Some(voice) = pick_voice(
+            &self.inner,
+            preferred.map(String::as_str),
+            utterance.language,
+        ) {
             // Voice selection is best-effort — a backend that doesn't
             // support `set_voice` (or rejects this voice) shouldn't kill
             // the run; we still want to attempt the speak call.
             let _ = self.inner.set_voice(&voice);
         }
-        self.inner.speak(text, true)?;
+        if self.inner.supported_features().rate {
+            let rate = backend_scale(
+                self.rate * utterance.rate,
+                self.inner.min_rate(),
+                self.inner.normal_rate(),
+                self.inner.max_rate(),
+            );
+            let _ = self.inner.set_rate(rate);
+        }
+        self.discard_events();
+        self.inner.speak(utterance.text, true)?;
+        if let Progress::Polled(watch) = &mut self.progress {
+            watch.started();
+        }
         Ok(())
     }
 
-    pub fn stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    fn stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.inner.stop()?;
+        self.discard_events();
         Ok(())
     }
 
-    /// Whether the backend is currently producing audio. Reserved for
-    /// the run-loop work in #32-#37 (which will gate Space-replay on
-    /// "is the previous utterance still going?"). Foundation flow
-    /// always interrupts on replay so this isn't called yet.
-    #[allow(dead_code)]
-    pub fn is_speaking(&self) -> bool {
+    fn is_speaking(&self) -> bool {
         self.inner.is_speaking().unwrap_or(false)
     }
+
+    /// Without callbacks or `is_speaking` support the utterance "ends"
+    /// on the first poll, so nothing waits on audio it can't track.
+    fn poll_events(&mut self) -> Vec<SpeechEvent> {
+        let playing = self.is_speaking();
+        match &mut self.progress {
+            Progress::Callbacks(rx) => rx.try_iter().collect(),
+            Progress::Polled(watch) => watch.poll(playing),
+        }
+    }
 }
 
-/// Best-match voice for `lang`. Compares only the primary subtag (`ja`
-/// / `en`) so any locale flavour (`ja-JP`, `en-US`, `en-GB`...) counts
-/// as a match. Returns `None` when no voice's primary subtag matches —
-/// the caller then falls back to the backend default.
-fn pick_voice(tts: &Tts, lang: &Language) -> Option<Voice> {
+/// Best-match voice for `lang`: the voice called `preferred` if one is
+/// installed, else the first whose primary subtag matches (`ja` / `en`,
+/// so any locale flavour — `ja-JP`, `en-US`, `en-GB`... — counts).
+/// Returns `None` when neither matches — the caller then falls back to
+/// the backend default.
+fn pick_voice(tts: &Tts, preferred: Option<&str>, lang: &Language) -> Option<Voice> {
     let target = lang.code();
     let voices = tts.voices().ok()?;
-    voices
-        .into_iter()
-        .find(|v| v.language().primary_language() == target)
+    let named = preferred.and_then(|name| {
+        voices
+            .iter()
+            .find(|v| v.name().eq_ignore_ascii_case(name))
+            .cloned()
+    });
+    named.or_else(|| {
+        voices
+            .into_iter()
+            .find(|v| v.language().primary_language() == target)
+    })
+}
+
+/// Map a `tts_rate` / `tts_pitch` multiplier onto the backend's own
+/// scale. Backends disagree on units (speech-dispatcher is -100..100 with
+/// 0 as normal, AVFoundation 0.1..2.0 around 0.5), so 0.5 maps to `min`,
+/// 1.0 to `normal` and 2.0 to `max`, linearly in between.
+fn backend_scale(multiplier: f32, min: f32, normal: f32, max: f32) -> f32 {
+    let m = multiplier.clamp(0.5, 2.0);
+    if m < 1.0 {
+        normal - (normal - min) * (1.0 - m) / 0.5
+    } else {
+        normal + (max - normal) * (m - 1.0)
+    }
 }
 
 #[cfg(test)]
@@ -83,7 +253,17 @@ mod tests {
     // unit tests live in `game::listening` where the blind-input judge
     // is pure. This module is intentionally test-light: integration
     // testing for actual audio is manual on macOS / Linux.
+    use super::*;
 
     #[test]
     fn module_compiles() {}
+
+    #[test]
+    fn backend_scale_maps_the_multiplier_through_normal() {
+        assert_eq!(backend_scale(1.0, -100.0, 0.0, 100.0), 0.0);
+        assert_eq!(backend_scale(0.5, -100.0, 0.0, 100.0), -100.0);
+        assert_eq!(backend_scale(2.0, -100.0, 0.0, 100.0), 100.0);
+        assert_eq!(backend_scale(1.5, 0.1, 0.5, 2.0), 1.25);
+        assert_eq!(backend_scale(9.0, 0.1, 0.5, 2.0), 2.0);
+    }
 }
@@ -0,0 +1,426 @@
+//! Minimal RIFF/WAVE codec (#41, #42).
+//!
+//! `rodio` is built without its decoder features, so the speech backends
+//! that play audio files (pre-rendered prompts, local engines that write
+//! a WAV) decode here instead. Integer PCM at 8/16/24/32 bits and 32-bit
+//! float are supported, including the `WAVE_FORMAT_EXTENSIBLE` wrapper
+//! espeak-ng and piper emit, plus mono IMA ADPCM; anything else is an
+//! error.
+//!
+//! Two encoders go the other way. `render-listening` stores prompts as
+//! IMA ADPCM at 4 bits per sample (about a quarter of 16-bit PCM), in a
+//! format any audio player opens and this module can read back without
+//! a decoder dependency. `render-cues` writes 32-bit float, which reads
+//! back bit for bit so renders can be compared sample by sample.
+
+/// Decoded audio, interleaved, in `-1.0..=1.0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pcm {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_FLOAT: u16 = 3;
+const FORMAT_IMA_ADPCM: u16 = 0x11;
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Bytes per ADPCM block we write: a 4-byte header then 1016 samples'
+/// worth of nibbles.
+#[allow(dead_code)] // the encoder is only called from `render-listening`
+const ADPCM_BLOCK_ALIGN: u16 = 512;
+
+const IMA_STEPS: [i32; 89] = [
+    7, 8, 9, 10, 11, 12, 13, 14, 16, 17, 19, 21, 23, 25, 28, 31, 34, 37, 41, 45, 50, 55, 60, 66,
+    73, 80, 88, 97, 107, 118, 130, 143, 157, 173, 190, 209, 230, 253, 279, 307, 337, 371, 408, 449,
+    494, 544, 598, 658, 724, 796, 876, 963, 1060, 1166, 1282, 1411, 1552, 1707, 1878, 2066, 2272,
+    2499, 2749, 3024, 3327, 3660, 4026, 4428, 4871, 5358, 5894, 6484, 7132, 7845, 8630, 9493,
+    10442, 11487, 12635, 13899, 15289, 16818, 18500, 20350, 22385, 24623, 27086, 29794, 32767,
+];
+
+const IMA_INDEX: [i32; 16] = [-1, -1, -1, -1, 2, 4, 6, 8, -1, -1, -1, -1, 2, 4, 6, 8];
+
+/// The `fmt ` chunk fields this module looks at.
+struct Format {
+    tag: u16,
+    channels: u16,
+    sample_rate: u32,
+    block_align: u16,
+    bits: u16,
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Pcm, Box<dyn std::error::Error>> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("not a RIFF/WAVE file".into());
+    }
+    let mut format: Option<Format> = None;
+    let mut frames: Option<usize> = None;
+    let mut data: Option<&[u8]> = None;
+    let mut rest = &bytes[12..];
+    while rest.len() >= 8 {
+        let id = &rest[0..4];
+        let size = u32::from_le_bytes([rest[4], rest[5], rest[6], rest[7]]) as usize;
+        // Streaming writers (piper to a pipe) leave the data size at
+        // 0 or u32::MAX; take whatever follows.
+        let body = &rest[8..];
+        let body = &body[..size.min(body.len())];
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let mut tag = u16::from_le_bytes([body[0], body[1]]);
+                if tag == FORMAT_EXTENSIBLE && body.len() >= 26 {
+                    tag = u16::from_le_bytes([body[24], body[25]]);
+                }
+                format = Some(Format {
+                    tag,
+                    channels: u16::from_le_bytes([body[2], body[3]]),
+                    sample_rate: u32::from_le_bytes([body[4], body[5], body[6], body[7]]),
+                    block_align: u16::from_le_bytes([body[12], body[13]]),
+                    bits: u16::from_le_bytes([body[14], body[15]]),
+                });
+            }
+            b"fact" if body.len() >= 4 => {
+                frames = Some(u32::from_le_bytes([body[0], body[1], body[2], body[3]]) as usize);
+            }
+            b"data" => {
+                data = Some(if size == 0 || size == u32::MAX as usize {
+                    &rest[8..]
+                } else {
+                    body
+                });
+                break;
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        let next = 8 + size + (size & 1);
+        if next > rest.len() {
+            break;
+        }
+        rest = &rest[next..];
+    }
+    let Format {
+        tag,
+        channels,
+        sample_rate,
+        block_align,
+        bits,
+    } = format.ok_or("missing fmt chunk")?;
+    let data = data.ok_or("missing data chunk")?;
+    if channels == 0 {
+        return Err("zero channels".into());
+    }
+    let samples = match (tag, bits) {
+        (FORMAT_IMA_ADPCM, 4) if channels == 1 && block_align > 4 => {
+            let mut samples = decode_ima_adpcm(data, usize::from(block_align));
+            if let Some(frames) = frames {
+                samples.truncate(frames);
+            }
+            samples
+        }
+        (FORMAT_PCM, 8) => data
+            .iter()
+            .map(|&b| (f32::from(b) - 128.0) / 128.0)
+            .collect(),
+        (FORMAT_PCM, 16) => data
+            .chunks_exact(2)
+            .map(|s| f32::from(i16::from_le_bytes([s[0], s[1]])) / 32_768.0)
+            .collect(),
+        (FORMAT_PCM, 24) => data
+            .chunks_exact(3)
+            .map(|s| (i32::from_le_bytes([0, s[0], s[1], s[2]]) >> 8) as f32 / 8_388_608.0)
+            .collect(),
+        (FORMAT_PCM, 32) => data
+            .chunks_exact(4)
+            .map(|s| i32::from_le_bytes([s[0], s[1], s[2], s[3]]) as f32 / 2_147_483_648.0)
+            .collect(),
+        (FORMAT_FLOAT, 32) => data
+            .chunks_exact(4)
+            .map(|s| f32::from_le_bytes([s[0], s[1], s[2], s[3]]))
+            .collect(),
+        _ => return Err(format!("unsupported WAV encoding (format {tag}, {bits}-bit)").into()),
+    };
+    Ok(Pcm {
+        channels,
+        sample_rate,
+        samples,
+    })
+}
+
+/// Mix interleaved channels down to one.
+#[allow(dead_code)]
+pub fn downmix(pcm: Pcm) -> Pcm {
+    if pcm.channels <= 1 {
+        return pcm;
+    }
+    let channels = usize::from(pcm.channels);
+    let samples = pcm
+        .samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+    Pcm {
+        channels: 1,
+        sample_rate: pcm.sample_rate,
+        samples,
+    }
+}
+
+/// Write `pcm` as a mono IMA ADPCM WAV (stereo input is downmixed).
+#[allow(dead_code)]
+pub fn encode_ima_adpcm(pcm: Pcm) -> Vec<u8> {
+    let pcm = downmix(pcm);
+    let block_align = usize::from(ADPCM_BLOCK_ALIGN);
+    let per_block = (block_align - 4) * 2 + 1;
+    let mut data = Vec::new();
+    // Seed the step size from the opening slope instead of the smallest
+    // step, so a loud first syllable isn't smeared while it adapts.
+    let mut index = match pcm.samples.get(..2) {
+        Some([a, b]) => {
+            let slope = (i32::from(to_i16(*b)) - i32::from(to_i16(*a))).abs();
+            IMA_STEPS
+                .iter()
+                .position(|&step| step >= slope)
+                .unwrap_or(88) as i32
+        }
+        _ => 0,
+    };
+    for block in pcm.samples.chunks(per_block) {
+        let first = to_i16(block[0]);
+        let mut predictor = i32::from(first);
+        data.extend_from_slice(&first.to_le_bytes());
+        data.push(index as u8);
+        data.push(0);
+        // A short last block is padded with silence; `fact` holds the
+        // real length.
+        let rest = block[1..]
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(0.0))
+            .take(per_block - 1);
+        let mut low: Option<u8> = None;
+        for sample in rest {
+            let nibble = ima_encode_step(i32::from(to_i16(sample)), &mut predictor, &mut index);
+            match low.take() {
+                None => low = Some(nibble),
+                Some(first) => data.push(first | (nibble << 4)),
+            }
+        }
+    }
+
+    let samples_per_block = per_block as u16;
+    let byte_rate = (u64::from(pcm.sample_rate) * block_align as u64 / per_block as u64) as u32;
+    let mut out = Vec::with_capacity(data.len() + 60);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(4 + 28 + 12 + 8 + data.len() as u32).to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&20u32.to_le_bytes());
+    out.extend_from_slice(&FORMAT_IMA_ADPCM.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes());
+    out.extend_from_slice(&pcm.sample_rate.to_le_bytes());
+    out.extend_from_slice(&byte_rate.to_le_bytes());
+    out.extend_from_slice(&ADPCM_BLOCK_ALIGN.to_le_bytes());
+    out.extend_from_slice(&4u16.to_le_bytes());
+    out.extend_from_slice(&2u16.to_le_bytes());
+    out.extend_from_slice(&samples_per_block.to_le_bytes());
+    out.extend_from_slice(b"fact");
+    out.extend_from_slice(&4u32.to_le_bytes());
+    out.extend_from_slice(&(pcm.samples.len() as u32).to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(&data);
+    out
+}
+
+/// Write `pcm` as 32-bit float PCM, lossless.
+#[allow(dead_code)] // only called from `render-cues`
+pub fn encode_float(pcm: &Pcm) -> Vec<u8> {
+    let block_align = pcm.channels * 4;
+    let data_len = pcm.samples.len() as u32 * 4;
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&FORMAT_FLOAT.to_le_bytes());
+    out.extend_from_slice(&pcm.channels.to_le_bytes());
+    out.extend_from_slice(&pcm.sample_rate.to_le_bytes());
+    out.extend_from_slice(&(pcm.sample_rate * u32::from(block_align)).to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&32u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for sample in &pcm.samples {
+        out.extend_from_slice(&sample.to_le_bytes());
+    }
+    out
+}
+
+#[allow(dead_code)]
+fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * 32_767.0).round() as i16
+}
+
+/// Quantise one sample against the running predictor, updating it the
+/// same way the decoder will.
+#[allow(dead_code)]
+fn ima_encode_step(sample: i32, predictor: &mut i32, index: &mut i32) -> u8 {
+    let step = IMA_STEPS[*index as usize];
+    let mut diff = sample - *predictor;
+    let mut nibble = 0u8;
+    if diff < 0 {
+        nibble = 8;
+        diff = -diff;
+    }
+    if diff >= step {
+        nibble |= 4;
+        diff -= step;
+    }
+    if diff >= step >> 1 {
+        nibble |= 2;
+        diff -= step >> 1;
+    }
+    if diff >= step >> 2 {
+        nibble |= 1;
+    }
+    ima_apply(nibble, predictor, index);
+    nibble
+}
+
+fn ima_apply(nibble: u8, predictor: &mut i32, index: &mut i32) {
+    let step = IMA_STEPS[*index as usize];
+    let mut delta = step >> 3;
+    if nibble & 4 != 0 {
+        delta += step;
+    }
+    if nibble & 2 != 0 {
+        delta += step >> 1;
+    }
+    if nibble & 1 != 0 {
+        delta += step >> 2;
+    }
+    *predictor = if nibble & 8 != 0 {
+        *predictor - delta
+    } else {
+        *predictor + delta
+    }
+    .clamp(i32::from(i16::MIN), i32::from(i16::MAX));
+    *index = (*index + IMA_INDEX[usize::from(nibble)]).clamp(0, 88);
+}
+
+fn decode_ima_adpcm(data: &[u8], block_align: usize) -> Vec<f32> {
+    let mut samples = Vec::new();
+    for block in data.chunks(block_align).filter(|b| b.len() > 4) {
+        let mut predictor = i32::from(i16::from_le_bytes([block[0], block[1]]));
+        let mut index = i32::from(block[2]).clamp(0, 88);
+        samples.push(predictor as f32 / 32_768.0);
+        for byte in &block[4..] {
+            for nibble in [byte & 0x0F, byte >> 4] {
+                ima_apply(nibble, &mut predictor, &mut index);
+                samples.push(predictor as f32 / 32_768.0);
+            }
+        }
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(format: u16, bits: u16, channels: u16, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        out.extend_from_slice(&format.to_le_bytes());
+        out.extend_from_slice(&channels.to_le_bytes());
+        out.extend_from_slice(&22_050u32.to_le_bytes());
+        let block = channels * bits / 8;
+        out.extend_from_slice(&(22_050 * u32::from(block)).to_le_bytes());
+        out.extend_from_slice(&block.to_le_bytes());
+        out.extend_from_slice(&bits.to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+
+    #[test]
+    fn decodes_16_bit_pcm() {
+        let data: Vec<u8> = [0i16, 16_384, -32_768]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let pcm = decode(&wav(FORMAT_PCM, 16, 1, &data)).expect("decode");
+        assert_eq!(pcm.sample_rate, 22_050);
+        assert_eq!(pcm.samples, [0.0, 0.5, -1.0]);
+    }
+
+    #[test]
+    fn decodes_float_and_8_bit() {
+        let data: Vec<u8> = [0.25f32, -0.5]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let pcm = decode(&wav(FORMAT_FLOAT, 32, 2, &data)).expect("decode");
+        assert_eq!(pcm.channels, 2);
+        assert_eq!(pcm.samples, [0.25, -0.5]);
+        let pcm = decode(&wav(FORMAT_PCM, 8, 1, &[128, 192])).expect("decode");
+        assert_eq!(pcm.samples, [0.0, 0.5]);
+    }
+
+    #[test]
+    fn ima_adpcm_round_trips_close_to_the_source() {
+        let source: Vec<f32> = (0..3_000).map(|i| (i as f32 * 0.05).sin() * 0.5).collect();
+        let pcm = Pcm {
+            channels: 1,
+            sample_rate: 22_050,
+            samples: source.clone(),
+        };
+        let bytes = encode_ima_adpcm(pcm);
+        // 3 blocks of 512 bytes instead of 6000 bytes of 16-bit PCM.
+        assert!(bytes.len() < 1_700, "{}", bytes.len());
+        let decoded = decode(&bytes).expect("decode");
+        assert_eq!(decoded.sample_rate, 22_050);
+        assert_eq!(
+            decoded.samples.len(),
+            source.len(),
+            "fact trims the padding"
+        );
+        let worst = source
+            .iter()
+            .zip(&decoded.samples)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(worst < 0.05, "worst error {worst}");
+    }
+
+    #[test]
+    fn float_round_trips_exactly() {
+        let pcm = Pcm {
+            channels: 1,
+            sample_rate: 44_100,
+            samples: vec![0.0, 0.123_456_79, -1.0, 0.999],
+        };
+        assert_eq!(decode(&encode_float(&pcm)).expect("decode"), pcm);
+    }
+
+    #[test]
+    fn downmix_averages_frames() {
+        let pcm = downmix(Pcm {
+            channels: 2,
+            sample_rate: 8_000,
+            samples: vec![0.5, -0.5, 1.0, 0.0],
+        });
+        assert_eq!(pcm.channels, 1);
+        assert_eq!(pcm.samples, [0.0, 0.5]);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(decode(b"OggS\0\0\0\0\0\0\0\0").is_err());
+        assert!(decode(&wav(2, 4, 1, &[0, 0])).is_err(), "ADPCM");
+    }
+}
@@ -0,0 +1,658 @@
+//! Question-bank maintenance tool.
+//!
+//! Promotes staged questions (`data/staging/questions_<genre>.json`) into the
+//! main banks and keeps them tidy, replacing the ad-hoc Python scripts:
+//!
+//! - `merge`: append / update questions from other files into a base bank
+//! - `diff`: list added / removed / changed ids between two banks
+//! - `dedupe`: find duplicate ids and near-identical question texts
+//! - `renumber`: reassign sequential ids
+//! - `split-by-genre`: write one `questions_<genre>.json` per genre
+//!
+//! Everything operates on `Vec<Question>` and is written back through
+//! `Pack::to_json`, which reproduces the bundled files' key order, so a
+//! no-op run leaves the files byte-identical and real changes show up as
+//! minimal git diffs.
+
+#[path = "../types.rs"]
+#[allow(dead_code)]
+mod types;
+
+#[path = "../io/pack.rs"]
+#[allow(dead_code)]
+mod pack;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use pack::Pack;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::ExitCode;
+use types::Question;
+
+/// 問題バンクの統合・差分・重複検出・採番・ジャンル分割
+#[derive(Parser, Debug)]
+#[command(name = "bank", about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// incoming の問題を base に統合する
+    Merge {
+        /// 統合先のバンク
+        base: String,
+        /// 取り込むファイル（複数可）
+        #[arg(required = true)]
+        incoming: Vec<String>,
+        /// 書き出し先。省略時は base を上書き
+        #[arg(long)]
+        out: Option<String>,
+        /// 同じ id で内容が異なる場合の扱い
+        #[arg(long, value_enum, default_value_t = OnConflict::Keep)]
+        on_conflict: OnConflict,
+    },
+    /// 2 つのバンクの差分（追加 / 削除 / 変更 id）を表示する
+    Diff { old: String, new: String },
+    /// 重複 id と、ほぼ同一の問題文を検出する
+    Dedupe {
+        file: String,
+        /// 類似度のしきい値 (0.0-1.0)。正規化後の編集距離で判定
+        #[arg(long, default_value_t = 0.9)]
+        threshold: f64,
+        /// 検出した重複（後ろ側）を削除して書き出す
+        #[arg(long)]
+        apply: bool,
+        /// --apply 時の書き出し先。省略時は上書き
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// id を連番で振り直す
+    Renumber {
+        file: String,
+        #[arg(long, default_value = "q")]
+        prefix: String,
+        #[arg(long, default_value_t = 1)]
+        start: u32,
+        /// 数字部分の桁数
+        #[arg(long, default_value_t = 4)]
+        width: usize,
+        /// 書き出し先。省略時は上書き
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// ジャンルごとに questions_<genre>.json を書き出す
+    SplitByGenre {
+        file: String,
+        #[arg(long, default_value = "data/staging")]
+        out_dir: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OnConflict {
+    /// base 側を残して警告する
+    Keep,
+    /// incoming 側で置き換える
+    Replace,
+    /// incoming 側に新しい id を振って追加する
+    Renumber,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("bank: {e}");
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn run(command: Command) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    match command {
+        Command::Merge {
+            base,
+            incoming,
+            out,
+            on_conflict,
+        } => {
+            let mut bank = load(&base)?;
+            let mut summary = MergeSummary::default();
+            for path in &incoming {
+                let questions = load(path)?;
+                summary.add(merge(&mut bank, questions, on_conflict));
+            }
+            for id in &summary.conflicts {
+                eprintln!("conflict: {id} differs from base (kept base; see --on-conflict)");
+            }
+            save(out.as_deref().unwrap_or(&base), &bank)?;
+            println!(
+                "merge: {} added, {} replaced, {} renumbered, {} unchanged, {} conflict(s)",
+                summary.added,
+                summary.replaced,
+                summary.renumbered,
+                summary.unchanged,
+                summary.conflicts.len()
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Diff { old, new } => {
+            let entries = diff(&load(&old)?, &load(&new)?);
+            for entry in &entries {
+                println!("{entry}");
+            }
+            // Like `diff(1)`: exit 1 when the banks differ.
+            Ok(if entries.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::from(1)
+            })
+        }
+        Command::Dedupe {
+            file,
+            threshold,
+            apply,
+            out,
+        } => {
+            let mut bank = load(&file)?;
+            let findings = find_duplicates(&bank, threshold);
+            for f in &findings {
+                println!("{}", f.describe());
+            }
+            if apply && !findings.is_empty() {
+                let drop: HashSet<usize> = findings.iter().map(|f| f.later).collect();
+                let before = bank.len();
+                bank = bank
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| !drop.contains(i))
+                    .map(|(_, q)| q)
+                    .collect();
+                save(out.as_deref().unwrap_or(&file), &bank)?;
+                println!("dedupe: removed {} question(s)", before - bank.len());
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Renumber {
+            file,
+            prefix,
+            start,
+            width,
+            out,
+        } => {
+            let mut bank = load(&file)?;
+            renumber(&mut bank, &prefix, start, width);
+            save(out.as_deref().unwrap_or(&file), &bank)?;
+            println!("renumber: {} question(s)", bank.len());
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::SplitByGenre { file, out_dir } => {
+            let bank = load(&file)?;
+            for (genre, questions) in split_by_genre(bank) {
+                let path = Path::new(&out_dir).join(format!("questions_{genre}.json"));
+                let path = path.to_string_lossy();
+                save(&path, &questions)?;
+                println!("{path}: {} question(s)", questions.len());
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn load(path: &str) -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    if !Path::new(path).exists() {
+        return Err(format!("{path}: no such file").into());
+    }
+    Pack::load(path)
+}
+
+fn save(path: &str, questions: &[Question]) -> Result<(), Box<dyn std::error::Error>> {
+    Pack::save(path, questions)
+}
+
+// ---------------------------------------------------------------------------
+// merge
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct MergeSummary {
+    added: usize,
+    replaced: usize,
+    renumbered: usize,
+    unchanged: usize,
+    conflicts: Vec<String>,
+}
+
+impl MergeSummary {
+    fn add(&mut self, other: MergeSummary) {
+        self.added += other.added;
+        self.replaced += other.replaced;
+        self.renumbered += other.renumbered;
+        self.unchanged += other.unchanged;
+        self.conflicts.extend(other.conflicts);
+    }
+}
+
+/// Merge `incoming` into `base` in input order. New ids are appended;
+/// identical questions are no-ops; differing questions with the same id
+/// follow `on_conflict`.
+fn merge(
+    base: &mut Vec<Question>,
+    incoming: Vec<Question>,
+    on_conflict: OnConflict,
+) -> MergeSummary {
+    let mut summary = MergeSummary::default();
+    for question in incoming {
+        let Some(pos) = base.iter().position(|q| q.id == question.id) else {
+            base.push(question);
+            summary.added += 1;
+            continue;
+        };
+        if base[pos] == question {
+            summary.unchanged += 1;
+            continue;
+        }
+        match on_conflict {
+            OnConflict::Keep => summary.conflicts.push(question.id),
+            OnConflict::Replace => {
+                base[pos] = question;
+                summary.replaced += 1;
+            }
+            OnConflict::Renumber => {
+                let mut question = question;
+                question.id = next_free_id(base, &question.id);
+                base.push(question);
+                summary.renumbered += 1;
+            }
+        }
+    }
+    summary
+}
+
+/// Split `q0042` into (`q`, 4 digits). Ids without a numeric tail get an
+/// empty width so callers fall back to the default.
+fn split_id(id: &str) -> (&str, usize) {
+    let digits = id.chars().rev().take_while(|c| c.is_ascii_digit()).count();
+    (&id[..id.len() - digits], digits)
+}
+
+/// Next id after the highest one in `bank` sharing `like`'s prefix.
+fn next_free_id(bank: &[Question], like: &str) -> String {
+    let (prefix, width) = split_id(like);
+    let width = width.max(4);
+    let max = bank
+        .iter()
+        .filter_map(|q| q.id.strip_prefix(prefix))
+        .filter_map(|n| n.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+    format!("{prefix}{:0width$}", max + 1)
+}
+
+// ---------------------------------------------------------------------------
+// diff
+// ---------------------------------------------------------------------------
+
+/// `+ id`, `- id`, or `~ id: field, field` lines, in `new`'s order for
+/// additions / changes followed by removals in `old`'s order.
+fn diff(old: &[Question], new: &[Question]) -> Vec<String> {
+    let old_by_id: HashMap<&str, &Question> = old.iter().map(|q| (q.id.as_str(), q)).collect();
+    let new_ids: HashSet<&str> = new.iter().map(|q| q.id.as_str()).collect();
+    let mut out = Vec::new();
+    for q in new {
+        match old_by_id.get(q.id.as_str()) {
+            None => out.push(format!("+ {}", q.id)),
+            Some(before) if *before != q => {
+                out.push(format!(
+                    "~ {}: {}",
+                    q.id,
+                    changed_fields(before, q).join(", ")
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    for q in old {
+        if !new_ids.contains(q.id.as_str()) {
+            out.push(format!("- {}", q.id));
+        }
+    }
+    out
+}
+
+fn changed_fields(a: &Question, b: &Question) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if a.genre != b.genre {
+        fields.push("genre");
+    }
+    if a.question_text != b.question_text {
+        fields.push("question_text");
+    }
+    if a.question_text_reading != b.question_text_reading {
+        fields.push("question_text_reading");
+    }
+    if a.choices != b.choices {
+        fields.push("choices");
+    }
+    if a.correct_answer_index != b.correct_answer_index {
+        fields.push("correct_answer_index");
+    }
+    if a.image_path != b.image_path {
+        fields.push("image_path");
+    }
+    if a.ja_reviewed != b.ja_reviewed {
+        fields.push("ja_reviewed");
+    }
+    if a.explanation != b.explanation {
+        fields.push("explanation");
+    }
+    if a.source != b.source {
+        fields.push("source");
+    }
+    if a.reference != b.reference {
+        fields.push("reference");
+    }
+    fields
+}
+
+// ---------------------------------------------------------------------------
+// dedupe
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+struct Duplicate {
+    earlier: usize,
+    later: usize,
+    earlier_id: String,
+    later_id: String,
+    /// `None` for a duplicate id, `Some((lang, similarity))` for text.
+    text_match: Option<(String, f64)>,
+}
+
+impl Duplicate {
+    fn describe(&self) -> String {
+        match &self.text_match {
+            None => format!(
+                "[duplicate id] {} (entries #{} and #{})",
+                self.later_id, self.earlier, self.later
+            ),
+            Some((lang, similarity)) => format!(
+                "[near-identical {lang} {similarity:.2}] {} ~ {}",
+                self.earlier_id, self.later_id
+            ),
+        }
+    }
+}
+
+/// Report every later entry that duplicates an earlier one, either by id
+/// or by question text in some language at or above `threshold`. Each
+/// later entry is reported once (against the first earlier match).
+///
+/// A text match also requires the same correct answer: templated
+/// questions ("chemical symbol for gold?" / "… for silver?") are
+/// near-identical as text but ask different things.
+fn find_duplicates(bank: &[Question], threshold: f64) -> Vec<Duplicate> {
+    let normalized: Vec<HashMap<&str, Vec<char>>> = bank
+        .iter()
+        .map(|q| {
+            q.question_text
+                .iter()
+                .map(|(lang, text)| (lang.as_str(), normalize_text(text)))
+                .collect()
+        })
+        .collect();
+
+    let mut out = Vec::new();
+    for later in 0..bank.len() {
+        for earlier in 0..later {
+            let finding = if bank[earlier].id == bank[later].id {
+                Some(None)
+            } else if same_answer(&bank[earlier], &bank[later]) {
+                text_match(&normalized[earlier], &normalized[later], threshold).map(Some)
+            } else {
+                None
+            };
+            if let Some(text_match) = finding {
+                out.push(Duplicate {
+                    earlier,
+                    later,
+                    earlier_id: bank[earlier].id.clone(),
+                    later_id: bank[later].id.clone(),
+                    text_match,
+                });
+                break;
+            }
+        }
+    }
+    out
+}
+
+fn same_answer(a: &Question, b: &Question) -> bool {
+    let (Some(x), Some(y)) = (
+        a.choices.get(a.correct_answer_index),
+        b.choices.get(b.correct_answer_index),
+    ) else {
+        return false;
+    };
+    x.labels.iter().any(|(lang, label)| {
+        y.labels
+            .get(lang)
+            .is_some_and(|other| normalize_text(other) == normalize_text(label))
+    })
+}
+
+fn text_match(
+    a: &HashMap<&str, Vec<char>>,
+    b: &HashMap<&str, Vec<char>>,
+    threshold: f64,
+) -> Option<(String, f64)> {
+    let mut langs: Vec<&&str> = a.keys().collect();
+    langs.sort();
+    for lang in langs {
+        let (Some(x), Some(y)) = (a.get(*lang), b.get(*lang)) else {
+            continue;
+        };
+        let longest = x.len().max(y.len());
+        if longest == 0 {
+            continue;
+        }
+        // Cheap reject before the O(n*m) distance: the length gap alone
+        // bounds the best achievable similarity.
+        let gap = x.len().abs_diff(y.len());
+        if 1.0 - (gap as f64 / longest as f64) < threshold {
+            continue;
+        }
+        let similarity = 1.0 - levenshtein(x, y) as f64 / longest as f64;
+        if similarity >= threshold {
+            return Some((lang.to_string(), similarity));
+        }
+    }
+    None
+}
+
+/// Lowercase and drop whitespace / punctuation (ASCII and full-width) so
+/// `水の化学式は?` and `水の化学式は？` compare equal.
+fn normalize_text(text: &str) -> Vec<char> {
+    text.chars()
+        .filter(|c| !c.is_whitespace() && !c.is_ascii_punctuation())
+        .filter(|c| {
+            !matches!(
+                c,
+                '？' | '！' | '。' | '、' | '「' | '」' | '・' | '（' | '）'
+            )
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+// ---------------------------------------------------------------------------
+// renumber / split-by-genre
+// ---------------------------------------------------------------------------
+
+fn renumber(bank: &mut [Question], prefix: &str, start: u32, width: usize) {
+    for (offset, question) in bank.iter_mut().enumerate() {
+        question.id = format!("{prefix}{:0width$}", start + offset as u32);
+    }
+}
+
+/// Group by genre, preserving first-seen genre order and question order.
+fn split_by_genre(bank: Vec<Question>) -> Vec<(String, Vec<Question>)> {
+    let mut groups: Vec<(String, Vec<Question>)> = Vec::new();
+    for question in bank {
+        match groups.iter_mut().find(|(g, _)| *g == question.genre) {
+            Some((_, list)) => list.push(question),
+            None => groups.push((question.genre.clone(), vec![question])),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Choice;
+
+    fn q(id: &str, genre: &str, en: &str) -> Question {
+        q_with_answer(id, genre, en, "answer")
+    }
+
+    fn q_with_answer(id: &str, genre: &str, en: &str, answer: &str) -> Question {
+        Question {
+            id: id.into(),
+            genre: genre.into(),
+            question_text: HashMap::from([("en".to_string(), en.to_string())]),
+            question_text_reading: HashMap::new(),
+            choices: vec![Choice {
+                labels: HashMap::from([("en".to_string(), answer.to_string())]),
+                ja_typings: Vec::new(),
+            }],
+            correct_answer_index: 0,
+            image_path: None,
+            ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
+        }
+    }
+
+    #[test]
+    fn merge_appends_new_and_skips_identical() {
+        let mut base = vec![q("q0001", "a", "One?")];
+        let s = merge(
+            &mut base,
+            vec![q("q0001", "a", "One?"), q("q0002", "a", "Two?")],
+            OnConflict::Keep,
+        );
+        assert_eq!((s.added, s.unchanged), (1, 1));
+        assert_eq!(base.len(), 2);
+    }
+
+    #[test]
+    fn merge_conflict_policies() {
+        let incoming = vec![q("q0001", "a", "Changed?")];
+
+        let mut base = vec![q("q0001", "a", "One?")];
+        let s = merge(&mut base, incoming.clone(), OnConflict::Keep);
+        assert_eq!(s.conflicts, vec!["q0001".to_string()]);
+        assert_eq!(base[0].question_text["en"], "One?");
+
+        let mut base = vec![q("q0001", "a", "One?")];
+        merge(&mut base, incoming.clone(), OnConflict::Replace);
+        assert_eq!(base[0].question_text["en"], "Changed?");
+
+        let mut base = vec![q("q0001", "a", "One?"), q("q0009", "a", "Nine?")];
+        merge(&mut base, incoming, OnConflict::Renumber);
+        assert_eq!(base[2].id, "q0010");
+    }
+
+    #[test]
+    fn diff_lists_added_removed_changed() {
+        let old = vec![q("q1", "a", "One?"), q("q2", "a", "Two?")];
+        let new = vec![q("q1", "b", "One?"), q("q3", "a", "Three?")];
+        assert_eq!(diff(&old, &new), vec!["~ q1: genre", "+ q3", "- q2"]);
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn diff_names_source_and_reference_apart() {
+        let old = q("q1", "a", "One?");
+        let mut cited = old.clone();
+        cited.reference = Some("https://example.org".into());
+        assert_eq!(changed_fields(&old, &cited), ["reference"]);
+        cited.source = Some("Factbook".into());
+        assert_eq!(changed_fields(&old, &cited), ["source", "reference"]);
+    }
+
+    #[test]
+    fn dedupe_finds_duplicate_ids_and_near_identical_text() {
+        let bank = vec![
+            q("q1", "a", "What is the capital of France?"),
+            q("q2", "a", "What is the capital of Spain?"),
+            q("q3", "a", "what is the capital of france"),
+            q("q1", "a", "Unrelated"),
+        ];
+        let found = find_duplicates(&bank, 0.9);
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].earlier, found[0].later), (0, 2));
+        assert!(found[0]
+            .describe()
+            .starts_with("[near-identical en 1.00] q1 ~ q3"));
+        assert_eq!(found[1].text_match, None);
+        assert_eq!(found[1].later, 3);
+    }
+
+    #[test]
+    fn dedupe_ignores_templated_questions_with_different_answers() {
+        let bank = vec![
+            q_with_answer("q1", "a", "What is the chemical symbol for gold?", "Au"),
+            q_with_answer("q2", "a", "What is the chemical symbol for gold", "Au"),
+            q_with_answer("q3", "a", "What is the chemical symbol for golf?", "Gf"),
+        ];
+        let found = find_duplicates(&bank, 0.9);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].later_id, "q2");
+    }
+
+    #[test]
+    fn normalize_ignores_full_width_punctuation() {
+        assert_eq!(
+            normalize_text("水の化学式は？"),
+            normalize_text("水の化学式は?")
+        );
+    }
+
+    #[test]
+    fn renumber_and_split() {
+        let mut bank = vec![q("x", "b", "1"), q("y", "a", "2"), q("z", "b", "3")];
+        renumber(&mut bank, "q", 7, 4);
+        assert_eq!(bank[2].id, "q0009");
+        let groups = split_by_genre(bank);
+        assert_eq!(groups[0].0, "b");
+        assert_eq!(groups[0].1.len(), 2);
+        assert_eq!(groups[1].0, "a");
+    }
+
+    #[test]
+    fn next_free_id_keeps_prefix_and_width() {
+        let bank = vec![q("q0735", "a", "x"), q("c0002", "a", "y")];
+        assert_eq!(next_free_id(&bank, "q0001"), "q0736");
+        assert_eq!(next_free_id(&bank, "c01"), "c0003");
+    }
+}
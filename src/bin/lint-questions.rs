@@ -346,6 +346,9 @@ mod tests {
             correct_answer_index: 0,
             image_path: None,
             ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
         };
         let errors = super::find_ja_typing_errors("data/questions_ja.json", &[question]);
         assert!(
@@ -377,6 +380,9 @@ mod tests {
             correct_answer_index: 0,
             image_path: None,
             ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
         };
         let errors = super::find_ja_typing_errors("data/questions_ja.json", &[question]);
         assert!(
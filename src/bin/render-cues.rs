@@ -0,0 +1,312 @@
+//! Render the synthesized sound cues to WAV files.
+//!
+//! Tuning `audio::cues` otherwise means playing the game with speakers
+//! on. This tool runs every cue through every sound theme offline,
+//! writes each buffer as a 32-bit float WAV and prints the notes and
+//! envelope each one was built from:
+//!
+//! ```text
+//! <out>/chip/correct.wav
+//! <out>/soft/level-up.wav
+//! ```
+//!
+//! Float WAVs read back bit for bit, so `--check` re-synthesizes the
+//! same set and compares it sample by sample against a directory written
+//! earlier. Render before a change, then check after it to see exactly
+//! which cues moved.
+
+#[path = "../audio/cues.rs"]
+#[allow(dead_code)]
+mod cues;
+
+#[path = "../audio/wav.rs"]
+#[allow(dead_code)]
+mod wav;
+
+use clap::Parser;
+use cues::{Cue, SoundTheme, Step, SAMPLE_RATE};
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+/// 効果音をテーマごとに WAV へ書き出し、エンベロープを表示する
+#[derive(Parser, Debug)]
+#[command(name = "render-cues", about, long_about = None)]
+struct Cli {
+    /// 対象テーマ (classic / chip / soft / silent-keys、複数可)。省略時はすべて
+    #[arg(long = "theme", value_parser = parse_theme)]
+    themes: Vec<SoundTheme>,
+    /// 対象の効果音 (correct / level-up など、複数可)。省略時はすべて
+    #[arg(long = "cue", value_parser = parse_cue)]
+    cues: Vec<Cue>,
+    /// 書き出し先 (--check では比較元)
+    #[arg(long, default_value = "target/cues")]
+    out: String,
+    /// 書き出さずに、既存の WAV とサンプル単位で比較する
+    #[arg(long)]
+    check: bool,
+    /// エンベロープの表示を省く
+    #[arg(long, short)]
+    quiet: bool,
+}
+
+fn parse_theme(name: &str) -> Result<SoundTheme, String> {
+    SoundTheme::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = SoundTheme::ALL.iter().map(|t| t.name()).collect();
+        format!(
+            "'{name}' は {} のいずれかを指定してください",
+            names.join(" / ")
+        )
+    })
+}
+
+fn parse_cue(name: &str) -> Result<Cue, String> {
+    Cue::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Cue::ALL.iter().map(|c| c.name()).collect();
+        format!(
+            "'{name}' は {} のいずれかを指定してください",
+            names.join(" / ")
+        )
+    })
+}
+
+/// The run's outcome, for the summary line.
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    written: usize,
+    matched: usize,
+    differed: usize,
+    muted: usize,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("render-cues: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// `Ok(false)` when `--check` found a cue that no longer matches.
+fn run(cli: &Cli) -> Result<bool, Box<dyn std::error::Error>> {
+    let themes = if cli.themes.is_empty() {
+        SoundTheme::ALL.to_vec()
+    } else {
+        cli.themes.clone()
+    };
+    let cues = if cli.cues.is_empty() {
+        Cue::ALL.to_vec()
+    } else {
+        cli.cues.clone()
+    };
+    let out = Path::new(&cli.out);
+
+    let mut tally = Tally::default();
+    for theme in themes {
+        if !cli.quiet {
+            println!("{}", describe_theme(theme));
+        }
+        let dir = out.join(theme.name());
+        if !cli.check {
+            fs::create_dir_all(&dir)?;
+        }
+        for &cue in &cues {
+            let samples = cues::synthesize(cue, theme);
+            if !cli.quiet {
+                for line in describe_cue(cue, theme, &samples) {
+                    println!("{line}");
+                }
+            }
+            if samples.is_empty() {
+                tally.muted += 1;
+                continue;
+            }
+            let path = dir.join(format!("{}.wav", cue.name()));
+            if cli.check {
+                match check_file(&path, &samples) {
+                    Ok(()) => tally.matched += 1,
+                    Err(e) => {
+                        println!("changed: {}: {e}", path.display());
+                        tally.differed += 1;
+                    }
+                }
+            } else {
+                let pcm = wav::Pcm {
+                    channels: 1,
+                    sample_rate: SAMPLE_RATE,
+                    samples,
+                };
+                fs::write(&path, wav::encode_float(&pcm))?;
+                tally.written += 1;
+            }
+        }
+    }
+    if cli.check {
+        println!(
+            "{}: {} matched, {} changed, {} muted",
+            out.display(),
+            tally.matched,
+            tally.differed,
+            tally.muted
+        );
+    } else {
+        println!(
+            "{}: {} written, {} muted",
+            out.display(),
+            tally.written,
+            tally.muted
+        );
+    }
+    Ok(tally.differed == 0)
+}
+
+/// `chip: square, attack 1/100, release 1/12, gain 0.45`
+fn describe_theme(theme: SoundTheme) -> String {
+    let v = theme.voicing();
+    format!(
+        "{}: {}, attack 1/{}, release 1/{}, gain {}",
+        theme.name(),
+        v.wave.name(),
+        v.attack_div,
+        v.release_div,
+        v.gain
+    )
+}
+
+/// The cue's length and peak, then one line per step with the envelope
+/// sample counts the theme gives each note.
+fn describe_cue(cue: Cue, theme: SoundTheme, samples: &[f32]) -> Vec<String> {
+    if theme.mutes(cue) {
+        return vec![format!("  {:<16} muted", cue.name())];
+    }
+    let peak = samples.iter().map(|s| s.abs()).fold(0.0_f32, f32::max);
+    let mut lines = vec![format!(
+        "  {:<16} {:.3} s  {} samples  peak {:.3}",
+        cue.name(),
+        samples.len() as f32 / SAMPLE_RATE as f32,
+        samples.len(),
+        peak
+    )];
+    let voicing = theme.voicing();
+    for step in cues::score(cue) {
+        lines.push(match step {
+            Step::Tone {
+                freq,
+                duration_s,
+                gain,
+            } => {
+                let (total, attack, release) = cues::envelope(duration_s, &voicing);
+                format!(
+                    "    tone {freq:>8.2} Hz  {duration_s:.3} s  gain {:.3}  {total} samples, attack {attack}, release {release}",
+                    gain * voicing.gain
+                )
+            }
+            Step::Rest { duration_s } => format!("    rest {duration_s:>20.3} s"),
+        });
+    }
+    lines
+}
+
+/// Compare a fresh render against the WAV at `path`.
+fn check_file(path: &Path, samples: &[f32]) -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = fs::read(path).map_err(|e| format!("no stored render ({e})"))?;
+    let pcm = wav::decode(&bytes)?;
+    if pcm.channels != 1 || pcm.sample_rate != SAMPLE_RATE {
+        return Err(format!(
+            "stored as {} ch / {} Hz, rendered as 1 ch / {SAMPLE_RATE} Hz",
+            pcm.channels, pcm.sample_rate
+        )
+        .into());
+    }
+    match first_difference(&pcm.samples, samples) {
+        Some(message) => Err(message.into()),
+        None => Ok(()),
+    }
+}
+
+/// How `rendered` departs from `stored`, or `None` when they are the
+/// same buffer.
+fn first_difference(stored: &[f32], rendered: &[f32]) -> Option<String> {
+    if stored.len() != rendered.len() {
+        return Some(format!("{} samples, was {}", rendered.len(), stored.len()));
+    }
+    let first = stored.iter().zip(rendered).position(|(a, b)| a != b)?;
+    let largest = stored
+        .iter()
+        .zip(rendered)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0_f32, f32::max);
+    Some(format!(
+        "first differs at sample {first}, largest difference {largest:.6}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_parse() {
+        assert_eq!(parse_theme("silent-keys"), Ok(SoundTheme::SilentKeys));
+        assert!(parse_theme("loud").is_err());
+        assert_eq!(parse_cue("level-up"), Ok(Cue::LevelUp));
+        assert!(parse_cue("fanfare").is_err());
+    }
+
+    #[test]
+    fn identical_buffers_have_no_difference() {
+        let samples = cues::synthesize(Cue::Correct, SoundTheme::Chip);
+        assert_eq!(first_difference(&samples, &samples), None);
+        let mut moved = samples.clone();
+        moved[10] += 0.5;
+        assert_eq!(
+            first_difference(&samples, &moved).as_deref(),
+            Some("first differs at sample 10, largest difference 0.500000")
+        );
+        assert!(first_difference(&samples, &samples[1..])
+            .expect("shorter")
+            .contains("samples, was"));
+    }
+
+    #[test]
+    fn a_written_render_checks_clean_until_it_changes() {
+        let dir = std::env::temp_dir().join("type_globe_test_render_cues");
+        fs::create_dir_all(&dir).expect("mkdir");
+        let path = dir.join("wrong.wav");
+        let samples = cues::synthesize(Cue::Wrong, SoundTheme::Soft);
+        let pcm = wav::Pcm {
+            channels: 1,
+            sample_rate: SAMPLE_RATE,
+            samples: samples.clone(),
+        };
+        fs::write(&path, wav::encode_float(&pcm)).expect("write");
+        assert!(check_file(&path, &samples).is_ok());
+        let other = cues::synthesize(Cue::Wrong, SoundTheme::Classic);
+        assert!(check_file(&path, &other).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn descriptions_list_every_step() {
+        let lines = describe_cue(
+            Cue::LevelUp,
+            SoundTheme::Chip,
+            &cues::synthesize(Cue::LevelUp, SoundTheme::Chip),
+        );
+        assert!(lines[0].starts_with("  level-up"));
+        assert_eq!(lines.len(), 1 + cues::score(Cue::LevelUp).len());
+        assert!(lines[1].contains("attack"), "{}", lines[1]);
+        assert_eq!(
+            describe_cue(Cue::Keystroke, SoundTheme::SilentKeys, &[]),
+            ["  keystroke        muted"]
+        );
+        assert_eq!(
+            describe_theme(SoundTheme::Chip),
+            "chip: square, attack 1/100, release 1/12, gain 0.45"
+        );
+    }
+}
@@ -0,0 +1,317 @@
+//! Pre-render Listening prompts to audio files.
+//!
+//! Runtime TTS sounds different on every machine and needs a speech
+//! daemon. This tool runs each `text_reading` in
+//! `data/listening_<lang>.yaml` through a local engine (espeak-ng,
+//! Open JTalk or piper) once, stores it as an IMA ADPCM WAV and writes a
+//! `manifest.yaml` beside the files:
+//!
+//! ```text
+//! <out>/ja/l-ja-001.wav
+//! <out>/ja/manifest.yaml
+//! ```
+//!
+//! The `files` speech backend plays them through rodio, so every player
+//! hears the same audio with no daemon. Prompts whose manifest entry
+//! already matches the text, engine and rate are skipped, which makes
+//! re-runs after editing a few prompts cheap.
+
+#[path = "../types.rs"]
+#[allow(dead_code)]
+mod types;
+
+#[path = "../config.rs"]
+#[allow(dead_code)]
+mod config;
+
+#[path = "../audio/engine.rs"]
+#[allow(dead_code)]
+mod engine;
+
+#[path = "../audio/manifest.rs"]
+#[allow(dead_code)]
+mod manifest;
+
+#[path = "../audio/wav.rs"]
+#[allow(dead_code)]
+mod wav;
+
+use clap::Parser;
+use config::Config;
+use engine::{EngineOpts, LocalEngine};
+use manifest::{Manifest, ManifestEntry};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use types::{Language, ListeningPrompt};
+
+/// リスニング問題の読み上げを音声ファイルに事前レンダリングする
+#[derive(Parser, Debug)]
+#[command(name = "render-listening", about, long_about = None)]
+struct Cli {
+    /// 対象言語 (ja / en、複数可)。省略時は両方
+    #[arg(long = "lang")]
+    langs: Vec<String>,
+    /// 使う音声エンジン (espeak-ng / open-jtalk / piper)
+    #[arg(long, default_value = "espeak-ng", value_parser = parse_engine)]
+    engine: LocalEngine,
+    /// piper のモデル、または Open JTalk の音声ファイル
+    #[arg(long)]
+    model: Option<String>,
+    /// 話速の倍率 (0.5-2.0)
+    #[arg(long, default_value_t = 1.0)]
+    rate: f32,
+    /// listening_<lang>.yaml のあるディレクトリ
+    #[arg(long, default_value = "data")]
+    prompts_dir: String,
+    /// 書き出し先。省略時はデータディレクトリの audio/
+    #[arg(long)]
+    out: Option<String>,
+    /// manifest と一致するファイルも作り直す
+    #[arg(long)]
+    force: bool,
+}
+
+fn parse_engine(name: &str) -> Result<LocalEngine, String> {
+    LocalEngine::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = LocalEngine::ALL.iter().map(|e| e.name()).collect();
+        format!(
+            "'{name}' は {} のいずれかを指定してください",
+            names.join(" / ")
+        )
+    })
+}
+
+/// One language's outcome, for the summary line.
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    rendered: usize,
+    unchanged: usize,
+    failed: usize,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("render-listening: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// `Ok(false)` when some prompts failed to render.
+fn run(cli: &Cli) -> Result<bool, Box<dyn std::error::Error>> {
+    if !cli.engine.is_installed() {
+        return Err(format!("`{}` not found on PATH", cli.engine.program()).into());
+    }
+    let languages = if cli.langs.is_empty() {
+        vec![Language::Japanese, Language::English]
+    } else {
+        cli.langs
+            .iter()
+            .map(|code| {
+                Language::from_code(code).ok_or_else(|| format!("unknown language '{code}'"))
+            })
+            .collect::<Result<_, _>>()?
+    };
+    let out = PathBuf::from(
+        cli.out
+            .clone()
+            .unwrap_or_else(|| Config::default().audio_dir_path()),
+    );
+    let opts = EngineOpts {
+        rate: cli.rate,
+        model: cli.model.clone(),
+        ..EngineOpts::default()
+    };
+
+    let mut clean = true;
+    for language in &languages {
+        let path = Path::new(&cli.prompts_dir).join(format!("listening_{}.yaml", language.code()));
+        let prompts: Vec<ListeningPrompt> = serde_yaml::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        let tally = render_language(
+            &prompts,
+            language,
+            cli.engine,
+            &opts,
+            &out.join(language.code()),
+            cli.force,
+        )?;
+        println!(
+            "{}: {} prompts, {} rendered, {} unchanged, {} failed",
+            language.code(),
+            prompts.len(),
+            tally.rendered,
+            tally.unchanged,
+            tally.failed
+        );
+        clean &= tally.failed == 0;
+    }
+    Ok(clean)
+}
+
+fn render_language(
+    prompts: &[ListeningPrompt],
+    language: &Language,
+    engine: LocalEngine,
+    opts: &EngineOpts,
+    dir: &Path,
+    force: bool,
+) -> Result<Tally, Box<dyn std::error::Error>> {
+    fs::create_dir_all(dir)?;
+    let previous = Manifest::load(dir)?.unwrap_or_default();
+    let scratch = std::env::temp_dir().join(format!(
+        "type-globe-render-{}-{}.wav",
+        language.code(),
+        std::process::id()
+    ));
+    let mut manifest = Manifest {
+        language: language.code().to_string(),
+        entries: Vec::with_capacity(prompts.len()),
+    };
+    let mut tally = Tally::default();
+    for prompt in prompts {
+        if let Some(entry) = previous
+            .entry(&prompt.id)
+            .filter(|entry| !force && is_current(entry, prompt, engine, opts, dir))
+        {
+            manifest.entries.push(entry.clone());
+            tally.unchanged += 1;
+            continue;
+        }
+        match render_prompt(prompt, language, engine, opts, dir, &scratch) {
+            Ok(entry) => {
+                manifest.entries.push(entry);
+                tally.rendered += 1;
+            }
+            Err(e) => {
+                eprintln!("{}: {e}", prompt.id);
+                tally.failed += 1;
+            }
+        }
+    }
+    let _ = fs::remove_file(&scratch);
+    manifest.save(dir)?;
+    Ok(tally)
+}
+
+/// Whether `entry` was rendered from this prompt with these settings and
+/// its file is still there.
+fn is_current(
+    entry: &ManifestEntry,
+    prompt: &ListeningPrompt,
+    engine: LocalEngine,
+    opts: &EngineOpts,
+    dir: &Path,
+) -> bool {
+    entry.text_reading == prompt.text_reading
+        && entry.engine == engine.name()
+        && entry.rate == opts.rate
+        && dir.join(&entry.file).is_file()
+}
+
+fn render_prompt(
+    prompt: &ListeningPrompt,
+    language: &Language,
+    engine: LocalEngine,
+    opts: &EngineOpts,
+    dir: &Path,
+    scratch: &Path,
+) -> Result<ManifestEntry, Box<dyn std::error::Error>> {
+    engine.render(&prompt.text_reading, language, opts, scratch)?;
+    let pcm = wav::downmix(wav::decode(&fs::read(scratch)?)?);
+    let duration_ms = pcm.samples.len() as u64 * 1000 / u64::from(pcm.sample_rate.max(1));
+    let file = format!("{}.wav", prompt.id);
+    fs::write(dir.join(&file), wav::encode_ima_adpcm(pcm))?;
+    Ok(ManifestEntry {
+        id: prompt.id.clone(),
+        file,
+        text_reading: prompt.text_reading.clone(),
+        engine: engine.name().to_string(),
+        rate: opts.rate,
+        duration_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::AnswerKind;
+
+    fn prompt(id: &str, text: &str) -> ListeningPrompt {
+        ListeningPrompt {
+            id: id.into(),
+            text_reading: text.into(),
+            text_display: text.into(),
+            kind: AnswerKind::Word,
+        }
+    }
+
+    #[test]
+    fn matching_entries_with_files_are_current() {
+        let dir = std::env::temp_dir().join("type_globe_test_render_listening");
+        fs::create_dir_all(&dir).expect("mkdir");
+        fs::write(dir.join("l1.wav"), b"RIFF").expect("write");
+        let entry = ManifestEntry {
+            id: "l1".into(),
+            file: "l1.wav".into(),
+            text_reading: "あめ".into(),
+            engine: "espeak-ng".into(),
+            rate: 1.0,
+            duration_ms: 300,
+        };
+        let opts = EngineOpts::default();
+        let espeak = LocalEngine::EspeakNg;
+        assert!(is_current(
+            &entry,
+            &prompt("l1", "あめ"),
+            espeak,
+            &opts,
+            &dir
+        ));
+        assert!(!is_current(
+            &entry,
+            &prompt("l1", "あめだ"),
+            espeak,
+            &opts,
+            &dir
+        ));
+        assert!(!is_current(
+            &entry,
+            &prompt("l1", "あめ"),
+            LocalEngine::Piper,
+            &opts,
+            &dir
+        ));
+        let slow = EngineOpts {
+            rate: 0.8,
+            ..EngineOpts::default()
+        };
+        assert!(!is_current(
+            &entry,
+            &prompt("l1", "あめ"),
+            espeak,
+            &slow,
+            &dir
+        ));
+        fs::remove_file(dir.join("l1.wav")).expect("rm");
+        assert!(!is_current(
+            &entry,
+            &prompt("l1", "あめ"),
+            espeak,
+            &opts,
+            &dir
+        ));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn engine_names_parse() {
+        assert_eq!(parse_engine("open-jtalk"), Ok(LocalEngine::OpenJtalk));
+        assert!(parse_engine("festival").is_err());
+    }
+}
@@ -2,14 +2,22 @@ use crate::types::Language;
 
 pub struct Config {
     pub data_dir: String,
-    #[allow(dead_code)]
-    pub default_language: Language,
     pub questions_file_pattern: String,
     #[allow(dead_code)]
     pub player_data_file: String,
     #[allow(dead_code)]
     pub records_file_pattern: String,
     pub listening_file_pattern: String,
+    /// Default pack written by the question editor, relative to `data_dir`.
+    pub custom_pack_file: String,
+    /// Colour theme (`ui::theme`), relative to `data_dir`.
+    pub theme_file: String,
+    /// Key bindings (`ui::keymap`), relative to `data_dir`.
+    pub keymap_file: String,
+    /// User settings (`settings`), relative to `data_dir`.
+    pub settings_file: String,
+    /// Pre-rendered Listening audio (`audio::speech`), relative to `data_dir`.
+    pub audio_dir: String,
 }
 
 /// Return the user data directory: `~/.local/share/type-globe/` on Linux,
@@ -34,11 +42,15 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             data_dir: user_data_dir(),
-            default_language: Language::Japanese,
             questions_file_pattern: "questions_{}.json".to_string(),
             player_data_file: "player.yaml".to_string(),
             records_file_pattern: "records_{}.yaml".to_string(),
             listening_file_pattern: "listening_{}.yaml".to_string(),
+            custom_pack_file: "packs/custom.json".to_string(),
+            theme_file: "theme.yaml".to_string(),
+            keymap_file: "keymap.yaml".to_string(),
+            settings_file: "settings.yaml".to_string(),
+            audio_dir: "audio".to_string(),
         }
     }
 }
@@ -72,6 +84,26 @@ impl Config {
             self.listening_file_pattern.replace("{}", language.code())
         )
     }
+
+    pub fn custom_pack_file_path(&self) -> String {
+        format!("{}/{}", self.data_dir, self.custom_pack_file)
+    }
+
+    pub fn theme_file_path(&self) -> String {
+        format!("{}/{}", self.data_dir, self.theme_file)
+    }
+
+    pub fn keymap_file_path(&self) -> String {
+        format!("{}/{}", self.data_dir, self.keymap_file)
+    }
+
+    pub fn settings_file_path(&self) -> String {
+        format!("{}/{}", self.data_dir, self.settings_file)
+    }
+
+    pub fn audio_dir_path(&self) -> String {
+        format!("{}/{}", self.data_dir, self.audio_dir)
+    }
 }
 
 #[cfg(test)]
@@ -82,11 +114,15 @@ mod tests {
     fn listening_file_path_uses_language_code() {
         let cfg = Config {
             data_dir: "data".to_string(),
-            default_language: Language::Japanese,
             questions_file_pattern: "questions_{}.json".to_string(),
             player_data_file: "player.yaml".to_string(),
             records_file_pattern: "records_{}.yaml".to_string(),
             listening_file_pattern: "listening_{}.yaml".to_string(),
+            custom_pack_file: "packs/custom.json".to_string(),
+            theme_file: "theme.yaml".to_string(),
+            keymap_file: "keymap.yaml".to_string(),
+            settings_file: "settings.yaml".to_string(),
+            audio_dir: "audio".to_string(),
         };
         assert_eq!(
             cfg.listening_file_path(&Language::Japanese),
@@ -102,15 +138,23 @@ mod tests {
     fn questions_file_path_uses_language_code() {
         let cfg = Config {
             data_dir: "data".to_string(),
-            default_language: Language::Japanese,
             questions_file_pattern: "questions_{}.json".to_string(),
             player_data_file: "player.yaml".to_string(),
             records_file_pattern: "records_{}.yaml".to_string(),
             listening_file_pattern: "listening_{}.yaml".to_string(),
+            custom_pack_file: "packs/custom.json".to_string(),
+            theme_file: "theme.yaml".to_string(),
+            keymap_file: "keymap.yaml".to_string(),
+            settings_file: "settings.yaml".to_string(),
+            audio_dir: "audio".to_string(),
         };
         assert_eq!(
             cfg.questions_file_path(&Language::Japanese),
             "data/questions_ja.json"
         );
+        assert_eq!(cfg.custom_pack_file_path(), "data/packs/custom.json");
+        assert_eq!(cfg.theme_file_path(), "data/theme.yaml");
+        assert_eq!(cfg.keymap_file_path(), "data/keymap.yaml");
+        assert_eq!(cfg.settings_file_path(), "data/settings.yaml");
     }
 }
@@ -0,0 +1,157 @@
+//! Graduated Quiz hints.
+//!
+//! Per the "answer never shown" rule in `docs/spec.md`, a hint narrows the
+//! answer down without ever displaying it: first a wrong choice is struck
+//! out, then the number of characters to type is shown, then the first
+//! kana of the reading (the first letter outside JA mode). Each step costs
+//! part of the question's score, and the steps a player took are logged
+//! per question for the summary.
+
+use crate::io::normalize::canonical_romaji;
+use crate::io::romaji::hiragana_to_hepburn;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hint {
+    /// Original index (into `question.choices`) of the struck-out choice.
+    EliminateChoice(usize),
+    /// Characters to type. Romaji spellings of one answer can differ in
+    /// length, so this is a range; `min == max` for most answers.
+    Length { min: usize, max: usize },
+    /// First kana of the reading, or the first letter outside JA mode.
+    FirstKana(String),
+}
+
+impl Hint {
+    /// Points taken off the question's score when it is answered.
+    pub fn cost(&self) -> u32 {
+        match self {
+            Self::EliminateChoice(_) => 20,
+            Self::Length { .. } => 30,
+            Self::FirstKana(_) => 40,
+        }
+    }
+
+    /// What the Choices pane shows. The struck-out choice is drawn in the
+    /// list itself, so it has no text here.
+    pub fn text(&self) -> Option<String> {
+        match self {
+            Self::EliminateChoice(_) => None,
+            Self::Length { min, max } if min == max => Some(format!("{min} characters")),
+            Self::Length { min, max } => Some(format!("{min}-{max} characters")),
+            Self::FirstKana(first) => Some(format!("starts with {first}")),
+        }
+    }
+
+    /// Short name for the stealth log and the hint log.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EliminateChoice(_) => "eliminate",
+            Self::Length { .. } => "length",
+            Self::FirstKana(_) => "first-kana",
+        }
+    }
+}
+
+/// The hints taken on one question, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HintLog {
+    pub question_id: String,
+    pub hints: Vec<Hint>,
+}
+
+impl HintLog {
+    pub fn cost(&self) -> u32 {
+        total_cost(&self.hints)
+    }
+}
+
+pub fn total_cost(hints: &[Hint]) -> u32 {
+    hints.iter().map(Hint::cost).sum()
+}
+
+/// Small kana that join the preceding kana into one mora (`きょ`).
+const COMBINING_SMALL: &str = "ゃゅょぁぃぅぇぉャュョァィゥェォ";
+
+/// First kana of a JA answer. A label that starts with kana gives it
+/// directly (with a following small ゃ/ゅ/ょ); a kanji label is matched
+/// against its romaji typings instead, taking the longest kana whose
+/// Hepburn spelling starts the typing. `None` when neither works, e.g. a
+/// reading that starts with っ.
+pub fn first_kana(label: &str, typings: &[String]) -> Option<String> {
+    let mut chars = label.chars();
+    let first = chars.next()?;
+    if is_kana(first) {
+        let mut mora = first.to_string();
+        if let Some(next) = chars.next().filter(|c| COMBINING_SMALL.contains(*c)) {
+            mora.push(next);
+        }
+        return Some(mora);
+    }
+    let typing = canonical_romaji(&typings.first()?.to_lowercase());
+    kana_morae()
+        .filter_map(|mora| {
+            let romaji = canonical_romaji(&hiragana_to_hepburn(&mora));
+            (!romaji.is_empty() && typing.starts_with(&romaji)).then_some((romaji.len(), mora))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, mora)| mora)
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c as u32, 0x3041..=0x3096 | 0x30A1..=0x30FA)
+}
+
+/// Every hiragana from あ to ん, plus the い-row + ゃ/ゅ/ょ combinations.
+fn kana_morae() -> impl Iterator<Item = String> {
+    let singles = ('あ'..='ん').filter(|c| !COMBINING_SMALL.contains(*c) && *c != 'っ');
+    let combos = "きしちにひみりぎじびぴ"
+        .chars()
+        .flat_map(|c| "ゃゅょ".chars().map(move |small| format!("{c}{small}")));
+    singles.map(String::from).chain(combos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kana_labels_give_their_first_mora() {
+        assert_eq!(first_kana("とうきょう", &[]), Some("と".to_string()));
+        assert_eq!(first_kana("きょうと", &[]), Some("きょ".to_string()));
+        assert_eq!(first_kana("ロンドン", &[]), Some("ロ".to_string()));
+    }
+
+    #[test]
+    fn kanji_labels_fall_back_to_the_typing() {
+        let typings = |s: &str| vec![s.to_string()];
+        assert_eq!(
+            first_kana("東京", &typings("toukyou")),
+            Some("と".to_string())
+        );
+        assert_eq!(
+            first_kana("京都", &typings("kyouto")),
+            Some("きょ".to_string())
+        );
+        assert_eq!(
+            first_kana("新宿", &typings("shinjuku")),
+            Some("し".to_string())
+        );
+        assert_eq!(first_kana("千葉", &typings("tiba")), Some("ち".to_string()));
+        assert_eq!(first_kana("札幌", &[]), None);
+    }
+
+    #[test]
+    fn costs_add_up_below_a_full_answer() {
+        let all = [
+            Hint::EliminateChoice(1),
+            Hint::Length { min: 5, max: 5 },
+            Hint::FirstKana("と".into()),
+        ];
+        assert_eq!(total_cost(&all), 90);
+        assert_eq!(all[1].text().as_deref(), Some("5 characters"));
+        assert_eq!(
+            Hint::Length { min: 5, max: 7 }.text().as_deref(),
+            Some("5-7 characters")
+        );
+    }
+}
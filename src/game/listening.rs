@@ -14,6 +14,7 @@
 use crate::io::romaji::hiragana_to_hepburn_variants;
 use crate::types::{Language, ListeningPrompt};
 use rand::seq::SliceRandom;
+use std::time::{Duration, Instant};
 
 pub fn acceptable_listening_inputs(language: &Language, expected: &str) -> Vec<String> {
     match language {
@@ -60,6 +61,9 @@ pub struct ListeningSession {
     language: Language,
     input: String,
     submitted: Option<SubmissionResult>,
+    /// When the prompt's audio last played to its end (#44). Reaction
+    /// time counts from here, so a replay restarts the clock.
+    heard_at: Option<Instant>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -69,6 +73,9 @@ pub struct SubmissionResult {
     /// UI can reveal it on the result screen without holding the
     /// `ListeningPrompt` reference.
     pub expected: String,
+    /// From the end of the last utterance to the answer. `None` without
+    /// audio, or when the answer came before the audio finished.
+    pub reaction: Option<Duration>,
 }
 
 impl ListeningSession {
@@ -78,6 +85,7 @@ impl ListeningSession {
             language,
             input: String::new(),
             submitted: None,
+            heard_at: None,
         }
     }
 
@@ -113,14 +121,29 @@ impl ListeningSession {
         self.input.pop();
     }
 
+    /// The prompt's audio played to its end at `at`.
+    pub fn audio_finished(&mut self, at: Instant) {
+        if self.submitted.is_none() {
+            self.heard_at = Some(at);
+        }
+    }
+
     /// Apply the blind-input judge. Once submitted the session is
     /// frozen — further `push_char` / `pop_char` are no-ops.
     pub fn submit(&mut self) -> &SubmissionResult {
+        self.submit_at(Instant::now())
+    }
+
+    /// [`submit`](Self::submit), answering at `now`.
+    pub fn submit_at(&mut self, now: Instant) -> &SubmissionResult {
         let is_correct =
             is_correct_listening_input(&self.language, &self.input, &self.prompt.text_reading);
         self.submitted = Some(SubmissionResult {
             is_correct,
             expected: self.prompt.text_reading.clone(),
+            reaction: self
+                .heard_at
+                .map(|heard| now.saturating_duration_since(heard)),
         });
         // Safe: we just assigned `Some(_)`.
         self.submitted.as_ref().expect("just submitted")
@@ -307,6 +330,19 @@ mod tests {
         assert_eq!(r.expected, "apple");
     }
 
+    #[test]
+    fn reaction_counts_from_the_last_utterance_end() {
+        let start = Instant::now();
+        let mut s = ListeningSession::new(p("apple"), Language::English);
+        assert_eq!(s.submit_at(start).reaction, None);
+
+        let mut s = ListeningSession::new(p("apple"), Language::English);
+        s.audio_finished(start);
+        s.audio_finished(start + Duration::from_secs(2));
+        let r = s.submit_at(start + Duration::from_millis(2500)).clone();
+        assert_eq!(r.reaction, Some(Duration::from_millis(500)));
+    }
+
     #[test]
     fn from_pool_returns_none_on_empty() {
         let pool: Vec<ListeningPrompt> = Vec::new();
@@ -1,3 +1,4 @@
+pub mod hint;
 pub mod listening;
 pub mod quiz;
 
@@ -5,4 +6,4 @@ pub use listening::{ListeningSession, SubmissionResult};
 // `is_correct_listening_input` stays reachable via
 // `listening::is_correct_listening_input`; not re-exported until a
 // non-test caller appears.
-pub use quiz::QuizGame;
+pub use quiz::{QuizGame, QuizVariant};
@@ -1,13 +1,62 @@
+use crate::game::hint::{self, Hint, HintLog};
 use crate::io::normalize::canonical_romaji;
 use crate::io::DataLoader;
-use crate::types::{Language, Question};
+use crate::types::{Language, Question, Records, ScoreEntry};
 use rand::seq::SliceRandom;
 use std::time::{Duration, Instant};
 
-/// Number of questions a single Quiz run is locked to. Per `docs/spec.md`
-/// (Quiz Mode header) and Issue #26's acceptance criteria.
+/// Number of questions in the classic Quiz run. Per `docs/spec.md`
+/// (Quiz Mode header) and Issue #26's acceptance criteria. Other lengths
+/// (`quiz --count`, the `run_length` setting) are separate variants.
 pub const QUIZ_RUN_LENGTH: usize = 10;
 
+/// Which shape of Quiz run is being played. Each variant keeps its own
+/// Records list, so a 50-question run never outranks a 10-question one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuizVariant {
+    /// A fixed number of questions. `Count(QUIZ_RUN_LENGTH)` is the
+    /// classic run and the only one stored in `Records::quiz_mode`.
+    Count(usize),
+    /// Endless: the pool is reshuffled whenever it runs out, and the run
+    /// ends on the first skip.
+    Survival,
+    /// Every question of one genre, once each.
+    Marathon(String),
+    /// The classic run length with a per-question time limit; see
+    /// [`QuizGame::current_time_limit`].
+    Timed,
+}
+
+/// Time every Timed question gets regardless of its answer.
+pub const TIMED_BASE_LIMIT: Duration = Duration::from_secs(4);
+/// Extra time per character of the answer's typing target, so a long
+/// romaji answer isn't held to the same limit as `"rome"`.
+pub const TIMED_PER_CHAR: Duration = Duration::from_millis(400);
+
+impl QuizVariant {
+    /// Short name for titles and Records section headers.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Count(QUIZ_RUN_LENGTH) => "Quiz".to_string(),
+            Self::Count(n) => format!("Quiz ({n} questions)"),
+            Self::Survival => "Survival".to_string(),
+            Self::Marathon(genre) => format!("Marathon: {genre}"),
+            Self::Timed => "Timed".to_string(),
+        }
+    }
+
+    /// File `entry` under this variant's Records list.
+    pub fn push_record(&self, records: &mut Records, entry: ScoreEntry) {
+        match self {
+            Self::Count(QUIZ_RUN_LENGTH) => records.push_quiz(entry),
+            Self::Count(n) => records.push_quiz_count(*n, entry),
+            Self::Survival => records.push_survival(entry),
+            Self::Marathon(genre) => records.push_marathon(genre, entry),
+            Self::Timed => records.push_timed(entry),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct QuizGame {
     questions: Vec<Question>,
@@ -27,6 +76,23 @@ pub struct QuizGame {
     /// timer that keeps ticking afterwards.
     frozen_time: Option<Duration>,
     language: Language,
+    variant: QuizVariant,
+    /// Survival only: the full pool, reshuffled onto the end of
+    /// `questions` each time the run catches up with it.
+    refill: Vec<Question>,
+    /// Survival only: set by the skip that ends the run.
+    ended: bool,
+    /// Off when the `hints_in_records` setting bars hints from this run.
+    hints_enabled: bool,
+    /// Hints taken on the active question so far.
+    hints: Vec<Hint>,
+    /// One entry per finished question that used any hint.
+    hint_log: Vec<HintLog>,
+    /// Practice runs: a skipped question goes to the back of the queue
+    /// once, so the player gets a second try.
+    practice: bool,
+    /// Ids of questions already re-queued by a practice skip.
+    requeued: Vec<String>,
 }
 
 /// Per-answer outcome. Issue #70 removed the result interstitial, so
@@ -50,6 +116,14 @@ impl QuizGame {
     /// tests and any caller that has already curated its own ordering.
     pub fn new(questions: Vec<Question>, language: Language) -> Self {
         Self {
+            variant: QuizVariant::Count(questions.len()),
+            refill: Vec::new(),
+            ended: false,
+            hints_enabled: true,
+            hints: Vec::new(),
+            hint_log: Vec::new(),
+            practice: false,
+            requeued: Vec::new(),
             questions,
             current_question_index: 0,
             score: 0,
@@ -67,13 +141,19 @@ impl QuizGame {
     /// the whole pool is used (no padding, no repeats). Order is shuffled
     /// so two consecutive runs don't see the same questions in the same
     /// sequence.
+    ///
+    /// The binary always goes through [`from_pool_with_count`] now that
+    /// the run length is a setting; this stays as the default-length entry.
+    #[allow(dead_code)]
     pub fn from_pool(pool: &[Question], language: Language) -> Self {
         Self::from_pool_with_count(pool, language, QUIZ_RUN_LENGTH)
     }
 
-    /// Same as [`from_pool`] but with a caller-specified run length.
-    /// Used by the auto-demo (#106) where the operator can dial the
-    /// session length up or down with `--demo-count`.
+    /// Same as [`from_pool`] but with a caller-specified run length:
+    /// `quiz --count`, `run_length` from `settings.yaml`, or
+    /// `--demo-count` in the auto-demo (#106). The variant records the
+    /// length actually drawn, so a short pool files its score under the
+    /// length that was played.
     pub fn from_pool_with_count(pool: &[Question], language: Language, count: usize) -> Self {
         let mut rng = rand::thread_rng();
         let take = pool.len().min(count.max(1));
@@ -81,14 +161,186 @@ impl QuizGame {
         Self::new(sampled, language)
     }
 
+    /// Endless run over `pool`; see [`QuizVariant::Survival`].
+    pub fn survival(pool: &[Question], language: Language) -> Self {
+        let mut questions = pool.to_vec();
+        questions.shuffle(&mut rand::thread_rng());
+        Self {
+            variant: QuizVariant::Survival,
+            refill: pool.to_vec(),
+            ..Self::new(questions, language)
+        }
+    }
+
+    /// Every question of `genre` in `pool`, shuffled. Empty when the
+    /// genre has no questions; callers check before starting.
+    pub fn marathon(pool: &[Question], genre: &str, language: Language) -> Self {
+        let mut questions = DataLoader::filter_questions_by_genre(pool, Some(genre));
+        questions.shuffle(&mut rand::thread_rng());
+        Self {
+            variant: QuizVariant::Marathon(genre.to_string()),
+            ..Self::new(questions, language)
+        }
+    }
+
+    pub fn for_variant(pool: &[Question], language: Language, variant: &QuizVariant) -> Self {
+        match variant {
+            QuizVariant::Count(n) => Self::from_pool_with_count(pool, language, *n),
+            QuizVariant::Survival => Self::survival(pool, language),
+            QuizVariant::Marathon(genre) => Self::marathon(pool, genre, language),
+            QuizVariant::Timed => Self {
+                variant: QuizVariant::Timed,
+                ..Self::from_pool_with_count(pool, language, QUIZ_RUN_LENGTH)
+            },
+        }
+    }
+
+    pub fn variant(&self) -> &QuizVariant {
+        &self.variant
+    }
+
+    pub fn set_hints_enabled(&mut self, enabled: bool) {
+        self.hints_enabled = enabled;
+    }
+
+    /// Practice run: skips re-queue the question once; see
+    /// [`skip_question`](Self::skip_question).
+    pub fn set_practice(&mut self, practice: bool) {
+        self.practice = practice;
+    }
+
+    /// The wrong choice whose typing starts with `typed`, i.e. the answer
+    /// a rejected keystroke was heading for. Practice mode uses it to
+    /// explain the miss once the question is over.
+    pub fn wrong_choice_for_prefix(&self, typed: &str) -> Option<usize> {
+        let question = self.get_current_question()?;
+        let key = self.canonical_key(typed);
+        if key.is_empty() {
+            return None;
+        }
+        question
+            .choices
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != question.correct_answer_index)
+            .find(|(_, choice)| {
+                DataLoader::get_choice_typing_texts(choice, &self.language)
+                    .iter()
+                    .any(|candidate| self.canonical_key(candidate).starts_with(&key))
+            })
+            .map(|(idx, _)| idx)
+    }
+
+    /// Take the next hint for the active question, in the order
+    /// eliminate → length → first kana, skipping any that don't apply
+    /// (e.g. no first kana can be derived). `None` when hints are off or
+    /// all have been taken.
+    pub fn take_hint(&mut self) -> Option<Hint> {
+        if !self.hints_enabled {
+            return None;
+        }
+        let next = (self.hints.len()..3).find_map(|step| self.hint_for_step(step))?;
+        self.hints.push(next.clone());
+        Some(next)
+    }
+
+    fn hint_for_step(&self, step: usize) -> Option<Hint> {
+        let question = self.get_current_question()?;
+        match step {
+            0 => {
+                let wrong: Vec<usize> = (0..question.choices.len())
+                    .filter(|&i| i != question.correct_answer_index)
+                    .collect();
+                wrong
+                    .choose(&mut rand::thread_rng())
+                    .map(|&i| Hint::EliminateChoice(i))
+            }
+            1 => {
+                let lengths = self
+                    .current_correct_typing_candidates()
+                    .iter()
+                    .map(|c| c.chars().count())
+                    .collect::<Vec<_>>();
+                Some(Hint::Length {
+                    min: *lengths.iter().min()?,
+                    max: *lengths.iter().max()?,
+                })
+            }
+            2 => {
+                let label = self
+                    .get_choice_texts(question)
+                    .into_iter()
+                    .nth(question.correct_answer_index)?;
+                let first = if matches!(self.language, Language::Japanese) {
+                    hint::first_kana(&label, &self.current_correct_typing_candidates())?
+                } else {
+                    label.chars().next()?.to_string()
+                };
+                Some(Hint::FirstKana(first))
+            }
+            _ => None,
+        }
+    }
+
+    /// Hints taken on the active question.
+    pub fn current_hints(&self) -> &[Hint] {
+        &self.hints
+    }
+
+    pub fn hint_log(&self) -> &[HintLog] {
+        &self.hint_log
+    }
+
+    /// Move the active question's hints into the log; called as the run
+    /// leaves the question.
+    fn close_hints(&mut self) {
+        if self.hints.is_empty() {
+            return;
+        }
+        let question_id = self
+            .get_current_question()
+            .map(|q| q.id.clone())
+            .unwrap_or_default();
+        self.hint_log.push(HintLog {
+            question_id,
+            hints: std::mem::take(&mut self.hints),
+        });
+    }
+
+    /// How long the player has for the active question in a Timed run:
+    /// [`TIMED_BASE_LIMIT`] plus [`TIMED_PER_CHAR`] for each character of
+    /// the longest typing candidate (romaji spellings differ in length, so
+    /// the limit is set by the slowest accepted one). `None` outside
+    /// Timed runs or when there is no active question.
+    pub fn current_time_limit(&self) -> Option<Duration> {
+        if self.variant != QuizVariant::Timed {
+            return None;
+        }
+        self.get_current_question()?;
+        let chars = self
+            .current_correct_typing_candidates()
+            .iter()
+            .map(|c| c.chars().count())
+            .max()
+            .unwrap_or(0);
+        Some(TIMED_BASE_LIMIT + TIMED_PER_CHAR * chars as u32)
+    }
+
     pub fn start(&mut self) {
         self.start_time = Some(Instant::now());
     }
 
     pub fn get_current_question(&self) -> Option<&Question> {
+        if self.ended {
+            return None;
+        }
         self.questions.get(self.current_question_index)
     }
 
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
     pub fn get_question_text(&self, question: &Question) -> String {
         DataLoader::get_question_text(question, &self.language)
     }
@@ -241,6 +493,7 @@ impl QuizGame {
             }
 
             self.total_answers += 1;
+            self.close_hints();
 
             let result = QuizResult {
                 is_correct,
@@ -250,6 +503,7 @@ impl QuizGame {
             };
 
             self.current_question_index += 1;
+            self.refill_if_exhausted();
 
             self.maybe_freeze_time();
 
@@ -260,7 +514,47 @@ impl QuizGame {
     }
 
     pub fn is_game_finished(&self) -> bool {
-        self.current_question_index >= self.questions.len()
+        self.ended || self.current_question_index >= self.questions.len()
+    }
+
+    /// Practice: put the active question at the back of the queue unless
+    /// it is already a retry.
+    fn requeue_for_practice(&mut self) {
+        if !self.practice {
+            return;
+        }
+        let Some(question) = self.get_current_question() else {
+            return;
+        };
+        if self.requeued.contains(&question.id) {
+            return;
+        }
+        let question = question.clone();
+        self.requeued.push(question.id.clone());
+        self.questions.push(question);
+    }
+
+    /// Survival: append a reshuffled pool once the last queued question
+    /// has been answered, so the run never runs dry.
+    fn refill_if_exhausted(&mut self) {
+        if self.refill.is_empty() || self.current_question_index < self.questions.len() {
+            return;
+        }
+        let mut more = self.refill.clone();
+        more.shuffle(&mut rand::thread_rng());
+        self.questions.extend(more);
+    }
+
+    /// The questions answered or skipped so far, in play order. A
+    /// practice retry appears twice.
+    pub fn played_questions(&self) -> &[Question] {
+        &self.questions[..self.current_question_index.min(self.questions.len())]
+    }
+
+    /// Questions answered or skipped so far. Equals the run length once a
+    /// fixed-length run is over; for Survival it includes the final skip.
+    pub fn questions_played(&self) -> usize {
+        self.current_question_index
     }
 
     pub fn get_final_score(&self) -> u32 {
@@ -327,14 +621,19 @@ impl QuizGame {
         (self.current_question_index, self.questions.len())
     }
 
+    /// 100 per correct answer, less the cost of any hints taken on it.
     fn calculate_score_for_question(&self) -> u32 {
-        100
+        100u32.saturating_sub(hint::total_cost(&self.hints))
     }
 
     pub fn skip_question(&mut self) -> bool {
-        if self.current_question_index < self.questions.len() {
+        if !self.is_game_finished() {
+            self.close_hints();
+            self.requeue_for_practice();
             self.current_question_index += 1;
             self.total_answers += 1;
+            // A skip is the only way out of a Survival run.
+            self.ended = self.variant == QuizVariant::Survival;
             self.maybe_freeze_time();
             true
         } else {
@@ -376,6 +675,9 @@ mod tests {
             correct_answer_index: correct,
             image_path: None,
             ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
         }
     }
 
@@ -841,6 +1143,163 @@ mod tests {
         assert_eq!(ids.len(), QUIZ_RUN_LENGTH);
     }
 
+    fn genre_pool() -> Vec<Question> {
+        (0..6)
+            .map(|i| {
+                let mut q = make_question(&["a", "b", "c", "d"], 0);
+                q.id = format!("q{i}");
+                q.genre = if i % 2 == 0 { "even" } else { "odd" }.to_string();
+                q
+            })
+            .collect()
+    }
+
+    #[test]
+    fn count_variant_records_the_length_drawn() {
+        let pool = genre_pool();
+        let game = QuizGame::from_pool_with_count(&pool, Language::English, 20);
+        assert_eq!(game.variant(), &QuizVariant::Count(6));
+        assert_eq!(QuizVariant::Count(QUIZ_RUN_LENGTH).label(), "Quiz");
+        assert_eq!(QuizVariant::Count(20).label(), "Quiz (20 questions)");
+    }
+
+    #[test]
+    fn survival_refills_and_ends_on_the_first_skip() {
+        let pool = genre_pool();
+        let mut game = QuizGame::survival(&pool, Language::English);
+        for _ in 0..pool.len() * 2 + 1 {
+            assert!(game.answer_question(0, 1).expect("question").is_correct);
+        }
+        assert!(!game.is_game_finished());
+        assert!(game.skip_question());
+        assert!(game.is_game_finished());
+        assert_eq!(game.questions_played(), pool.len() * 2 + 2);
+        assert_eq!(game.get_correct_count() as usize, pool.len() * 2 + 1);
+        assert!(!game.skip_question());
+        assert!(game.get_current_question().is_none());
+        assert!(game.answer_question(0, 1).is_none());
+    }
+
+    #[test]
+    fn marathon_plays_every_question_of_the_genre() {
+        let pool = genre_pool();
+        let mut game = QuizGame::marathon(&pool, "odd", Language::English);
+        assert_eq!(game.get_progress(), (0, 3));
+        assert!(game.questions.iter().all(|q| q.genre == "odd"));
+        assert!(game.skip_question());
+        assert!(!game.is_game_finished());
+        assert!(QuizGame::marathon(&pool, "none", Language::English).is_game_finished());
+    }
+
+    #[test]
+    fn timed_limit_scales_with_the_answer_length() {
+        let pool = vec![make_question(&["a", "amsterdam", "c", "d"], 1)];
+        let timed = QuizGame::for_variant(&pool, Language::English, &QuizVariant::Timed);
+        assert_eq!(timed.variant(), &QuizVariant::Timed);
+        assert_eq!(
+            timed.current_time_limit(),
+            Some(TIMED_BASE_LIMIT + TIMED_PER_CHAR * 9)
+        );
+        let mut untimed = QuizGame::from_pool_with_count(&pool, Language::English, 1);
+        assert_eq!(untimed.current_time_limit(), None);
+        untimed.skip_question();
+        let mut timed = timed;
+        timed.skip_question();
+        assert_eq!(timed.current_time_limit(), None);
+    }
+
+    #[test]
+    fn hints_come_in_order_cost_score_and_are_logged() {
+        let mut q = make_question(&["paris", "london", "rome", "tokyo"], 1);
+        q.id = "capital-uk".into();
+        let mut game = QuizGame::new(vec![q.clone(), q], Language::English);
+
+        let Some(Hint::EliminateChoice(struck)) = game.take_hint() else {
+            panic!("first hint eliminates a choice");
+        };
+        assert_ne!(struck, 1);
+        assert_eq!(game.take_hint(), Some(Hint::Length { min: 6, max: 6 }));
+        assert_eq!(game.take_hint(), Some(Hint::FirstKana("l".into())));
+        assert_eq!(game.take_hint(), None);
+
+        assert!(
+            game.answer_question_typed("london")
+                .expect("answered")
+                .is_correct
+        );
+        assert_eq!(game.get_final_score(), 10);
+        assert!(game.current_hints().is_empty());
+        assert_eq!(game.hint_log().len(), 1);
+        assert_eq!(game.hint_log()[0].question_id, "capital-uk");
+        assert_eq!(game.hint_log()[0].cost(), 90);
+
+        game.take_hint();
+        game.skip_question();
+        assert_eq!(game.hint_log().len(), 2);
+        assert_eq!(game.get_final_score(), 10);
+    }
+
+    #[test]
+    fn practice_skips_requeue_each_question_once() {
+        let mut game = QuizGame::new(
+            genre_pool().into_iter().take(2).collect(),
+            Language::English,
+        );
+        game.set_practice(true);
+        assert!(game.skip_question());
+        assert_eq!(game.get_progress(), (1, 3));
+        assert!(game.answer_question(0, 1).expect("q1").is_correct);
+        let retry = game.get_current_question().expect("retry").id.clone();
+        assert_eq!(retry, "q0");
+        assert!(game.skip_question());
+        assert!(game.is_game_finished(), "a retry is not re-queued again");
+    }
+
+    #[test]
+    fn wrong_choice_for_prefix_finds_the_choice_being_typed() {
+        let game = QuizGame::new(
+            vec![make_question(&["paris", "london", "lisbon", "rome"], 0)],
+            Language::English,
+        );
+        assert_eq!(game.wrong_choice_for_prefix("lo"), Some(1));
+        assert_eq!(game.wrong_choice_for_prefix("li"), Some(2));
+        assert_eq!(game.wrong_choice_for_prefix("pa"), None, "correct choice");
+        assert_eq!(game.wrong_choice_for_prefix("x"), None);
+        assert_eq!(game.wrong_choice_for_prefix(""), None);
+    }
+
+    #[test]
+    fn hints_can_be_switched_off() {
+        let mut game = QuizGame::new(vec![make_question(&["a", "b"], 0)], Language::English);
+        game.set_hints_enabled(false);
+        assert_eq!(game.take_hint(), None);
+        assert!(game.answer_question(0, 1).is_some());
+        assert_eq!(game.get_final_score(), 100);
+        assert!(game.hint_log().is_empty());
+    }
+
+    #[test]
+    fn variants_file_records_in_their_own_lists() {
+        let entry = |score| ScoreEntry {
+            name: "p".into(),
+            score,
+            cpm: 0,
+            wpm: 0,
+            ts: String::new(),
+        };
+        let mut records = Records::default();
+        QuizVariant::Count(QUIZ_RUN_LENGTH).push_record(&mut records, entry(1));
+        QuizVariant::Count(30).push_record(&mut records, entry(2));
+        QuizVariant::Survival.push_record(&mut records, entry(3));
+        QuizVariant::Marathon("odd".into()).push_record(&mut records, entry(4));
+        QuizVariant::Timed.push_record(&mut records, entry(5));
+        assert_eq!(records.quiz_mode.len(), 1);
+        assert_eq!(records.quiz_count[&30][0].score, 2);
+        assert_eq!(records.survival[0].score, 3);
+        assert_eq!(records.marathon["odd"][0].score, 4);
+        assert_eq!(records.timed[0].score, 5);
+    }
+
     #[test]
     fn cpm_and_wpm_are_zero_before_any_correct_answer() {
         let q = make_question(&["a", "b", "c", "d"], 0);
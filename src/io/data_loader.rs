@@ -109,9 +109,19 @@ impl DataLoader {
             })
     }
 
+    /// Commentary for `language`, if the question has any. No fallback to
+    /// another language: an explanation in the wrong language is worse
+    /// than none on a Summary card.
+    pub fn get_explanation_text(question: &Question, language: &Language) -> Option<String> {
+        question
+            .explanation
+            .get(language.code())
+            .filter(|text| !text.trim().is_empty())
+            .cloned()
+    }
+
     /// Phonetic reading form (hiragana for ja). Falls back to display text when
     /// the reading field is absent. Used by TTS / RPG audio paths.
-    #[allow(dead_code)]
     pub fn get_question_reading_text(question: &Question, language: &Language) -> String {
         question
             .question_text_reading
@@ -240,6 +250,9 @@ mod tests {
             correct_answer_index: 0,
             image_path: None,
             ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
         };
         assert_eq!(
             DataLoader::get_question_reading_text(&question, &Language::Japanese),
@@ -261,6 +274,9 @@ mod tests {
             correct_answer_index: 0,
             image_path: None,
             ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
         };
         assert_eq!(
             DataLoader::get_question_reading_text(&question, &Language::Japanese),
@@ -0,0 +1,611 @@
+//! Import question sets from spreadsheets and Anki text exports.
+//!
+//! Rows are mapped onto `Question` / `Choice` through a column list. For
+//! CSV / TSV the first row is the header; for Anki (`Notes in Plain Text`
+//! export) there is no header, so the mapping defaults to
+//! `question.<lang>,answer.<lang>` (Front / Back) unless `--columns` says
+//! otherwise.
+//!
+//! Column names follow the editor's field captions:
+//!
+//! | column            | meaning                                              |
+//! |-------------------|------------------------------------------------------|
+//! | `id`, `genre`     | copied as-is (ids are generated when empty)          |
+//! | `question.<lang>` | `question_text`                                      |
+//! | `reading.<lang>`  | `question_text_reading`                              |
+//! | `explanation.<lang>` | `explanation`                                     |
+//! | `source`, `reference` | copied as-is                                     |
+//! | `A.<lang>`..`D.<lang>` | choice labels                                   |
+//! | `A.typings`..     | `ja_typings`, separated by `,` or `\|`               |
+//! | `correct`         | `A`-`D` or a 0-based index                           |
+//! | `answer.<lang>`   | single correct answer; the other three choices are   |
+//! |                   | borrowed from neighbouring rows                      |
+//! | `-` / `tags`      | ignored                                              |
+//!
+//! Kana-only `ja` labels without explicit typings get `ja_typings`
+//! generated through `io::romaji`, the same conversion the runtime uses.
+
+use super::romaji::{contains_han, hiragana_to_hepburn_variants};
+use super::shape::CHOICES_PER_QUESTION;
+use super::Pack;
+use crate::types::{Choice, Language, Question};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Tsv,
+    Anki,
+}
+
+impl ImportFormat {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "csv" => Ok(ImportFormat::Csv),
+            "tsv" => Ok(ImportFormat::Tsv),
+            "anki" => Ok(ImportFormat::Anki),
+            other => Err(format!(
+                "不明な形式: '{other}'. csv / tsv / anki を指定してください"
+            )),
+        }
+    }
+
+    /// Guess from the file extension: `.tsv` → TSV, `.txt` → Anki (its
+    /// default export extension), anything else → CSV.
+    pub fn from_path(path: &str) -> Self {
+        let lower = path.to_lowercase();
+        if lower.ends_with(".tsv") {
+            ImportFormat::Tsv
+        } else if lower.ends_with(".txt") {
+            ImportFormat::Anki
+        } else {
+            ImportFormat::Csv
+        }
+    }
+}
+
+/// Where one input column ends up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    Id,
+    Genre,
+    Text(String),
+    Reading(String),
+    Explanation(String),
+    Source,
+    Reference,
+    Label(usize, String),
+    Typings(usize),
+    Correct,
+    Answer(String),
+    Ignore,
+}
+
+impl Column {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+        let lower = name.to_lowercase();
+        match lower.as_str() {
+            "" | "-" | "tags" => return Ok(Column::Ignore),
+            "id" => return Ok(Column::Id),
+            "genre" => return Ok(Column::Genre),
+            "correct" => return Ok(Column::Correct),
+            "source" => return Ok(Column::Source),
+            "reference" => return Ok(Column::Reference),
+            _ => {}
+        }
+        let Some((head, tail)) = lower.split_once('.') else {
+            return Err(format!("unknown column {name:?}"));
+        };
+        if tail.is_empty() {
+            return Err(format!("column {name:?} is missing a language"));
+        }
+        match head {
+            "question" => Ok(Column::Text(tail.to_string())),
+            "reading" => Ok(Column::Reading(tail.to_string())),
+            "explanation" => Ok(Column::Explanation(tail.to_string())),
+            "answer" => Ok(Column::Answer(tail.to_string())),
+            "a" | "b" | "c" | "d" => {
+                let idx = (head.as_bytes()[0] - b'a') as usize;
+                if tail == "typings" {
+                    Ok(Column::Typings(idx))
+                } else {
+                    Ok(Column::Label(idx, tail.to_string()))
+                }
+            }
+            _ => Err(format!("unknown column {name:?}")),
+        }
+    }
+
+    /// Parse a comma-separated `--columns` spec.
+    pub fn parse_list(spec: &str) -> Result<Vec<Self>, String> {
+        spec.split(',').map(Column::parse).collect()
+    }
+}
+
+pub struct ImportOptions {
+    pub format: ImportFormat,
+    /// Explicit column mapping. `None` means "use the header row" for
+    /// CSV / TSV and Front / Back for Anki.
+    pub columns: Option<Vec<Column>>,
+    /// Genre for rows that don't carry a `genre` column.
+    pub genre: String,
+    /// Language of the Front / Back fields in the Anki default mapping.
+    pub language: Language,
+}
+
+pub struct ImportReport {
+    pub questions: Vec<Question>,
+    /// Human-readable reasons for rows that were dropped (1-based line
+    /// numbers of the data rows).
+    pub skipped: Vec<String>,
+}
+
+/// Turn `text` into questions. `existing` is the pack the result will be
+/// appended to; generated ids continue after its highest `cNNNN`.
+pub fn import_questions(
+    text: &str,
+    options: &ImportOptions,
+    existing: &[Question],
+) -> Result<ImportReport, Box<dyn std::error::Error>> {
+    let mut rows = match options.format {
+        ImportFormat::Csv => parse_delimited(text, ','),
+        ImportFormat::Tsv => parse_delimited(text, '\t'),
+        ImportFormat::Anki => parse_anki(text)?,
+    };
+
+    let columns = match (&options.columns, options.format) {
+        (Some(columns), _) => columns.clone(),
+        (None, ImportFormat::Anki) => {
+            let lang = options.language.code().to_string();
+            vec![Column::Text(lang.clone()), Column::Answer(lang)]
+        }
+        (None, _) => {
+            if rows.is_empty() {
+                return Ok(ImportReport {
+                    questions: Vec::new(),
+                    skipped: Vec::new(),
+                });
+            }
+            let header = rows.remove(0);
+            header
+                .iter()
+                .map(|h| Column::parse(h))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+    };
+
+    let answer_mode = columns.iter().any(|c| matches!(c, Column::Answer(_)));
+    let mut drafts: Vec<(Question, HashMap<String, String>)> = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (row_idx, row) in rows.iter().enumerate() {
+        if row.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        match build_row(row, &columns, &options.genre) {
+            Ok(draft) => drafts.push(draft),
+            Err(reason) => skipped.push(format!("row {}: {reason}", row_idx + 1)),
+        }
+    }
+
+    if answer_mode {
+        fill_distractors(&mut drafts);
+    }
+
+    let mut all: Vec<Question> = existing.to_vec();
+    let mut questions = Vec::with_capacity(drafts.len());
+    for (mut question, _) in drafts {
+        if question.id.is_empty() {
+            question.id = Pack::next_id(&all);
+        }
+        for choice in &mut question.choices {
+            fill_kana_typings(choice);
+        }
+        all.push(question.clone());
+        questions.push(question);
+    }
+
+    Ok(ImportReport { questions, skipped })
+}
+
+/// Build one question from a row. The second tuple element carries the
+/// `answer.<lang>` cells (empty outside answer mode).
+fn build_row(
+    row: &[String],
+    columns: &[Column],
+    default_genre: &str,
+) -> Result<(Question, HashMap<String, String>), String> {
+    let mut question = Question {
+        id: String::new(),
+        genre: default_genre.to_string(),
+        question_text: HashMap::new(),
+        question_text_reading: HashMap::new(),
+        choices: vec![Choice::default(); CHOICES_PER_QUESTION],
+        correct_answer_index: 0,
+        image_path: None,
+        ja_reviewed: false,
+        explanation: HashMap::new(),
+        source: None,
+        reference: None,
+    };
+    let mut answers: HashMap<String, String> = HashMap::new();
+
+    for (column, cell) in columns.iter().zip(row.iter()) {
+        let cell = cell.trim();
+        if cell.is_empty() {
+            continue;
+        }
+        match column {
+            Column::Id => question.id = cell.to_string(),
+            Column::Genre => question.genre = cell.to_string(),
+            Column::Text(lang) => {
+                question
+                    .question_text
+                    .insert(lang.clone(), cell.to_string());
+            }
+            Column::Reading(lang) => {
+                question
+                    .question_text_reading
+                    .insert(lang.clone(), cell.to_string());
+            }
+            Column::Explanation(lang) => {
+                question.explanation.insert(lang.clone(), cell.to_string());
+            }
+            Column::Source => question.source = Some(cell.to_string()),
+            Column::Reference => question.reference = Some(cell.to_string()),
+            Column::Label(idx, lang) => {
+                question.choices[*idx]
+                    .labels
+                    .insert(lang.clone(), cell.to_string());
+            }
+            Column::Typings(idx) => {
+                question.choices[*idx].ja_typings = cell
+                    .split([',', '|'])
+                    .map(|t| t.trim().to_lowercase())
+                    .filter(|t| !t.is_empty())
+                    .collect();
+            }
+            Column::Correct => question.correct_answer_index = parse_correct(cell)?,
+            Column::Answer(lang) => {
+                answers.insert(lang.clone(), cell.to_string());
+            }
+            Column::Ignore => {}
+        }
+    }
+
+    if question.question_text.is_empty() {
+        return Err("no question text".to_string());
+    }
+    if !answers.is_empty() {
+        question.choices[0].labels = answers.clone();
+        question.correct_answer_index = 0;
+    }
+    Ok((question, answers))
+}
+
+fn parse_correct(cell: &str) -> Result<usize, String> {
+    let upper = cell.to_uppercase();
+    let idx = match upper.as_str() {
+        "A" => 0,
+        "B" => 1,
+        "C" => 2,
+        "D" => 3,
+        other => other
+            .parse::<usize>()
+            .map_err(|_| format!("correct {cell:?} is not A-D or a 0-based index"))?,
+    };
+    if idx >= CHOICES_PER_QUESTION {
+        return Err(format!("correct {cell:?} is out of range"));
+    }
+    Ok(idx)
+}
+
+/// Answer mode: choices 1..4 borrow the answers of the following rows
+/// (wrapping around). Neighbouring rows in a deck tend to share a topic,
+/// which makes for plausible distractors, and the result is deterministic
+/// so re-importing the same file produces the same pack. Answers equal to
+/// the correct one are skipped. Decks with fewer than four distinct
+/// answers leave the remaining choices empty for the validator to report.
+fn fill_distractors(drafts: &mut [(Question, HashMap<String, String>)]) {
+    let answers: Vec<HashMap<String, String>> = drafts.iter().map(|(_, a)| a.clone()).collect();
+    let n = answers.len();
+    for (i, (question, own)) in drafts.iter_mut().enumerate() {
+        if own.is_empty() {
+            continue;
+        }
+        let mut slot = 1;
+        for offset in 1..n {
+            if slot >= CHOICES_PER_QUESTION {
+                break;
+            }
+            let candidate = &answers[(i + offset) % n];
+            if candidate.is_empty() || candidate == own {
+                continue;
+            }
+            let taken = question.choices[..slot]
+                .iter()
+                .any(|c| c.labels == *candidate);
+            if taken {
+                continue;
+            }
+            question.choices[slot].labels = candidate.clone();
+            slot += 1;
+        }
+    }
+}
+
+/// Generate `ja_typings` for a kana-only `ja` label that has none. ASCII
+/// labels are typed verbatim and kanji labels can't be read mechanically,
+/// so both are left alone (the validator flags the kanji case).
+fn fill_kana_typings(choice: &mut Choice) {
+    if !choice.ja_typings.is_empty() {
+        return;
+    }
+    let Some(ja) = choice.labels.get("ja") else {
+        return;
+    };
+    if ja.is_ascii() || contains_han(ja) {
+        return;
+    }
+    choice.ja_typings = hiragana_to_hepburn_variants(ja);
+}
+
+/// Minimal RFC 4180 reader: quoted fields, doubled quotes, embedded
+/// delimiters / newlines, CRLF line endings, and a leading UTF-8 BOM.
+pub fn parse_delimited(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c if c == delimiter => row.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Anki "Notes in Plain Text" export: `#key:value` header lines, HTML in
+/// fields, separated by whatever `#separator:` names (tab when absent).
+fn parse_anki(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut delimiter = '\t';
+    for line in text.lines().filter(|line| line.starts_with('#')) {
+        if let Some(name) = line.strip_prefix("#separator:") {
+            delimiter = anki_separator(name.trim())?;
+        }
+    }
+    let body: String = text
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(parse_delimited(&body, delimiter)
+        .into_iter()
+        .map(|row| row.iter().map(|cell| strip_html(cell)).collect())
+        .collect())
+}
+
+/// The character behind an Anki `#separator:` value: one of the names
+/// Anki writes, or the character itself.
+fn anki_separator(name: &str) -> Result<char, String> {
+    let delimiter = match name.to_ascii_lowercase().as_str() {
+        "tab" => '\t',
+        "comma" => ',',
+        "semicolon" => ';',
+        "space" => ' ',
+        "pipe" => '|',
+        "colon" => ':',
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => return Err(format!("unknown Anki separator '{name}'")),
+            }
+        }
+    };
+    Ok(delimiter)
+}
+
+/// Drop tags (`<br>` becomes a space) and decode the handful of entities
+/// Anki emits.
+fn strip_html(s: &str) -> String {
+    let mut out = String::new();
+    let mut in_tag = false;
+    let mut tag = String::new();
+    for c in s.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                tag.clear();
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                let name = tag.trim_start_matches('/').to_lowercase();
+                if name.starts_with("br") || name.starts_with("div") {
+                    out.push(' ');
+                }
+            }
+            c if in_tag => tag.push(c),
+            c => out.push(c),
+        }
+    }
+    let decoded = out
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(format: ImportFormat) -> ImportOptions {
+        ImportOptions {
+            format,
+            columns: None,
+            genre: "imported".into(),
+            language: Language::Japanese,
+        }
+    }
+
+    #[test]
+    fn parse_delimited_handles_quotes_and_crlf() {
+        let rows = parse_delimited("a,\"b,c\",\"say \"\"hi\"\"\"\r\n1,2,3", ',');
+        assert_eq!(
+            rows,
+            vec![
+                vec!["a".to_string(), "b,c".into(), "say \"hi\"".into()],
+                vec!["1".to_string(), "2".into(), "3".into()],
+            ]
+        );
+    }
+
+    #[test]
+    fn column_names_parse() {
+        assert_eq!(Column::parse("question.ja"), Ok(Column::Text("ja".into())));
+        assert_eq!(Column::parse("B.en"), Ok(Column::Label(1, "en".into())));
+        assert_eq!(Column::parse("d.typings"), Ok(Column::Typings(3)));
+        assert_eq!(Column::parse("tags"), Ok(Column::Ignore));
+        assert_eq!(
+            Column::parse("Explanation.en"),
+            Ok(Column::Explanation("en".into()))
+        );
+        assert_eq!(Column::parse("source"), Ok(Column::Source));
+        assert!(Column::parse("question.").is_err());
+        assert!(Column::parse("bogus").is_err());
+    }
+
+    #[test]
+    fn csv_with_header_maps_choices_and_generates_kana_typings() {
+        let csv = "genre,question.ja,A.ja,B.ja,C.ja,D.ja,B.typings,correct\n\
+                   animal,なに?,いぬ,猫,トリ,uma,neko,B\n";
+        let report = import_questions(csv, &options(ImportFormat::Csv), &[]).expect("import");
+        assert!(report.skipped.is_empty());
+        let q = &report.questions[0];
+        assert_eq!(q.id, "c0001");
+        assert_eq!(q.genre, "animal");
+        assert_eq!(q.correct_answer_index, 1);
+        assert_eq!(q.choices[0].ja_typings, vec!["inu"]);
+        assert_eq!(q.choices[1].ja_typings, vec!["neko"]);
+        assert_eq!(q.choices[2].ja_typings, vec!["tori"]);
+        // ASCII label: typed verbatim at runtime, nothing generated.
+        assert!(q.choices[3].ja_typings.is_empty());
+    }
+
+    #[test]
+    fn explanation_and_source_columns_are_copied() {
+        let csv = "question.en,answer.en,explanation.en,source,reference\n\
+                   Capital of Peru?,Lima,Founded in 1535 by `Pizarro`.,Britannica,https://example.org/lima\n";
+        let report = import_questions(csv, &options(ImportFormat::Csv), &[]).expect("import");
+        let q = &report.questions[0];
+        assert_eq!(q.explanation["en"], "Founded in 1535 by `Pizarro`.");
+        assert_eq!(q.source.as_deref(), Some("Britannica"));
+        assert_eq!(q.reference.as_deref(), Some("https://example.org/lima"));
+    }
+
+    #[test]
+    fn rows_without_question_text_are_skipped() {
+        let csv = "question.en,A.en\n,orphan\nWhat?,that\n";
+        let report = import_questions(csv, &options(ImportFormat::Csv), &[]).expect("import");
+        assert_eq!(report.questions.len(), 1);
+        assert_eq!(report.skipped, vec!["row 1: no question text".to_string()]);
+    }
+
+    #[test]
+    fn anki_export_uses_front_back_and_borrows_distractors() {
+        let anki = "#separator:tab\n#html:true\n\
+                    いぬ<br>は?\tいぬ\n\
+                    ねこ&nbsp;は?\tねこ\n\
+                    とり\tとり\n\
+                    うま\tうま\n";
+        let report = import_questions(anki, &options(ImportFormat::Anki), &[]).expect("import");
+        assert_eq!(report.questions.len(), 4);
+        let q = &report.questions[0];
+        assert_eq!(q.question_text["ja"], "いぬ は?");
+        assert_eq!(q.correct_answer_index, 0);
+        let labels: Vec<&str> = q.choices.iter().map(|c| c.labels["ja"].as_str()).collect();
+        assert_eq!(labels, vec!["いぬ", "ねこ", "とり", "うま"]);
+        assert_eq!(report.questions[1].question_text["ja"], "ねこ は?");
+    }
+
+    #[test]
+    fn anki_export_honours_the_separator_header() {
+        let anki = "#separator:Semicolon\n#html:false\nいぬは?;いぬ\nねこは?;\"ね;こ\"\n";
+        let report = import_questions(anki, &options(ImportFormat::Anki), &[]).expect("import");
+        assert_eq!(report.questions.len(), 2);
+        assert_eq!(report.questions[0].question_text["ja"], "いぬは?");
+        let q = &report.questions[1];
+        assert_eq!(q.choices[q.correct_answer_index].labels["ja"], "ね;こ");
+
+        let comma = import_questions(
+            "#separator:,\nうまは?,うま\n",
+            &options(ImportFormat::Anki),
+            &[],
+        )
+        .expect("import");
+        assert_eq!(comma.questions[0].question_text["ja"], "うまは?");
+
+        assert!(
+            import_questions("#separator:Newline\nx\n", &options(ImportFormat::Anki), &[]).is_err()
+        );
+    }
+
+    #[test]
+    fn generated_ids_continue_after_existing_pack() {
+        let existing = import_questions(
+            "question.en,id\nOld?,c0007\n",
+            &options(ImportFormat::Csv),
+            &[],
+        )
+        .expect("import")
+        .questions;
+        let report = import_questions(
+            "question.en\nNew?\n",
+            &options(ImportFormat::Csv),
+            &existing,
+        )
+        .expect("import");
+        assert_eq!(report.questions[0].id, "c0008");
+    }
+
+    #[test]
+    fn correct_accepts_letters_and_indices() {
+        assert_eq!(parse_correct("c"), Ok(2));
+        assert_eq!(parse_correct("3"), Ok(3));
+        assert!(parse_correct("4").is_err());
+        assert!(parse_correct("x").is_err());
+    }
+}
@@ -1,10 +1,14 @@
 pub mod data_loader;
+pub mod import;
 pub mod normalize;
+pub mod pack;
 pub mod romaji;
+pub mod shape;
 pub mod storage;
 pub mod validator;
 
 pub use data_loader::DataLoader;
+pub use pack::Pack;
 pub use storage::Storage;
 pub use validator::{find_prefix_conflicts, format_conflict};
 // PrefixConflict stays accessible via `io::validator::PrefixConflict` for the
@@ -0,0 +1,189 @@
+//! Custom question packs.
+//!
+//! A pack is a plain `Vec<Question>` JSON file — the same schema as the
+//! bundled `data/questions_<lang>.json`, so `DataLoader::load_questions`
+//! reads it unchanged. Packs live outside the bundled bank so authors can
+//! iterate on their own questions without touching shipped data.
+//!
+//! Writes use `serde_json::to_string_pretty` plus a trailing newline, the
+//! same shape `backfill-ja-typing` produces. `Question` keeps its language
+//! labels in `HashMap`s, whose iteration order is random, so
+//! [`Pack::to_json`] re-orders those keys (`ja`, `en`, other languages
+//! alphabetically, then `ja_typings`) before printing. With serde_json's
+//! `preserve_order` feature that reproduces the bundled files byte for
+//! byte, so a pack diff against the bundled bank stays readable.
+
+use crate::types::Question;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+pub struct Pack;
+
+impl Pack {
+    /// Load a pack from disk. A missing file is an empty pack (not an
+    /// error) so the editor can start authoring into a fresh path.
+    pub fn load(file_path: &str) -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+        if !Path::new(file_path).exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(file_path)?;
+        let questions: Vec<Question> = serde_json::from_str(&content)?;
+        Ok(questions)
+    }
+
+    /// Write `questions` to `file_path`, creating the parent directory if
+    /// needed.
+    pub fn save(file_path: &str, questions: &[Question]) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = Path::new(file_path).parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        fs::write(file_path, Self::to_json(questions)?)?;
+        Ok(())
+    }
+
+    /// Serialise `questions` in the bundled files' canonical layout
+    /// (pretty-printed, stable key order, trailing newline).
+    pub fn to_json(questions: &[Question]) -> Result<String, Box<dyn std::error::Error>> {
+        let mut value = serde_json::to_value(questions)?;
+        if let Some(items) = value.as_array_mut() {
+            for question in items {
+                canonicalize_question(question);
+            }
+        }
+        let mut content = serde_json::to_string_pretty(&value)?;
+        content.push('\n');
+        Ok(content)
+    }
+
+    /// Next free `cNNNN` id for a pack. Custom ids use a `c` prefix so they
+    /// never collide with the bundled `qNNNN` range when a pack is merged
+    /// into the main bank.
+    pub fn next_id(questions: &[Question]) -> String {
+        let max = questions
+            .iter()
+            .filter_map(|q| q.id.strip_prefix('c'))
+            .filter_map(|n| n.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0);
+        format!("c{:04}", max + 1)
+    }
+}
+
+/// Sort rank for language-keyed objects: `ja`, `en`, any other language
+/// (alphabetical via the tie-break), then `ja_typings` last.
+fn key_rank(key: &str) -> u8 {
+    match key {
+        "ja" => 0,
+        "en" => 1,
+        "ja_typings" => 3,
+        _ => 2,
+    }
+}
+
+fn sort_keys(value: &mut Value) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    let mut entries: Vec<(String, Value)> = std::mem::take(obj).into_iter().collect();
+    entries.sort_by(|(a, _), (b, _)| key_rank(a).cmp(&key_rank(b)).then_with(|| a.cmp(b)));
+    *obj = entries.into_iter().collect::<Map<String, Value>>();
+}
+
+fn canonicalize_question(question: &mut Value) {
+    for key in ["question_text", "question_text_reading", "explanation"] {
+        if let Some(map) = question.get_mut(key) {
+            sort_keys(map);
+        }
+    }
+    if let Some(choices) = question.get_mut("choices").and_then(Value::as_array_mut) {
+        for choice in choices {
+            sort_keys(choice);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Choice;
+    use std::collections::HashMap;
+    use std::env::temp_dir;
+
+    fn unique_path(prefix: &str) -> String {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir = temp_dir();
+        format!("{}/type-globe-{prefix}-{nanos}/pack.json", dir.display())
+    }
+
+    fn q(id: &str) -> Question {
+        let mut question_text = HashMap::new();
+        question_text.insert("en".to_string(), "What?".to_string());
+        let mut labels = HashMap::new();
+        labels.insert("en".to_string(), "that".to_string());
+        Question {
+            id: id.into(),
+            genre: "custom".into(),
+            question_text,
+            question_text_reading: HashMap::new(),
+            choices: vec![Choice {
+                labels,
+                ja_typings: Vec::new(),
+            }],
+            correct_answer_index: 0,
+            image_path: None,
+            ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
+        }
+    }
+
+    #[test]
+    fn load_missing_pack_is_empty() {
+        let path = unique_path("pack-missing");
+        assert!(Pack::load(&path).expect("load").is_empty());
+    }
+
+    #[test]
+    fn save_then_load_round_trip_creates_parent_dir() {
+        let path = unique_path("pack-roundtrip");
+        Pack::save(&path, &[q("c0001"), q("c0002")]).expect("save");
+        let text = std::fs::read_to_string(&path).expect("read");
+        assert!(text.ends_with("]\n"));
+        let loaded = Pack::load(&path).expect("load");
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].id, "c0002");
+        if let Some(dir) = Path::new(&path).parent() {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+
+    #[test]
+    fn to_json_reproduces_bundled_layout() {
+        // Reads the JSON directly so this also compiles when `pack.rs` is
+        // `#[path]`-included into the `bank` binary.
+        for path in ["data/questions_ja.json", "data/questions_en.json"] {
+            let text = std::fs::read_to_string(path).expect("read bundled bank");
+            let questions: Vec<Question> = serde_json::from_str(&text).expect("parse");
+            assert!(
+                Pack::to_json(&questions).expect("serialise") == text,
+                "{path} drifted"
+            );
+        }
+    }
+
+    #[test]
+    fn next_id_skips_past_highest_custom_id() {
+        assert_eq!(Pack::next_id(&[]), "c0001");
+        assert_eq!(
+            Pack::next_id(&[q("c0003"), q("q0100"), q("c0001")]),
+            "c0004"
+        );
+    }
+}
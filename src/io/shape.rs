@@ -0,0 +1,225 @@
+//! Question shape checks — the "separate validator" that `validator.rs`
+//! explicitly leaves out of its scope.
+//!
+//! `validator::find_prefix_conflicts` only cares about typing ambiguity.
+//! Everything else that makes a question unplayable (wrong choice count,
+//! out-of-range correct index, a language present on the question but
+//! missing on a choice, kanji labels without `ja_typings`, …) is reported
+//! here. The question editor runs this on every keystroke, so the checks
+//! are cheap and never touch disk.
+
+use super::romaji::{contains_han, hiragana_to_hepburn_variants};
+use crate::types::Question;
+use std::collections::HashSet;
+
+/// Number of choices `QuizUI` renders (labels A-D, per `docs/spec.md`).
+pub const CHOICES_PER_QUESTION: usize = 4;
+
+/// One shape problem in a single question.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeIssue {
+    pub question_id: String,
+    pub message: String,
+}
+
+/// Check a single question. Results are ordered top-to-bottom in the
+/// same order the fields appear in the JSON, so the editor can list them
+/// next to the form without re-sorting.
+pub fn check_question(question: &Question) -> Vec<ShapeIssue> {
+    let mut messages: Vec<String> = Vec::new();
+
+    if question.id.trim().is_empty() {
+        messages.push("id is empty".to_string());
+    }
+    if question.genre.trim().is_empty() {
+        messages.push("genre is empty".to_string());
+    }
+
+    let mut languages: Vec<&String> = question
+        .question_text
+        .iter()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(lang, _)| lang)
+        .collect();
+    languages.sort();
+    if languages.is_empty() {
+        messages.push("question_text is empty in every language".to_string());
+    }
+
+    if question.choices.len() != CHOICES_PER_QUESTION {
+        messages.push(format!(
+            "expected {CHOICES_PER_QUESTION} choices, found {}",
+            question.choices.len()
+        ));
+    }
+    if question.correct_answer_index >= question.choices.len() {
+        messages.push(format!(
+            "correct_answer_index {} is out of range",
+            question.correct_answer_index
+        ));
+    }
+
+    for lang in &languages {
+        let mut seen: HashSet<&str> = HashSet::new();
+        for (idx, choice) in question.choices.iter().enumerate() {
+            match choice.labels.get(lang.as_str()).map(|l| l.trim()) {
+                None | Some("") => {
+                    messages.push(format!("choice #{idx} has no {lang} label"));
+                }
+                Some(label) => {
+                    if !seen.insert(label) {
+                        messages.push(format!("choice #{idx} duplicates {lang} label {label:?}"));
+                    }
+                }
+            }
+        }
+    }
+
+    for (idx, choice) in question.choices.iter().enumerate() {
+        for typing in &choice.ja_typings {
+            if !typing.is_ascii() || typing.trim().is_empty() {
+                messages.push(format!("choice #{idx} has invalid ja_typing {typing:?}"));
+            }
+        }
+        let Some(ja) = choice.labels.get("ja") else {
+            continue;
+        };
+        if !choice.ja_typings.is_empty() || ja.is_ascii() {
+            continue;
+        }
+        if contains_han(ja) {
+            messages.push(format!("choice #{idx} ja={ja:?} needs ja_typings (kanji)"));
+        } else if hiragana_to_hepburn_variants(ja).is_empty() {
+            messages.push(format!("choice #{idx} ja={ja:?} has no typeable reading"));
+        }
+    }
+
+    messages
+        .into_iter()
+        .map(|message| ShapeIssue {
+            question_id: question.id.clone(),
+            message,
+        })
+        .collect()
+}
+
+/// Check every question plus pack-wide invariants (duplicate ids).
+pub fn check_questions(questions: &[Question]) -> Vec<ShapeIssue> {
+    let mut issues = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+    for question in questions {
+        if !question.id.is_empty() && !seen.insert(question.id.as_str()) {
+            issues.push(ShapeIssue {
+                question_id: question.id.clone(),
+                message: "duplicate id".to_string(),
+            });
+        }
+        issues.extend(check_question(question));
+    }
+    issues
+}
+
+/// Format a single issue, mirroring `validator::format_conflict`.
+pub fn format_issue(issue: &ShapeIssue) -> String {
+    format!("[shape] question {}: {}", issue.question_id, issue.message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Choice;
+    use std::collections::HashMap;
+
+    fn choice(ja: &str, en: &str) -> Choice {
+        let mut labels = HashMap::new();
+        labels.insert("ja".to_string(), ja.to_string());
+        labels.insert("en".to_string(), en.to_string());
+        Choice {
+            labels,
+            ja_typings: Vec::new(),
+        }
+    }
+
+    fn valid() -> Question {
+        let mut question_text = HashMap::new();
+        question_text.insert("ja".to_string(), "いろは?".to_string());
+        question_text.insert("en".to_string(), "Which?".to_string());
+        Question {
+            id: "c0001".into(),
+            genre: "custom".into(),
+            question_text,
+            question_text_reading: HashMap::new(),
+            choices: vec![
+                choice("いぬ", "dog"),
+                choice("ねこ", "cat"),
+                choice("とり", "bird"),
+                choice("うま", "horse"),
+            ],
+            correct_answer_index: 0,
+            image_path: None,
+            ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
+        }
+    }
+
+    #[test]
+    fn valid_question_has_no_issues() {
+        assert!(check_question(&valid()).is_empty());
+    }
+
+    #[test]
+    fn flags_choice_count_and_index() {
+        let mut q = valid();
+        q.choices.truncate(2);
+        q.correct_answer_index = 3;
+        let issues = check_question(&q);
+        assert!(issues.iter().any(|i| i.message.contains("expected 4")));
+        assert!(issues.iter().any(|i| i.message.contains("out of range")));
+    }
+
+    #[test]
+    fn flags_missing_and_duplicate_labels() {
+        let mut q = valid();
+        q.choices[1].labels.remove("en");
+        q.choices[2].labels.insert("ja".into(), "いぬ".into());
+        let issues = check_question(&q);
+        assert!(issues
+            .iter()
+            .any(|i| i.message == "choice #1 has no en label"));
+        assert!(issues
+            .iter()
+            .any(|i| i.message.contains("choice #2 duplicates ja")));
+    }
+
+    #[test]
+    fn flags_kanji_label_without_typings() {
+        let mut q = valid();
+        q.choices[0].labels.insert("ja".into(), "東京".into());
+        assert!(check_question(&q)
+            .iter()
+            .any(|i| i.message.contains("needs ja_typings")));
+        q.choices[0].ja_typings = vec!["toukyou".into()];
+        assert!(check_question(&q).is_empty());
+    }
+
+    #[test]
+    fn flags_non_ascii_typing() {
+        let mut q = valid();
+        q.choices[0].ja_typings = vec!["いぬ".into()];
+        assert!(check_question(&q)
+            .iter()
+            .any(|i| i.message.contains("invalid ja_typing")));
+    }
+
+    #[test]
+    fn check_questions_flags_duplicate_ids() {
+        let issues = check_questions(&[valid(), valid()]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            format_issue(&issues[0]),
+            "[shape] question c0001: duplicate id"
+        );
+    }
+}
@@ -166,6 +166,9 @@ mod tests {
             correct_answer_index: 0,
             image_path: None,
             ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
         }
     }
 
@@ -270,6 +273,9 @@ mod tests {
             correct_answer_index: 0,
             image_path: None,
             ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
         };
         // cross-choice prefix は #70 で無視する方針なので no conflict。
         assert!(find_prefix_conflicts(&[q]).is_empty());
@@ -312,6 +318,9 @@ mod tests {
             correct_answer_index: 0,
             image_path: None,
             ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
         };
         // cross-choice prefix は #70 で無視する方針なので no conflict。
         assert!(find_prefix_conflicts(&[q]).is_empty());
@@ -2,18 +2,29 @@ mod audio;
 mod config;
 mod game;
 mod io;
+mod settings;
 mod types;
 mod ui;
 
-use audio::TtsEngine;
+use audio::engine::LocalEngine;
+use audio::speech::SpeechOptions;
+use audio::tts::TtsEngine;
 use clap::{Parser, Subcommand};
 use config::Config;
-use game::ListeningSession;
-use io::{DataLoader, Storage};
-use std::io::{stdin, stdout, Write};
+use game::{ListeningSession, QuizVariant};
+use io::import::{Column, ImportFormat, ImportOptions};
+use io::{DataLoader, Pack, Storage};
+use settings::{ReadAloud, Settings};
+use std::collections::HashMap;
+use std::io::{stdin, stdout, IsTerminal, Write};
 use std::time::Duration;
 use types::{AnswerKind, GameMode, Language, ListeningPrompt, Question};
-use ui::{tts_unavailable_message, DemoInputSource, ListenUI, MenuUI, QuizUI, RecordsUI};
+use ui::keymap::Keymap;
+use ui::theme::{Palette, Theme};
+use ui::{
+    tts_unavailable_message, DemoInputSource, EditorUI, ListenUI, MenuUI, PlainListen, PlainQuiz,
+    QuizUI, RecordsUI, SettingsUI,
+};
 
 // ---------------------------------------------------------------------------
 // CLI definition (#48)
@@ -59,6 +70,21 @@ struct Cli {
     /// デモモードのジャンル絞り込み。指定したジャンルの問題だけから出題する。
     #[arg(long)]
     genre: Option<String>,
+
+    /// 色数を指定する (auto / truecolor / 256 / 16 / none)。auto は
+    /// NO_COLOR / COLORTERM / TERM から判定する。
+    #[arg(long, global = true, default_value = "auto", value_parser = parse_color_mode)]
+    color: ColorMode,
+
+    /// settings.yaml の値をこの起動だけ上書きする（例: `--set volume=40`）。
+    /// 複数指定可。環境変数 TYPE_GLOBE_<KEY> より優先される。
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE", value_parser = settings::parse_assignment)]
+    set: Vec<(String, String)>,
+
+    /// 画面読み上げ・パイプ向けのプレーン表示で遊ぶ（代替画面・色・罫線なし、
+    /// 1 行ずつ入出力）。標準出力が端末でないときは自動で有効。
+    #[arg(long, global = true)]
+    plain: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -76,6 +102,33 @@ enum Commands {
         /// 特定の問題IDから開始（スタブ: 受け取るが未実装）
         #[arg(long)]
         question: Option<String>,
+
+        /// ステルスモード（ログビューア風の偽装表示）で開始する。F8 で切替、F12 でボスキー
+        #[arg(long)]
+        stealth: bool,
+
+        /// 出題数を指定（1〜100）。settings.yaml の run_length より優先。
+        /// 既定の 10 問以外は出題数ごとに別の Records に記録される
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=settings::MAX_RUN_LENGTH as i64))]
+        count: Option<u32>,
+
+        /// サバイバル: 問題が尽きても再シャッフルして続き、最初のスキップで終了
+        #[arg(long, conflicts_with_all = ["count", "marathon"])]
+        survival: bool,
+
+        /// マラソン: 指定ジャンルの全問を一度ずつ出題
+        #[arg(long, value_name = "GENRE", conflicts_with = "count")]
+        marathon: Option<String>,
+
+        /// タイムアタック: 10 問それぞれに制限時間（答えの打鍵数に応じて延長）。
+        /// 時間切れは自動スキップ
+        #[arg(long, conflicts_with_all = ["count", "survival", "marathon"])]
+        timed: bool,
+
+        /// 練習モード: スキップした問題は後で再出題、誤答は終了後に正解つきで解説。
+        /// Records には記録しない
+        #[arg(long, conflicts_with = "survival")]
+        practice: bool,
     },
 
     /// ハクスラRPGモードを即開始
@@ -95,6 +148,10 @@ enum Commands {
         /// TTS 読み上げをスキップする
         #[arg(long)]
         no_tts: bool,
+
+        /// ステルスモード（ログビューア風の偽装表示）で開始する。F8 で切替、F12 でボスキー
+        #[arg(long)]
+        stealth: bool,
     },
 
     /// Time Attack 25 を即開始
@@ -114,16 +171,69 @@ enum Commands {
         #[arg(long, value_parser = parse_language)]
         lang: Option<Language>,
     },
+
+    /// 問題エディタを開く（カスタム問題パックの作成・編集）
+    Edit {
+        /// 編集するパックファイル。省略時はデータディレクトリの packs/custom.json
+        #[arg(long)]
+        pack: Option<String>,
+    },
+
+    /// CSV / TSV / Anki テキストエクスポートから問題パックを取り込む
+    Import {
+        /// 取り込むファイル
+        input: String,
+
+        /// 入力形式（csv / tsv / anki）。省略時は拡張子から推定（.tsv → tsv, .txt → anki）
+        #[arg(long, value_parser = ImportFormat::parse)]
+        format: Option<ImportFormat>,
+
+        /// 列の割り当て（例: `question.ja,answer.ja`）。省略時は CSV/TSV はヘッダ行、Anki は表/裏
+        #[arg(long)]
+        columns: Option<String>,
+
+        /// 追記先のパックファイル。省略時はデータディレクトリの packs/custom.json
+        #[arg(long)]
+        out: Option<String>,
+
+        /// genre 列が無い行に付けるジャンル
+        #[arg(long, default_value = "custom")]
+        genre: String,
+
+        /// Anki の表/裏フィールドの言語（ja / en, default ja）
+        #[arg(long, value_parser = parse_language)]
+        lang: Option<Language>,
+    },
+
+    /// 読み上げに使える TTS の声とローカル音声エンジンを一覧表示（* は選ばれる声）
+    Voices {
+        /// 言語を指定（ja / en）。省略時は両方
+        #[arg(long, value_parser = parse_language)]
+        lang: Option<Language>,
+    },
 }
 
 fn parse_language(s: &str) -> Result<Language, String> {
-    match s {
-        "ja" => Ok(Language::Japanese),
-        "en" => Ok(Language::English),
-        other => Err(format!(
-            "不明な言語コード: '{other}'. ja または en を指定してください"
-        )),
+    Language::from_code(s)
+        .ok_or_else(|| format!("不明な言語コード: '{s}'. ja または en を指定してください"))
+}
+
+/// `--color` の値。`Auto` は起動時に環境変数から判定する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorMode {
+    Auto,
+    Fixed(Palette),
+}
+
+fn parse_color_mode(s: &str) -> Result<ColorMode, String> {
+    if s == "auto" {
+        return Ok(ColorMode::Auto);
     }
+    Palette::from_name(s).map(ColorMode::Fixed).ok_or_else(|| {
+        format!(
+            "不明な色指定: '{s}'. auto / truecolor / 256 / 16 / none のいずれかを指定してください"
+        )
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -135,20 +245,40 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = Config::default();
 
     Storage::ensure_data_directory(&config.data_dir)?;
+    // settings.yaml の不備は警告して既定値で続行する。一方、環境変数と
+    // --set はその場で打った値なので、不正ならエラーで止める。
+    let settings = resolve_settings(load_settings_file(&config), &cli.set)?;
+    let palette = match cli.color {
+        ColorMode::Auto => Palette::detect(),
+        ColorMode::Fixed(palette) => palette,
+    };
+    install_theme(&config, &settings, palette);
+    // キーマップも設定ファイルと同じく、不正なファイルは警告してデフォルトのキー割り当てで続行する。
+    let keymap = Keymap::load(&config.keymap_file_path()).unwrap_or_else(|e| {
+        eprintln!("warning: {}: {e}", config.keymap_file_path());
+        Keymap::default()
+    });
+    ui::keymap::install(keymap);
 
     // --demo は最優先。サブコマンド経路を通さず、専用の auto-demo
     // ループに直行する。--demo 指定時はサブコマンドを無視する仕様。
     //
-    // 言語選択は --lang が未指定なら Japanese を採用する。demo は
+    // 言語選択は --lang が未指定なら settings.yaml の language、それも
+    // 無ければ Japanese を採用する。demo は
     // 無人ループ展示・OBS 録画・CI 動画生成など非対話用途が中心で、
     // TTY/stdin が無い環境で `resolve_language_or_select` の対話
     // プロンプトに詰まると死ぬため、ここでは絶対にプロンプトを出さない
     // (R-1)。日本語デフォルトは type-globe の主用途と既存データ量、
     // および日本人ユーザー優先方針に従う。
     if cli.demo {
-        let language = cli.lang.clone().unwrap_or(Language::Japanese);
+        let language = cli
+            .lang
+            .clone()
+            .or_else(|| settings.language())
+            .unwrap_or(Language::Japanese);
         return run_quiz_demo(
             &config,
+            &settings,
             &language,
             cli.genre.as_deref(),
             DemoOptions {
@@ -160,15 +290,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         );
     }
 
+    // --plain、または出力先が端末でなければ TUI を使わず 1 行ずつ遊ぶ
+    let plain = cli.plain || !stdout().is_terminal();
+
     match cli.command {
         // ---- サブコマンドなし: 従来どおりメインメニューへ ----
-        None => run_menu_loop(&config),
+        None if plain => run_plain_menu(&config, &settings),
+        None => run_menu_loop(&config, settings, &cli.set, palette),
 
         // ---- quiz サブコマンド ----
         Some(Commands::Quiz {
             lang,
             seed,
             question,
+            stealth,
+            count,
+            survival,
+            marathon,
+            timed,
+            practice,
         }) => {
             // TODO(#48): --seed は未実装。引数を受け取るのみ。
             if seed.is_some() {
@@ -179,8 +319,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 eprintln!("note: --question は現在未実装です（スタブ）");
             }
 
-            let language = resolve_language_or_select(lang)?;
-            run_quiz_mode(&config, &language)?;
+            let variant = if timed {
+                QuizVariant::Timed
+            } else if survival {
+                QuizVariant::Survival
+            } else if let Some(genre) = marathon {
+                QuizVariant::Marathon(genre)
+            } else {
+                QuizVariant::Count(count.map_or(settings.run_length, |n| n as usize))
+            };
+
+            let language = resolve_language_or_select(lang, &settings)?;
+            if plain {
+                run_plain_quiz(&config, &settings, &language, &variant, practice)?;
+            } else {
+                run_quiz_mode(&config, &settings, &language, &variant, practice, stealth)?;
+            }
             Ok(())
         }
 
@@ -190,6 +344,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             seed,
             floor,
             no_tts,
+            stealth,
         }) => {
             // TODO(#48): --seed は未実装。引数を受け取るのみ。
             if seed.is_some() {
@@ -200,8 +355,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 eprintln!("note: --floor は現在未実装です（スタブ）");
             }
 
-            let language = resolve_language_or_select(lang)?;
-            run_listening_practice(&config, &language, no_tts)?;
+            let language = resolve_language_or_select(lang, &settings)?;
+            run_listening_practice(&config, &settings, &language, no_tts, stealth, plain)?;
             Ok(())
         }
 
@@ -219,20 +374,187 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         // ---- ranking サブコマンド ----
         Some(Commands::Ranking { lang }) => {
-            let language = resolve_language_or_select(lang)?;
+            let language = resolve_language_or_select(lang, &settings)?;
             let records_path = config.records_file_path(&language);
             let mut records_ui = RecordsUI::load(&records_path)?;
-            records_ui.run()?;
+            if plain {
+                records_ui.print_plain(&mut stdout())?;
+            } else {
+                records_ui.run()?;
+            }
+            Ok(())
+        }
+
+        // ---- edit サブコマンド ----
+        Some(Commands::Edit { pack }) => {
+            // エディタはフォーム入力の TUI しかないので、パイプへは描かずに断る
+            if plain {
+                return Err(
+                    "edit は端末でのみ使えます（--plain やパイプでは開けません）。パックの追加は import を使ってください"
+                        .into(),
+                );
+            }
+            let pack_path = pack.unwrap_or_else(|| config.custom_pack_file_path());
+            let mut editor = EditorUI::load(&pack_path)?;
+            editor.run()?;
             Ok(())
         }
+
+        // ---- import サブコマンド ----
+        Some(Commands::Import {
+            input,
+            format,
+            columns,
+            out,
+            genre,
+            lang,
+        }) => {
+            let columns = columns.as_deref().map(Column::parse_list).transpose()?;
+            let options = ImportOptions {
+                format: format.unwrap_or_else(|| ImportFormat::from_path(&input)),
+                columns,
+                genre,
+                language: lang.unwrap_or(Language::Japanese),
+            };
+            let out = out.unwrap_or_else(|| config.custom_pack_file_path());
+            run_import(&input, &out, &options)
+        }
+
+        // ---- voices サブコマンド ----
+        Some(Commands::Voices { lang }) => run_voices(&settings, lang, plain),
+    }
+}
+
+/// Print the TTS voices per language, marking the one Listening would
+/// use, then which local engines are installed. `plain` spells the mark
+/// out as `(chosen)`, since a screen reader skips a lone `*`.
+fn run_voices(
+    settings: &Settings,
+    lang: Option<Language>,
+    plain: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let languages = match lang {
+        Some(language) => vec![language],
+        None => vec![Language::Japanese, Language::English],
+    };
+    match TtsEngine::new() {
+        Ok(tts) => {
+            let tts = tts.with_voices(speech_voices(settings));
+            for language in &languages {
+                let chosen = tts.chosen_voice(language);
+                println!("tts voices ({}):", language.code());
+                let voices = tts.voices(Some(language))?;
+                if voices.is_empty() {
+                    println!("  (none; the system default voice speaks)");
+                }
+                for voice in voices {
+                    let is_chosen = chosen.as_ref() == Some(&voice.name);
+                    if plain {
+                        let mark = if is_chosen { "  (chosen)" } else { "" };
+                        println!("  {} {}{mark}", voice.name, voice.language);
+                    } else {
+                        let mark = if is_chosen { '*' } else { ' ' };
+                        println!("  {mark} {:<32} {}", voice.name, voice.language);
+                    }
+                }
+            }
+        }
+        Err(e) => println!("tts: unavailable ({e})"),
+    }
+    println!("local engines:");
+    for engine in LocalEngine::ALL {
+        let state = if engine.is_installed() {
+            "installed"
+        } else {
+            "not found"
+        };
+        println!("    {:<32} {state}", engine.name());
+    }
+    Ok(())
+}
+
+/// The speech chain `settings.yaml` asks for, shared by Listening and the
+/// quiz's read-aloud.
+fn speech_options(config: &Config, settings: &Settings) -> SpeechOptions {
+    SpeechOptions {
+        order: settings.speech.clone(),
+        audio_dir: config.audio_dir_path().into(),
+        voices: speech_voices(settings),
+        rate: settings.tts_rate,
+        pitch: settings.tts_pitch,
+        volume: settings.speech_volume(),
+        model: settings.speech_model.clone(),
+    }
+}
+
+/// Listening's sound-effect engine at the `settings.yaml` volume and
+/// theme. `None` with sound off or no audio device.
+fn listen_cues(settings: &Settings) -> Option<audio::CueEngine> {
+    let volume = settings.cue_volume();
+    if volume <= 0.0 {
+        return None;
     }
+    audio::CueEngine::new().map(|engine| {
+        engine
+            .with_volume(volume)
+            .with_keystroke_volume(settings.keystroke_scale())
+            .with_theme(settings.sound_theme)
+    })
 }
 
-/// サブコマンドで --lang が省略された場合、簡易選択プロンプトを表示する。
+/// `tts_voice_<lang>` / `tts_voice` as the TTS backend takes them.
+fn speech_voices(settings: &Settings) -> HashMap<String, String> {
+    [Language::Japanese, Language::English]
+        .iter()
+        .filter_map(|language| {
+            let voice = settings.voice_for(language)?;
+            Some((language.code().to_string(), voice.to_string()))
+        })
+        .collect()
+}
+
+/// Append the questions parsed from `input` to the pack at `out`, then run
+/// the shape + prefix validators over the whole pack. Validator findings
+/// are warnings — the pack is still written so the author can fix the
+/// remaining rows in `type-globe edit`.
+fn run_import(
+    input: &str,
+    out: &str,
+    options: &ImportOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(input)?;
+    let mut pack = Pack::load(out)?;
+    let report = io::import::import_questions(&text, options, &pack)?;
+    for reason in &report.skipped {
+        eprintln!("skipped: {reason}");
+    }
+    let imported = report.questions.len();
+    pack.extend(report.questions);
+
+    let issues = io::shape::check_questions(&pack);
+    for issue in &issues {
+        eprintln!("warning: {}", io::shape::format_issue(issue));
+    }
+    for c in io::find_prefix_conflicts(&pack) {
+        eprintln!("warning: {}", io::format_conflict(&c));
+    }
+
+    Pack::save(out, &pack)?;
+    println!(
+        "{imported} 問を {out} に取り込みました（スキップ {} 行、警告 {} 件）",
+        report.skipped.len(),
+        issues.len()
+    );
+    Ok(())
+}
+
+/// サブコマンドで --lang が省略された場合、settings.yaml の language を
+/// 使う。それも未設定なら簡易選択プロンプトを表示する。
 fn resolve_language_or_select(
     lang: Option<Language>,
+    settings: &Settings,
 ) -> Result<Language, Box<dyn std::error::Error>> {
-    if let Some(l) = lang {
+    if let Some(l) = lang.or_else(|| settings.language()) {
         return Ok(l);
     }
     // 簡易プロンプト（メニュー TUI を経由しない）
@@ -240,7 +562,10 @@ fn resolve_language_or_select(
         print!("言語を選択してください (ja/en): ");
         stdout().flush()?;
         let mut input = String::new();
-        stdin().read_line(&mut input)?;
+        // パイプで入力が尽きたら聞き直さずに終える
+        if stdin().read_line(&mut input)? == 0 {
+            return Err("言語が選択されませんでした".into());
+        }
         match input.trim() {
             "ja" => return Ok(Language::Japanese),
             "en" => return Ok(Language::English),
@@ -253,8 +578,35 @@ fn resolve_language_or_select(
 // メニューループ（サブコマンドなし時の従来フロー）
 // ---------------------------------------------------------------------------
 
-fn run_menu_loop(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
-    let mut menu = MenuUI::new();
+/// プレーン表示のメニュー (#47): 言語を聞いてから、行入力でモードを選ぶ。
+/// プレーン表示のあるクイズ・練習・リスニングだけを並べる。
+fn run_plain_menu(config: &Config, settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let language = resolve_language_or_select(None, settings)?;
+    let variant = QuizVariant::Count(settings.run_length);
+    loop {
+        // stdin のロックはモード選択のあいだだけ持つ
+        let mode = ui::plain::choose_mode(&mut stdin().lock(), &mut stdout())?;
+        match mode {
+            Some(GameMode::Quiz) => run_plain_quiz(config, settings, &language, &variant, false)?,
+            Some(GameMode::Practice) => {
+                run_plain_quiz(config, settings, &language, &variant, true)?
+            }
+            Some(GameMode::Rpg) => {
+                run_listening_practice(config, settings, &language, false, false, true)?
+            }
+            Some(_) => {}
+            None => return Ok(()),
+        }
+    }
+}
+
+fn run_menu_loop(
+    config: &Config,
+    mut settings: Settings,
+    overrides: &[(String, String)],
+    palette: Palette,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut menu = MenuUI::new().with_language(settings.language());
 
     loop {
         let (language, mode) = match menu.run() {
@@ -264,7 +616,13 @@ fn run_menu_loop(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
 
         match mode {
             GameMode::Quiz => {
-                run_quiz_mode(config, &language)?;
+                let variant = QuizVariant::Count(settings.run_length);
+                run_quiz_mode(config, &settings, &language, &variant, false, false)?;
+                menu.return_to_mode_selection(language);
+            }
+            GameMode::Practice => {
+                let variant = QuizVariant::Count(settings.run_length);
+                run_quiz_mode(config, &settings, &language, &variant, true, false)?;
                 menu.return_to_mode_selection(language);
             }
             GameMode::TimeAttack25 => {
@@ -272,7 +630,7 @@ fn run_menu_loop(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
                 menu.return_to_mode_selection(language);
             }
             GameMode::Rpg => {
-                run_listening_practice(config, &language, false)?;
+                run_listening_practice(config, &settings, &language, false, false, false)?;
                 menu.return_to_mode_selection(language);
             }
             GameMode::Records => {
@@ -281,10 +639,50 @@ fn run_menu_loop(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
                 records_ui.run()?;
                 menu.return_to_mode_selection(language);
             }
+            GameMode::Settings => {
+                // 画面で編集するのはファイルの値だけ。保存後に環境変数と
+                // --set を重ね直し、テーマもその場で差し替える。
+                let mut settings_ui =
+                    SettingsUI::new(load_settings_file(config), config.settings_file_path());
+                if let Some(saved) = settings_ui.run()? {
+                    settings = resolve_settings(saved, overrides)?;
+                    install_theme(config, &settings, palette);
+                }
+                menu.return_to_mode_selection(language);
+            }
         }
     }
 }
 
+/// settings.yaml を読む。不正なファイルは警告して既定値で続行する。
+fn load_settings_file(config: &Config) -> Settings {
+    Settings::load(&config.settings_file_path()).unwrap_or_else(|e| {
+        eprintln!("warning: {}: {e}", config.settings_file_path());
+        Settings::default()
+    })
+}
+
+/// ファイルの値に環境変数 TYPE_GLOBE_<KEY>、次に --set を重ねた実効値。
+fn resolve_settings(
+    file: Settings,
+    overrides: &[(String, String)],
+) -> Result<Settings, Box<dyn std::error::Error>> {
+    file.with_env(|var| std::env::var(var).ok())?
+        .with_overrides(overrides)
+}
+
+/// theme.yaml を読み、settings.yaml の theme をプリセットとして重ねて
+/// インストールする。読込の失敗でゲームを止めない。警告して dark
+/// プリセットで続行する。
+fn install_theme(config: &Config, settings: &Settings, palette: Palette) {
+    let theme =
+        Theme::load(&config.theme_file_path(), settings.theme.as_deref()).unwrap_or_else(|e| {
+            eprintln!("warning: {}: {e}", config.theme_file_path());
+            Theme::default()
+        });
+    ui::theme::install(theme.with_palette(palette));
+}
+
 // ---------------------------------------------------------------------------
 // モード実装ヘルパー
 // ---------------------------------------------------------------------------
@@ -309,6 +707,7 @@ struct DemoOptions {
 /// or loops until the user aborts with Esc / Ctrl+C.
 fn run_quiz_demo(
     config: &Config,
+    settings: &Settings,
     language: &Language,
     genre: Option<&str>,
     options: DemoOptions,
@@ -347,7 +746,12 @@ fn run_quiz_demo(
     loop {
         let demo = DemoInputSource::new(options.type_cps, wait);
         let mut quiz_ui =
-            QuizUI::from_pool_with_count(&questions, language.clone(), records_path.clone(), count);
+            QuizUI::from_pool_with_count(&questions, language.clone(), records_path.clone(), count)
+                .with_image_dirs(ui::image::search_dirs_for(&questions_file))
+                .with_reveal_speed(settings.reveal_speed)
+                .with_volume(settings.cue_volume())
+                .with_keystroke_volume(settings.keystroke_scale())
+                .with_sound_theme(settings.sound_theme);
         // Demo path discards the score — the operator only cares that
         // the run completes and the screen looks right. Errors are
         // surfaced so a broken terminal doesn't get swallowed in loop
@@ -393,21 +797,89 @@ fn run_quiz_demo(
     Ok(())
 }
 
-fn run_quiz_mode(config: &Config, language: &Language) -> Result<(), Box<dyn std::error::Error>> {
+fn run_quiz_mode(
+    config: &Config,
+    settings: &Settings,
+    language: &Language,
+    variant: &QuizVariant,
+    practice: bool,
+    stealth: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     let questions_file = config.questions_file_path(language);
-
-    let questions = load_questions_with_warnings(&questions_file)?;
-    if questions.is_empty() {
-        println!("問題が見つかりません。");
+    let Some(questions) = load_quiz_pool(&questions_file, variant)? else {
         return Ok(());
-    }
+    };
+
+    // 読み上げは任意機能: 音声が使えなければ警告だけ出して無音で続ける
+    let speech: Option<Box<dyn audio::SpeechBackend>> = if settings.read_aloud == ReadAloud::Off {
+        None
+    } else {
+        match audio::speech::open(&speech_options(config, settings)) {
+            Ok(chain) => Some(Box::new(chain)),
+            Err(err) => {
+                eprintln!("warning: read_aloud を無効にします: {err}");
+                None
+            }
+        }
+    };
 
     let records_path = config.records_file_path(language);
-    let mut quiz_ui = QuizUI::from_pool(&questions, language.clone(), records_path);
+    let mut quiz_ui = QuizUI::for_variant(&questions, language.clone(), records_path, variant)
+        .with_image_dirs(ui::image::search_dirs_for(&questions_file))
+        .with_stealth(stealth)
+        .with_practice(practice)
+        .with_hints(settings.hints_in_records)
+        .with_reveal_speed(settings.reveal_speed)
+        .with_volume(settings.cue_volume())
+        .with_keystroke_volume(settings.keystroke_scale())
+        .with_sound_theme(settings.sound_theme)
+        .with_read_aloud(settings.read_aloud, speech);
     let _final_score = quiz_ui.run()?;
     Ok(())
 }
 
+/// `quiz` in plain mode (#47): the same run as `run_quiz_mode`, announced
+/// a line at a time on stdout and answered a line at a time on stdin.
+fn run_plain_quiz(
+    config: &Config,
+    settings: &Settings,
+    language: &Language,
+    variant: &QuizVariant,
+    practice: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let questions_file = config.questions_file_path(language);
+    let Some(questions) = load_quiz_pool(&questions_file, variant)? else {
+        return Ok(());
+    };
+    let records_path = config.records_file_path(language);
+    let mut quiz = PlainQuiz::for_variant(&questions, language.clone(), records_path, variant)
+        .with_practice(practice)
+        .with_hints(settings.hints_in_records);
+    let _final_score = quiz.run(&mut stdin().lock(), &mut stdout())?;
+    Ok(())
+}
+
+/// The bank a Quiz run draws from; `None` (after saying so) when it is
+/// empty. A Marathon genre with no questions exits with code 1.
+fn load_quiz_pool(
+    questions_file: &str,
+    variant: &QuizVariant,
+) -> Result<Option<Vec<Question>>, Box<dyn std::error::Error>> {
+    let questions = load_questions_with_warnings(questions_file)?;
+    if questions.is_empty() {
+        println!("問題が見つかりません。");
+        return Ok(None);
+    }
+    if let QuizVariant::Marathon(g) = variant {
+        if DataLoader::filter_questions_by_genre(&questions, Some(g)).is_empty() {
+            // --genre（デモ）と同じく非ゼロ終了で空ジャンルを知らせる
+            eprintln!("error: --marathon '{g}' に一致する問題がありません。");
+            std::process::exit(1);
+        }
+    }
+    Ok(Some(questions))
+}
+
 /// Load a question bank and warn (non-fatally) on any prefix conflicts in
 /// the data. Routing every question-loading code path through this helper
 /// keeps future modes (Time Attack 25, Records) from silently bypassing the
@@ -439,10 +911,15 @@ fn show_return_to_menu_message(message: &str) -> Result<(), Box<dyn std::error::
 /// `skip_tts`: when `true` (set via `rpg --no-tts`), the TTS engine is
 /// not initialised and the session runs silently. Useful for debugging
 /// in environments where TTS is unavailable or undesirable.
+///
+/// `plain`: play through `PlainListen` instead of the TUI (#47).
 fn run_listening_practice(
     config: &Config,
+    settings: &Settings,
     language: &Language,
     skip_tts: bool,
+    stealth: bool,
+    plain: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let path = config.listening_file_path(language);
     let prompts = DataLoader::load_listening_prompts(&path)?;
@@ -466,22 +943,44 @@ fn run_listening_practice(
         }
     };
 
+    if plain {
+        let speech: Option<Box<dyn audio::SpeechBackend>> = if skip_tts {
+            None
+        } else {
+            match audio::speech::open(&speech_options(config, settings)) {
+                Ok(chain) => Some(Box::new(chain)),
+                Err(err) => {
+                    show_return_to_menu_message(&tts_unavailable_message(&err))?;
+                    return Ok(());
+                }
+            }
+        };
+        let mut listen = PlainListen::new(session, speech, language.clone());
+        let _ = listen.run(&mut stdin().lock(), &mut stdout())?;
+        return Ok(());
+    }
+
     if skip_tts {
         // --no-tts: TTS を初期化せずサイレント実行
-        let mut ui = ListenUI::new_without_tts(session, language.clone());
+        let mut ui = ListenUI::new_without_tts(session, language.clone())
+            .with_stealth(stealth)
+            .with_cues(listen_cues(settings));
         let _ = ui.run()?;
         return Ok(());
     }
 
-    let tts = match TtsEngine::new() {
-        Ok(t) => t,
+    // settings.yaml の `speech:` 順に起動できるバックエンドを連結する
+    let speech = match audio::speech::open(&speech_options(config, settings)) {
+        Ok(chain) => chain,
         Err(err) => {
-            show_return_to_menu_message(&tts_unavailable_message(err.as_ref()))?;
+            show_return_to_menu_message(&tts_unavailable_message(&err))?;
             return Ok(());
         }
     };
 
-    let mut ui = ListenUI::new(session, tts, language.clone());
+    let mut ui = ListenUI::new(session, Box::new(speech), language.clone())
+        .with_stealth(stealth)
+        .with_cues(listen_cues(settings));
     let _ = ui.run()?;
     Ok(())
 }
@@ -568,6 +1067,141 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_quiz_variants_parse_and_conflict() {
+        let cli = Cli::parse_from(["type-globe", "quiz", "--count", "25"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Quiz {
+                count: Some(25),
+                survival: false,
+                marathon: None,
+                ..
+            })
+        ));
+        let cli = Cli::parse_from(["type-globe", "quiz", "--timed", "--practice"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Quiz {
+                timed: true,
+                practice: true,
+                ..
+            })
+        ));
+        let cli = Cli::parse_from(["type-globe", "quiz", "--marathon", "capitals"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Quiz { marathon: Some(ref g), .. }) if g == "capitals"
+        ));
+        for args in [
+            &["type-globe", "quiz", "--count", "0"][..],
+            &["type-globe", "quiz", "--count", "101"],
+            &["type-globe", "quiz", "--survival", "--count", "5"],
+            &["type-globe", "quiz", "--survival", "--marathon", "x"],
+            &["type-globe", "quiz", "--marathon", "x", "--count", "5"],
+            &["type-globe", "quiz", "--timed", "--count", "5"],
+            &["type-globe", "quiz", "--timed", "--survival"],
+            &["type-globe", "quiz", "--practice", "--survival"],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn cli_stealth_flag_on_quiz_and_rpg() {
+        let cli = Cli::parse_from(["type-globe", "quiz", "--stealth"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Quiz { stealth: true, .. })
+        ));
+        let cli = Cli::parse_from(["type-globe", "rpg"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Rpg { stealth: false, .. })
+        ));
+    }
+
+    #[test]
+    fn cli_color_flag_is_global_and_defaults_to_auto() {
+        let cli = Cli::parse_from(["type-globe", "quiz"]);
+        assert_eq!(cli.color, ColorMode::Auto);
+        let cli = Cli::parse_from(["type-globe", "quiz", "--color", "256"]);
+        assert_eq!(cli.color, ColorMode::Fixed(Palette::Ansi256));
+        let cli = Cli::parse_from(["type-globe", "--color", "none"]);
+        assert_eq!(cli.color, ColorMode::Fixed(Palette::NoColor));
+        assert!(Cli::try_parse_from(["type-globe", "--color", "88"]).is_err());
+    }
+
+    #[test]
+    fn cli_set_is_global_and_repeatable() {
+        let cli = Cli::parse_from([
+            "type-globe",
+            "--set",
+            "volume=40",
+            "--set",
+            "reveal_speed=fast",
+        ]);
+        assert_eq!(
+            cli.set,
+            vec![
+                ("volume".to_string(), "40".to_string()),
+                ("reveal_speed".to_string(), "fast".to_string()),
+            ]
+        );
+        let cli = Cli::parse_from(["type-globe", "quiz", "--set", "run_length=20"]);
+        assert_eq!(cli.set, vec![("run_length".to_string(), "20".to_string())]);
+        assert!(Cli::try_parse_from(["type-globe", "--set", "colour=red"]).is_err());
+        assert!(Cli::try_parse_from(["type-globe", "--set", "volume"]).is_err());
+    }
+
+    #[test]
+    fn cli_plain_flag_is_global() {
+        assert!(!Cli::parse_from(["type-globe", "quiz"]).plain);
+        assert!(Cli::parse_from(["type-globe", "--plain"]).plain);
+        assert!(Cli::parse_from(["type-globe", "rpg", "--plain"]).plain);
+    }
+
+    #[test]
+    fn cli_edit_accepts_pack_path() {
+        let cli = Cli::parse_from(["type-globe", "edit", "--pack", "my.json"]);
+        match cli.command {
+            Some(Commands::Edit { pack }) => assert_eq!(pack.as_deref(), Some("my.json")),
+            other => panic!("expected Edit subcommand, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cli_import_parses_format_and_columns() {
+        let cli = Cli::parse_from([
+            "type-globe",
+            "import",
+            "deck.txt",
+            "--format",
+            "anki",
+            "--columns",
+            "question.en,answer.en",
+        ]);
+        match cli.command {
+            Some(Commands::Import {
+                input,
+                format,
+                columns,
+                ..
+            }) => {
+                assert_eq!(input, "deck.txt");
+                assert_eq!(format, Some(ImportFormat::Anki));
+                assert_eq!(columns.as_deref(), Some("question.en,answer.en"));
+            }
+            other => panic!("expected Import subcommand, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cli_import_rejects_unknown_format() {
+        let result = Cli::try_parse_from(["type-globe", "import", "x.csv", "--format", "xlsx"]);
+        assert!(result.is_err());
+    }
+
     // --- TC-29: --seed -1 causes clap to return an error (negative not accepted for u64) ---
     #[test]
     fn cli_seed_negative_one_fails_to_parse() {
@@ -0,0 +1,641 @@
+//! User settings (`settings.yaml` in the data dir).
+//!
+//! ```yaml
+//! language: ja          # ja | en; omit to pick in the menu
+//! run_length: 10        # questions per Quiz run
+//! sound: true
+//! volume: 80            # 0-100, the cues
+//! master_volume: 100    # 0-100, scales the cues and speech alike
+//! keystroke_volume: 100 # 0-100, typing clicks, on top of volume
+//! sound_theme: classic  # classic | chip | soft | silent-keys
+//! reveal_speed: normal  # slow | normal | fast
+//! theme: light          # a theme preset; omit to use theme.yaml's own
+//! tts_voice: Kyoko      # omit to pick a voice by language
+//! tts_voice_ja: Kyoko   # per language; wins over tts_voice
+//! tts_voice_en: Alex
+//! tts_rate: 1.0         # 0.5-2.0, multiplier on the backend's normal rate
+//! tts_pitch: 1.0        # 0.5-2.0, likewise for pitch
+//! tts_volume: 100       # 0-100, speech loudness
+//! speech: [files, tts, espeak-ng, open-jtalk, piper]  # tried in this order
+//! speech_model: ~/voices/ja_JP-test.onnx  # piper model / Open JTalk voice
+//! hints_in_records: true  # false: no hints in runs that save to Records
+//! read_aloud: off       # off | question | choices: speak Quiz questions
+//! ```
+//!
+//! Every key is optional. Sources stack, weakest first: built-in
+//! defaults, the file, `TYPE_GLOBE_<KEY>` environment variables
+//! (`TYPE_GLOBE_VOLUME=40`), then `--set key=value` on the command line.
+//! The dedicated flags (`--lang`, `rpg --no-tts`) still win over all of
+//! them. The settings screen edits and saves the file values only, so a
+//! one-off override never ends up written back.
+
+use crate::audio::cues::SoundTheme;
+use crate::audio::speech::SpeechKind;
+use crate::types::Language;
+use crate::ui::theme::{Theme, PRESET_NAMES};
+use jiwa::RevealOpts;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Keys accepted by `settings.yaml`, `--set` and the environment.
+pub const KEYS: [&str; 19] = [
+    "language",
+    "run_length",
+    "sound",
+    "volume",
+    "reveal_speed",
+    "theme",
+    "tts_voice",
+    "tts_rate",
+    "hints_in_records",
+    "speech",
+    "speech_model",
+    "tts_voice_ja",
+    "tts_voice_en",
+    "tts_pitch",
+    "tts_volume",
+    "read_aloud",
+    "sound_theme",
+    "master_volume",
+    "keystroke_volume",
+];
+
+/// Prefix of the environment variable for each key, upper-cased:
+/// `TYPE_GLOBE_RUN_LENGTH`.
+pub const ENV_PREFIX: &str = "TYPE_GLOBE_";
+
+pub const MAX_RUN_LENGTH: usize = 100;
+pub const TTS_RATE_RANGE: (f32, f32) = (0.5, 2.0);
+/// Same bounds as the rate: both map 0.5 / 1.0 / 2.0 onto the backend's
+/// min / normal / max.
+pub const TTS_PITCH_RANGE: (f32, f32) = TTS_RATE_RANGE;
+
+/// How fast the question text types itself out. There is no "instant":
+/// `docs/spec.md` forbids skipping the reveal, so the fastest setting
+/// still animates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RevealSpeed {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl RevealSpeed {
+    pub const ALL: [Self; 3] = [Self::Slow, Self::Normal, Self::Fast];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Slow => "slow",
+            Self::Normal => "normal",
+            Self::Fast => "fast",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.name() == name)
+    }
+
+    /// Stretch or shorten jiwa's per-grapheme interval and fade.
+    pub fn apply(self, opts: RevealOpts) -> RevealOpts {
+        let scale = match self {
+            Self::Slow => 1.5,
+            Self::Normal => return opts,
+            Self::Fast => 0.6,
+        };
+        RevealOpts {
+            char_interval: opts.char_interval.mul_f32(scale),
+            fade_duration: opts.fade_duration.mul_f32(scale),
+            ..opts
+        }
+    }
+}
+
+/// Whether Quiz speaks each question through the speech backends, for
+/// players who can't read the screen. The typing target is never read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadAloud {
+    #[default]
+    Off,
+    Question,
+    /// The question, then the four choices in their on-screen order.
+    Choices,
+}
+
+impl ReadAloud {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Question, Self::Choices];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Question => "question",
+            Self::Choices => "choices",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// `ja` / `en`. `None` asks in the menu (or the subcommand prompt).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub run_length: usize,
+    pub sound: bool,
+    pub volume: u8,
+    pub reveal_speed: RevealSpeed,
+    /// Theme preset name; `None` keeps `theme.yaml`'s own `preset:`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Voice name as the TTS backend reports it; `None` picks by language.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts_voice: Option<String>,
+    pub tts_rate: f32,
+    /// Whether Quiz hints are available in runs that save to Records.
+    /// Turning it off keeps those lists hint-free for competitive play.
+    pub hints_in_records: bool,
+    /// Listening speech backends, tried in order (`audio::speech`).
+    pub speech: Vec<SpeechKind>,
+    /// Voice model for the `piper` / `open-jtalk` backends.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speech_model: Option<String>,
+    /// Per-language voices; each wins over `tts_voice` for its language.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts_voice_ja: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts_voice_en: Option<String>,
+    pub tts_pitch: f32,
+    /// Speech loudness, 0-100. Separate from `volume`, which is the cues.
+    pub tts_volume: u8,
+    pub read_aloud: ReadAloud,
+    /// How the cues are voiced (`audio::cues`).
+    pub sound_theme: SoundTheme,
+    /// 0-100, applied on top of both `volume` and `tts_volume`.
+    pub master_volume: u8,
+    /// 0-100, the keystroke and mistype clicks relative to `volume`.
+    pub keystroke_volume: u8,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            language: None,
+            run_length: crate::game::quiz::QUIZ_RUN_LENGTH,
+            sound: true,
+            volume: 100,
+            reveal_speed: RevealSpeed::Normal,
+            theme: None,
+            tts_voice: None,
+            tts_rate: 1.0,
+            hints_in_records: true,
+            speech: SpeechKind::ALL.to_vec(),
+            speech_model: None,
+            tts_voice_ja: None,
+            tts_voice_en: None,
+            tts_pitch: 1.0,
+            tts_volume: 100,
+            read_aloud: ReadAloud::Off,
+            sound_theme: SoundTheme::Classic,
+            master_volume: 100,
+            keystroke_volume: 100,
+        }
+    }
+}
+
+impl Settings {
+    /// Load `path`. A missing file is the defaults, not an error.
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        if !Path::new(path).exists() {
+            return Ok(Self::default());
+        }
+        Self::from_yaml(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        // An empty file deserialises as `null`; treat it as "all defaults".
+        let settings = serde_yaml::from_str::<Option<Self>>(yaml)?.unwrap_or_default();
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.validate()?;
+        if let Some(parent) = Path::new(path).parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        fs::write(path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Apply `TYPE_GLOBE_<KEY>` variables looked up through `get`, so
+    /// tests can pass a fake environment.
+    pub fn with_env(
+        mut self,
+        get: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        for key in KEYS {
+            let var = format!("{ENV_PREFIX}{}", key.to_ascii_uppercase());
+            if let Some(value) = get(&var) {
+                self.set(key, &value).map_err(|e| format!("{var}: {e}"))?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Apply `--set key=value` pairs in command-line order.
+    pub fn with_overrides(
+        mut self,
+        overrides: &[(String, String)],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        for (key, value) in overrides {
+            self.set(key, value)
+                .map_err(|e| format!("--set {key}: {e}"))?;
+        }
+        Ok(self)
+    }
+
+    /// Set one key from its textual form. An empty value resets the
+    /// optional keys (`language`, `theme`, the `tts_voice*` keys,
+    /// `speech_model`) to "unset". `speech` takes a comma-separated list. On
+    /// error `self` is left unchanged.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        let optional = || (!value.is_empty()).then(|| value.to_string());
+        let mut next = self.clone();
+        match key {
+            "language" => next.language = optional(),
+            "run_length" => next.run_length = parse_number(value)?,
+            "sound" => next.sound = parse_switch(value)?,
+            "volume" => next.volume = parse_number(value)?,
+            "reveal_speed" => {
+                next.reveal_speed = RevealSpeed::from_name(value).ok_or_else(|| {
+                    format!("unknown reveal speed '{value}' (expected slow, normal or fast)")
+                })?
+            }
+            "theme" => next.theme = optional(),
+            "tts_voice" => next.tts_voice = optional(),
+            "tts_rate" => next.tts_rate = parse_number(value)?,
+            "hints_in_records" => next.hints_in_records = parse_switch(value)?,
+            "speech" => next.speech = parse_speech(value)?,
+            "speech_model" => next.speech_model = optional(),
+            "tts_voice_ja" => next.tts_voice_ja = optional(),
+            "tts_voice_en" => next.tts_voice_en = optional(),
+            "tts_pitch" => next.tts_pitch = parse_number(value)?,
+            "tts_volume" => next.tts_volume = parse_number(value)?,
+            "read_aloud" => {
+                next.read_aloud = ReadAloud::from_name(value).ok_or_else(|| {
+                    format!("unknown read_aloud '{value}' (expected off, question or choices)")
+                })?
+            }
+            "sound_theme" => {
+                next.sound_theme = SoundTheme::from_name(value).ok_or_else(|| {
+                    let names: Vec<&str> = SoundTheme::ALL.iter().map(|t| t.name()).collect();
+                    format!(
+                        "unknown sound theme '{value}' (expected one of: {})",
+                        names.join(", ")
+                    )
+                })?
+            }
+            "master_volume" => next.master_volume = parse_number(value)?,
+            "keystroke_volume" => next.keystroke_volume = parse_number(value)?,
+            _ => {
+                return Err(format!(
+                    "unknown setting '{key}' (expected one of: {})",
+                    KEYS.join(", ")
+                ))
+            }
+        }
+        next.validate()?;
+        *self = next;
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(code) = &self.language {
+            if Language::from_code(code).is_none() {
+                return Err(format!("unknown language '{code}' (expected ja or en)"));
+            }
+        }
+        if !(1..=MAX_RUN_LENGTH).contains(&self.run_length) {
+            return Err(format!("run_length must be 1-{MAX_RUN_LENGTH}"));
+        }
+        if self.volume > 100 {
+            return Err("volume must be 0-100".into());
+        }
+        if let Some(name) = &self.theme {
+            if Theme::preset(name).is_none() {
+                return Err(format!(
+                    "unknown theme preset '{name}' (expected one of: {})",
+                    PRESET_NAMES.join(", ")
+                ));
+            }
+        }
+        let (min, max) = TTS_RATE_RANGE;
+        if !(min..=max).contains(&self.tts_rate) {
+            return Err(format!("tts_rate must be {min}-{max}"));
+        }
+        let (min, max) = TTS_PITCH_RANGE;
+        if !(min..=max).contains(&self.tts_pitch) {
+            return Err(format!("tts_pitch must be {min}-{max}"));
+        }
+        if self.tts_volume > 100 {
+            return Err("tts_volume must be 0-100".into());
+        }
+        if self.master_volume > 100 {
+            return Err("master_volume must be 0-100".into());
+        }
+        if self.keystroke_volume > 100 {
+            return Err("keystroke_volume must be 0-100".into());
+        }
+        Ok(())
+    }
+
+    pub fn language(&self) -> Option<Language> {
+        self.language.as_deref().and_then(Language::from_code)
+    }
+
+    /// The voice to ask for in `lang`: its own key, else `tts_voice`.
+    pub fn voice_for(&self, lang: &Language) -> Option<&str> {
+        let own = match lang {
+            Language::Japanese => &self.tts_voice_ja,
+            Language::English => &self.tts_voice_en,
+        };
+        own.as_deref().or(self.tts_voice.as_deref())
+    }
+
+    /// Speech gain in `0.0..=1.0`, master volume included.
+    pub fn speech_volume(&self) -> f32 {
+        f32::from(self.tts_volume) / 100.0 * self.master()
+    }
+
+    /// Sound-effect gain in `0.0..=1.0`, master volume included; zero
+    /// when sound is off.
+    pub fn cue_volume(&self) -> f32 {
+        if self.sound {
+            f32::from(self.volume) / 100.0 * self.master()
+        } else {
+            0.0
+        }
+    }
+
+    /// Scale of the typing clicks relative to [`cue_volume`](Self::cue_volume).
+    pub fn keystroke_scale(&self) -> f32 {
+        f32::from(self.keystroke_volume) / 100.0
+    }
+
+    fn master(&self) -> f32 {
+        f32::from(self.master_volume) / 100.0
+    }
+}
+
+/// clap value parser for `--set key=value`.
+pub fn parse_assignment(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("'{s}' は key=value の形式で指定してください"))?;
+    let key = key.trim();
+    if !KEYS.contains(&key) {
+        return Err(format!(
+            "不明な設定キー: '{key}'. {} のいずれかを指定してください",
+            KEYS.join(" / ")
+        ));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("'{value}' is not a valid number"))
+}
+
+fn parse_speech(value: &str) -> Result<Vec<SpeechKind>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            SpeechKind::from_name(name).ok_or_else(|| {
+                let names: Vec<&str> = SpeechKind::ALL.iter().map(|k| k.name()).collect();
+                format!(
+                    "unknown speech backend '{name}' (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+        })
+        .collect()
+}
+
+fn parse_switch(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "on" | "true" | "yes" | "1" => Ok(true),
+        "off" | "false" | "no" | "0" => Ok(false),
+        _ => Err(format!("'{value}' is not on/off")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn empty_yaml_is_the_defaults() {
+        let settings = Settings::from_yaml("").expect("parse");
+        assert_eq!(settings, Settings::default());
+        assert_eq!(settings.run_length, 10);
+        assert!(settings.language().is_none());
+    }
+
+    #[test]
+    fn yaml_sets_every_key() {
+        let settings = Settings::from_yaml(
+            "language: en\nrun_length: 20\nsound: false\nvolume: 40\nreveal_speed: fast\ntheme: light\ntts_voice: Alex\ntts_rate: 1.5\nhints_in_records: false\n",
+        )
+        .expect("parse");
+        assert!(matches!(settings.language(), Some(Language::English)));
+        assert_eq!(settings.run_length, 20);
+        assert_eq!(settings.reveal_speed, RevealSpeed::Fast);
+        assert_eq!(settings.theme.as_deref(), Some("light"));
+        assert_eq!(settings.tts_voice.as_deref(), Some("Alex"));
+        assert_eq!(settings.tts_rate, 1.5);
+        assert!(!settings.hints_in_records);
+        assert_eq!(settings.cue_volume(), 0.0);
+    }
+
+    #[test]
+    fn speech_order_comes_from_a_list_or_a_comma_string() {
+        let settings = Settings::from_yaml(
+            "speech: [espeak-ng, tts]
+speech_model: ja.onnx
+",
+        )
+        .expect("parse");
+        assert_eq!(settings.speech, [SpeechKind::EspeakNg, SpeechKind::Tts]);
+        assert_eq!(settings.speech_model.as_deref(), Some("ja.onnx"));
+
+        let mut settings = Settings::default();
+        settings.set("speech", "files, piper").expect("set");
+        assert_eq!(settings.speech, [SpeechKind::Files, SpeechKind::Piper]);
+        assert!(settings.set("speech", "festival").is_err());
+        assert!(Settings::from_yaml(
+            "speech: [festival]
+"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn per_language_voices_win_over_tts_voice() {
+        let settings = Settings::from_yaml(
+            "tts_voice: Alex\ntts_voice_ja: Kyoko\ntts_pitch: 0.8\ntts_volume: 60\n",
+        )
+        .expect("parse");
+        assert_eq!(settings.voice_for(&Language::Japanese), Some("Kyoko"));
+        assert_eq!(settings.voice_for(&Language::English), Some("Alex"));
+        assert_eq!(settings.tts_pitch, 0.8);
+        assert_eq!(settings.speech_volume(), 0.6);
+        assert_eq!(Settings::default().voice_for(&Language::English), None);
+        assert!(Settings::from_yaml("tts_pitch: 0.1\n").is_err());
+        assert!(Settings::from_yaml("tts_volume: 101\n").is_err());
+    }
+
+    #[test]
+    fn master_volume_scales_cues_and_speech() {
+        let settings = Settings::from_yaml(
+            "volume: 80\ntts_volume: 60\nmaster_volume: 50\nkeystroke_volume: 25\n",
+        )
+        .expect("parse");
+        assert_eq!(settings.cue_volume(), 0.4);
+        assert_eq!(settings.speech_volume(), 0.3);
+        assert_eq!(settings.keystroke_scale(), 0.25);
+        assert!(Settings::from_yaml("master_volume: 101\n").is_err());
+        assert!(Settings::default().set("keystroke_volume", "101").is_err());
+    }
+
+    #[test]
+    fn read_aloud_is_off_unless_asked_for() {
+        assert_eq!(Settings::default().read_aloud, ReadAloud::Off);
+        let settings = Settings::from_yaml("read_aloud: choices\n").expect("parse");
+        assert_eq!(settings.read_aloud, ReadAloud::Choices);
+        let mut settings = Settings::default();
+        settings.set("read_aloud", "question").expect("set");
+        assert_eq!(settings.read_aloud, ReadAloud::Question);
+        assert!(settings.set("read_aloud", "all").is_err());
+    }
+
+    #[test]
+    fn sound_theme_takes_a_theme_name() {
+        assert_eq!(Settings::default().sound_theme, SoundTheme::Classic);
+        let settings = Settings::from_yaml("sound_theme: silent-keys\n").expect("parse");
+        assert_eq!(settings.sound_theme, SoundTheme::SilentKeys);
+        let mut settings = Settings::default();
+        settings.set("sound_theme", "chip").expect("set");
+        assert_eq!(settings.sound_theme, SoundTheme::Chip);
+        assert!(settings.set("sound_theme", "loud").is_err());
+    }
+
+    #[test]
+    fn reveal_speeds_never_skip_the_animation() {
+        let base = RevealOpts::soft_green();
+        assert_eq!(
+            RevealSpeed::Normal.apply(base).char_interval,
+            base.char_interval
+        );
+        let fast = RevealSpeed::Fast.apply(base);
+        assert!(fast.char_interval < base.char_interval);
+        assert!(!fast.char_interval.is_zero());
+        assert!(RevealSpeed::Slow.apply(base).fade_duration > base.fade_duration);
+    }
+
+    #[test]
+    fn invalid_files_are_errors() {
+        assert!(Settings::from_yaml("colour: red\n").is_err());
+        assert!(Settings::from_yaml("language: fr\n").is_err());
+        assert!(Settings::from_yaml("run_length: 0\n").is_err());
+        assert!(Settings::from_yaml("volume: 101\n").is_err());
+        assert!(Settings::from_yaml("reveal_speed: instant\n").is_err());
+        assert!(Settings::from_yaml("theme: sepia\n").is_err());
+        assert!(Settings::from_yaml("tts_rate: 3.0\n").is_err());
+    }
+
+    #[test]
+    fn cli_overrides_beat_env_which_beats_the_file() {
+        let file =
+            Settings::from_yaml("volume: 80\nrun_length: 15\nlanguage: ja\n").expect("parse");
+        let env = |var: &str| match var {
+            "TYPE_GLOBE_VOLUME" => Some("50".to_string()),
+            "TYPE_GLOBE_LANGUAGE" => Some("en".to_string()),
+            _ => None,
+        };
+        let settings = file
+            .with_env(env)
+            .expect("env")
+            .with_overrides(&[("volume".into(), "20".into())])
+            .expect("overrides");
+        assert_eq!(settings.volume, 20);
+        assert_eq!(settings.run_length, 15);
+        assert!(matches!(settings.language(), Some(Language::English)));
+    }
+
+    #[test]
+    fn bad_env_values_name_the_variable() {
+        let env = |var: &str| (var == "TYPE_GLOBE_SOUND").then(|| "loud".to_string());
+        let err = Settings::default().with_env(env).unwrap_err().to_string();
+        assert!(err.contains("TYPE_GLOBE_SOUND"), "{err}");
+    }
+
+    #[test]
+    fn empty_values_unset_optional_keys() {
+        let mut settings = Settings::from_yaml("theme: dark\n").expect("parse");
+        settings.set("theme", "").expect("set");
+        assert_eq!(settings.theme, None);
+        assert!(settings.set("run_length", "").is_err());
+        assert!(settings.set("volume", "200").is_err());
+        assert_eq!(settings.volume, 100);
+    }
+
+    #[test]
+    fn parse_assignment_checks_the_key() {
+        assert_eq!(
+            parse_assignment("volume=30"),
+            Ok(("volume".to_string(), "30".to_string()))
+        );
+        assert_eq!(
+            parse_assignment("tts_voice="),
+            Ok(("tts_voice".to_string(), String::new()))
+        );
+        assert!(parse_assignment("volume").is_err());
+        assert!(parse_assignment("colour=red").is_err());
+    }
+
+    #[test]
+    fn save_then_load_round_trips() {
+        let path = temp_dir().join("type_globe_test_settings.yaml");
+        let path = path.to_str().expect("utf-8 temp path");
+        let settings = Settings {
+            language: Some("ja".into()),
+            reveal_speed: RevealSpeed::Slow,
+            tts_rate: 0.8,
+            ..Settings::default()
+        };
+        settings.save(path).expect("save");
+        assert_eq!(Settings::load(path).expect("load"), settings);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn missing_settings_file_is_the_default() {
+        let settings = Settings::load("/nonexistent/type-globe/settings.yaml").expect("load");
+        assert_eq!(settings, Settings::default());
+    }
+}
@@ -1,15 +1,15 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Question {
     pub id: String,
     pub genre: String,
     pub question_text: HashMap<String, String>,
     /// Per-language reading form (hiragana for ja, identical to display for en).
-    /// Used by TTS / RPG audio paths. Quiz mode does not read the prompt aloud,
-    /// so quiz entries may leave this empty; `get_question_reading_text` falls
-    /// back to `question_text` automatically.
+    /// Used by TTS / RPG audio paths and Quiz read-aloud. Quiz entries may
+    /// leave this empty; `get_question_reading_text` falls back to
+    /// `question_text` automatically.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub question_text_reading: HashMap<String, String>,
     pub choices: Vec<Choice>,
@@ -21,9 +21,19 @@ pub struct Question {
     /// the unreviewed count so the backlog can be drained over time.
     #[serde(default)]
     pub ja_reviewed: bool,
+    /// Per-language commentary shown after the question is over (Summary
+    /// cards, practice mode). Markdown inline code is allowed.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub explanation: HashMap<String, String>,
+    /// Where the fact comes from, as free text ("CIA World Factbook").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Link to read more, usually a URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
 pub struct Choice {
     #[serde(flatten)]
     pub labels: HashMap<String, String>,
@@ -103,6 +113,17 @@ pub struct Records {
     pub quiz_mode: Vec<ScoreEntry>,
     pub time_attack_25: Vec<TimeEntry>,
     pub rpg: Vec<ScoreEntry>,
+    /// Quiz runs of a non-default length (`quiz --count N`), keyed by N.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub quiz_count: BTreeMap<usize, Vec<ScoreEntry>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub survival: Vec<ScoreEntry>,
+    /// Marathon runs keyed by genre.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub marathon: BTreeMap<String, Vec<ScoreEntry>>,
+    /// `quiz --timed`: classic run length with a per-question time limit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timed: Vec<ScoreEntry>,
 }
 
 const RECORDS_TOP_N: usize = 10;
@@ -111,10 +132,24 @@ impl Records {
     /// Insert into `quiz_mode`, sort by score descending (ts descending as
     /// tiebreaker), and keep only the top 10.
     pub fn push_quiz(&mut self, entry: ScoreEntry) {
-        self.quiz_mode.push(entry);
-        self.quiz_mode
-            .sort_by(|a, b| b.score.cmp(&a.score).then(b.ts.cmp(&a.ts)));
-        self.quiz_mode.truncate(RECORDS_TOP_N);
+        push_score(&mut self.quiz_mode, entry);
+    }
+
+    /// Same ordering as [`push_quiz`], into the list for `count`-question runs.
+    pub fn push_quiz_count(&mut self, count: usize, entry: ScoreEntry) {
+        push_score(self.quiz_count.entry(count).or_default(), entry);
+    }
+
+    pub fn push_survival(&mut self, entry: ScoreEntry) {
+        push_score(&mut self.survival, entry);
+    }
+
+    pub fn push_timed(&mut self, entry: ScoreEntry) {
+        push_score(&mut self.timed, entry);
+    }
+
+    pub fn push_marathon(&mut self, genre: &str, entry: ScoreEntry) {
+        push_score(self.marathon.entry(genre.to_string()).or_default(), entry);
     }
 
     /// Insert into `rpg`, sort by score descending (ts descending as
@@ -138,6 +173,12 @@ impl Records {
     }
 }
 
+fn push_score(list: &mut Vec<ScoreEntry>, entry: ScoreEntry) {
+    list.push(entry);
+    list.sort_by(|a, b| b.score.cmp(&a.score).then(b.ts.cmp(&a.ts)));
+    list.truncate(RECORDS_TOP_N);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +201,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn variant_lists_are_optional_in_yaml_and_omitted_when_empty() {
+        let yaml = "quiz_mode: []\ntime_attack_25: []\nrpg: []\n";
+        let mut records: Records = serde_yaml::from_str(yaml).expect("old records file");
+        assert!(records.quiz_count.is_empty() && records.survival.is_empty());
+        assert!(!serde_yaml::to_string(&records)
+            .unwrap()
+            .contains("survival"));
+
+        for i in 0..11 {
+            records.push_quiz_count(25, score_entry(&format!("p{i}"), i));
+        }
+        records.push_marathon("capitals", score_entry("m", 7));
+        assert_eq!(records.quiz_count[&25].len(), 10);
+        assert_eq!(records.quiz_count[&25][0].score, 10);
+        let back: Records =
+            serde_yaml::from_str(&serde_yaml::to_string(&records).unwrap()).expect("round trip");
+        assert_eq!(back.marathon["capitals"][0].name, "m");
+    }
+
     #[test]
     fn push_quiz_11_entries_truncates_to_10() {
         let mut records = Records::default();
@@ -248,9 +309,11 @@ mod tests {
 #[derive(Debug, Clone)]
 pub enum GameMode {
     Quiz,
+    Practice,
     TimeAttack25,
     Rpg,
     Records,
+    Settings,
 }
 
 #[derive(Debug, Clone)]
@@ -266,6 +329,15 @@ impl Language {
             Language::English => "en",
         }
     }
+
+    /// Inverse of [`code`](Self::code). Exact match only (`ja` / `en`).
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "ja" => Some(Language::Japanese),
+            "en" => Some(Language::English),
+            _ => None,
+        }
+    }
 }
 
 impl Default for Player {
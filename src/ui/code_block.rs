@@ -0,0 +1,547 @@
+//! Fenced code blocks in question text.
+//!
+//! `inline_code` covers single-backtick spans inside one line. Questions in
+//! the programming / web_development genres also need multi-line snippets,
+//! written as Markdown fences inside `question_text`:
+//!
+//! ````text
+//! What does this print?
+//! ```rust
+//! let v = vec![1, 2, 3];
+//! println!("{}", v.len());
+//! ```
+//! ````
+//!
+//! [`split_fenced`] pulls the fences out before anything else touches the
+//! text (a stray ```` ``` ```` would otherwise confuse the inline-code
+//! parser). The prose around them still goes through the reveal animation;
+//! the code is drawn in its own pane with line numbers, width-aware
+//! wrapping and a small keyword / string / comment / number highlighter for
+//! Rust, JavaScript, Python, SQL and shell. Unknown or missing language
+//! tags render uncoloured.
+//!
+//! Like `inline_code`, this is display-only: typing targets come from the
+//! choices, never from `question_text`.
+//!
+//! An opening fence without a closer is kept as prose verbatim, so
+//! malformed data degrades the same way an unmatched backtick does.
+
+use crate::ui::theme::theme;
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use unicode_width::UnicodeWidthChar;
+
+const TAB_WIDTH: usize = 4;
+const FENCE: &str = "```";
+
+/// Languages the highlighter knows. Tags are matched case-insensitively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLang {
+    Rust,
+    JavaScript,
+    Python,
+    Sql,
+    Shell,
+}
+
+impl CodeLang {
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag.trim().to_ascii_lowercase().as_str() {
+            "rust" | "rs" => Some(Self::Rust),
+            "javascript" | "js" | "typescript" | "ts" | "jsx" | "tsx" => Some(Self::JavaScript),
+            "python" | "py" => Some(Self::Python),
+            "sql" => Some(Self::Sql),
+            "sh" | "bash" | "shell" | "zsh" | "console" => Some(Self::Shell),
+            _ => None,
+        }
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match",
+                "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct",
+                "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+            ],
+            Self::JavaScript => &[
+                "async",
+                "await",
+                "break",
+                "case",
+                "catch",
+                "class",
+                "const",
+                "continue",
+                "default",
+                "delete",
+                "do",
+                "else",
+                "export",
+                "extends",
+                "false",
+                "finally",
+                "for",
+                "function",
+                "if",
+                "import",
+                "in",
+                "instanceof",
+                "let",
+                "new",
+                "null",
+                "of",
+                "return",
+                "static",
+                "switch",
+                "this",
+                "throw",
+                "true",
+                "try",
+                "typeof",
+                "undefined",
+                "var",
+                "void",
+                "while",
+                "yield",
+            ],
+            Self::Python => &[
+                "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+                "continue", "def", "del", "elif", "else", "except", "finally", "for", "from",
+                "global", "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass",
+                "raise", "return", "try", "while", "with", "yield",
+            ],
+            Self::Sql => &[
+                "and", "as", "asc", "by", "create", "delete", "desc", "distinct", "drop", "from",
+                "group", "having", "in", "index", "inner", "insert", "into", "is", "join", "left",
+                "like", "limit", "not", "null", "on", "or", "order", "outer", "primary", "key",
+                "right", "select", "set", "table", "union", "update", "values", "where",
+            ],
+            Self::Shell => &[
+                "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function",
+                "if", "in", "local", "then", "until", "while",
+            ],
+        }
+    }
+
+    fn line_comment(&self) -> &'static str {
+        match self {
+            Self::Rust | Self::JavaScript => "//",
+            Self::Python | Self::Shell => "#",
+            Self::Sql => "--",
+        }
+    }
+
+    fn has_block_comments(&self) -> bool {
+        matches!(self, Self::Rust | Self::JavaScript | Self::Sql)
+    }
+
+    fn quotes(&self) -> &'static [char] {
+        match self {
+            // `'` in Rust is usually a lifetime; char literals are rare in
+            // quiz snippets and render fine as plain text.
+            Self::Rust => &['"'],
+            Self::JavaScript => &['"', '\'', '`'],
+            Self::Python | Self::Shell => &['"', '\''],
+            Self::Sql => &['\''],
+        }
+    }
+}
+
+/// One fenced block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// Raw info string after the opening fence (`rust`, `sql`, …).
+    pub tag: String,
+    pub lines: Vec<String>,
+}
+
+impl CodeBlock {
+    pub fn lang(&self) -> Option<CodeLang> {
+        CodeLang::from_tag(&self.tag)
+    }
+}
+
+/// `question_text` split into prose and fenced code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestionPart {
+    Prose(String),
+    Code(CodeBlock),
+}
+
+/// Split `text` on lines that start with ```` ``` ````.
+pub fn split_fenced(text: &str) -> Vec<QuestionPart> {
+    let mut parts = Vec::new();
+    let mut prose: Vec<&str> = Vec::new();
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let Some(tag) = line.trim_start().strip_prefix(FENCE) else {
+            prose.push(line);
+            i += 1;
+            continue;
+        };
+        let Some(close) = lines[i + 1..]
+            .iter()
+            .position(|l| l.trim() == FENCE)
+            .map(|rel| i + 1 + rel)
+        else {
+            // Unclosed fence: keep the rest verbatim.
+            prose.extend(&lines[i..]);
+            break;
+        };
+        push_prose(&mut parts, &mut prose);
+        parts.push(QuestionPart::Code(CodeBlock {
+            tag: tag.trim().to_string(),
+            lines: lines[i + 1..close]
+                .iter()
+                .map(|l| {
+                    l.trim_end_matches('\r')
+                        .replace('\t', &" ".repeat(TAB_WIDTH))
+                })
+                .collect(),
+        }));
+        i = close + 1;
+    }
+    push_prose(&mut parts, &mut prose);
+    parts
+}
+
+fn push_prose(parts: &mut Vec<QuestionPart>, prose: &mut Vec<&str>) {
+    let text = prose.join("\n").trim().to_string();
+    prose.clear();
+    if !text.is_empty() {
+        parts.push(QuestionPart::Prose(text));
+    }
+}
+
+/// The prose of `parts` on one line, for the question-text reveal.
+pub fn prose_text(parts: &[QuestionPart]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            QuestionPart::Prose(text) => {
+                Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+            }
+            QuestionPart::Code(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The code blocks of `parts`, in order.
+pub fn code_blocks(parts: &[QuestionPart]) -> Vec<CodeBlock> {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            QuestionPart::Code(block) => Some(block.clone()),
+            QuestionPart::Prose(_) => None,
+        })
+        .collect()
+}
+
+/// Highlight `block` into one `Vec<(text, style)>` per source line.
+/// Tokenises the whole block at once so block comments and strings that
+/// span lines stay coloured.
+pub fn highlight(block: &CodeBlock) -> Vec<Vec<(String, Style)>> {
+    let source = block.lines.join("\n");
+    let tokens = match block.lang() {
+        Some(lang) => tokenize(&source, lang),
+        None => vec![(source, theme().text())],
+    };
+    let mut lines: Vec<Vec<(String, Style)>> = vec![Vec::new()];
+    for (text, style) in tokens {
+        for (i, piece) in text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Vec::new());
+            }
+            if !piece.is_empty() {
+                if let Some(line) = lines.last_mut() {
+                    line.push((piece.to_string(), style));
+                }
+            }
+        }
+    }
+    lines.truncate(block.lines.len().max(1));
+    lines
+}
+
+fn tokenize(source: &str, lang: CodeLang) -> Vec<(String, Style)> {
+    let chars: Vec<char> = source.chars().collect();
+    let starts_with = |i: usize, pat: &str| {
+        pat.chars()
+            .enumerate()
+            .all(|(k, p)| chars.get(i + k) == Some(&p))
+    };
+    let mut tokens: Vec<(String, Style)> = Vec::new();
+    let mut push = |text: String, style: Style| match tokens.last_mut() {
+        Some((prev, prev_style)) if *prev_style == style => prev.push_str(&text),
+        _ => tokens.push((text, style)),
+    };
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if starts_with(i, lang.line_comment()) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            push(chars[start..i].iter().collect(), theme().code_comment());
+        } else if lang.has_block_comments() && starts_with(i, "/*") {
+            i += 2;
+            while i < chars.len() && !starts_with(i, "*/") {
+                i += 1;
+            }
+            i = (i + 2).min(chars.len());
+            push(chars[start..i].iter().collect(), theme().code_comment());
+        } else if lang.quotes().contains(&c) {
+            let triple = lang == CodeLang::Python && starts_with(i, &c.to_string().repeat(3));
+            let close_len = if triple { 3 } else { 1 };
+            let close: String = c.to_string().repeat(close_len);
+            i += close_len;
+            while i < chars.len() && !starts_with(i, &close) {
+                if chars[i] == '\\' && lang != CodeLang::Sql {
+                    i += 1;
+                } else if chars[i] == '\n' && !triple && c != '`' {
+                    break;
+                }
+                i += 1;
+            }
+            if starts_with(i, &close) {
+                i += close_len;
+            }
+            i = i.min(chars.len());
+            push(chars[start..i].iter().collect(), theme().code_string());
+        } else if c.is_ascii_digit() {
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_')
+            {
+                i += 1;
+            }
+            push(chars[start..i].iter().collect(), theme().code_number());
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let is_keyword = if lang == CodeLang::Sql {
+                lang.keywords()
+                    .contains(&word.to_ascii_lowercase().as_str())
+            } else {
+                lang.keywords().contains(&word.as_str())
+            };
+            // Rust macros (`println!`) read as keywords too.
+            let is_macro = lang == CodeLang::Rust && chars.get(i) == Some(&'!');
+            if is_macro {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            push(
+                text,
+                if is_keyword || is_macro {
+                    theme().code_keyword()
+                } else {
+                    theme().text()
+                },
+            );
+        } else {
+            i += 1;
+            push(c.to_string(), theme().text());
+        }
+    }
+    tokens
+}
+
+/// Render `block` for a pane `width` cells wide: a right-aligned line
+/// number gutter, then the highlighted line wrapped at the remaining
+/// width. Wrapped continuation rows leave the number blank.
+pub fn render_lines(block: &CodeBlock, width: u16) -> Vec<Line<'static>> {
+    let number_width = block.lines.len().max(1).to_string().len();
+    let gutter_width = number_width + 3; // "12 │ "
+    let content_width = (width as usize).saturating_sub(gutter_width).max(1);
+    let mut out = Vec::new();
+    for (idx, tokens) in highlight(block).into_iter().enumerate() {
+        let rows = wrap_tokens(tokens, content_width);
+        for (row_idx, row) in rows.into_iter().enumerate() {
+            let gutter = if row_idx == 0 {
+                format!("{:>number_width$} │ ", idx + 1)
+            } else {
+                format!("{:>number_width$} │ ", "")
+            };
+            let mut spans = vec![Span::styled(gutter, theme().dim())];
+            spans.extend(row.into_iter().map(|(t, s)| Span::styled(t, s)));
+            out.push(Line::from(spans));
+        }
+    }
+    out
+}
+
+fn wrap_tokens(tokens: Vec<(String, Style)>, width: usize) -> Vec<Vec<(String, Style)>> {
+    let mut rows: Vec<Vec<(String, Style)>> = vec![Vec::new()];
+    let mut used = 0usize;
+    for (text, style) in tokens {
+        let mut current = String::new();
+        for c in text.chars() {
+            let w = c.width().unwrap_or(0);
+            if used + w > width && used > 0 {
+                if !current.is_empty() {
+                    if let Some(row) = rows.last_mut() {
+                        row.push((std::mem::take(&mut current), style));
+                    }
+                }
+                rows.push(Vec::new());
+                used = 0;
+            }
+            current.push(c);
+            used += w;
+        }
+        if !current.is_empty() {
+            if let Some(row) = rows.last_mut() {
+                row.push((current, style));
+            }
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_of(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn plain_text_is_one_prose_part() {
+        assert_eq!(
+            split_fenced("What is `let`?"),
+            vec![QuestionPart::Prose("What is `let`?".into())]
+        );
+    }
+
+    #[test]
+    fn splits_prose_and_code() {
+        let parts = split_fenced("What prints?\n```rust\nlet x = 1;\n\tx\n```\nPick one.");
+        assert_eq!(
+            parts,
+            vec![
+                QuestionPart::Prose("What prints?".into()),
+                QuestionPart::Code(CodeBlock {
+                    tag: "rust".into(),
+                    lines: vec!["let x = 1;".into(), "    x".into()],
+                }),
+                QuestionPart::Prose("Pick one.".into()),
+            ]
+        );
+        assert_eq!(prose_text(&parts), "What prints? Pick one.");
+        assert_eq!(code_blocks(&parts).len(), 1);
+    }
+
+    #[test]
+    fn unclosed_fence_stays_prose() {
+        assert_eq!(
+            split_fenced("Broken\n```sql\nSELECT 1"),
+            vec![QuestionPart::Prose("Broken\n```sql\nSELECT 1".into())]
+        );
+    }
+
+    #[test]
+    fn tags_map_to_languages() {
+        assert_eq!(CodeLang::from_tag("RS"), Some(CodeLang::Rust));
+        assert_eq!(CodeLang::from_tag("ts"), Some(CodeLang::JavaScript));
+        assert_eq!(CodeLang::from_tag("bash"), Some(CodeLang::Shell));
+        assert_eq!(CodeLang::from_tag("haskell"), None);
+        assert_eq!(CodeLang::from_tag(""), None);
+    }
+
+    #[test]
+    fn highlights_keywords_strings_comments_numbers() {
+        let block = CodeBlock {
+            tag: "rust".into(),
+            lines: vec![
+                "let s = \"hi\"; // note".into(),
+                "println!(\"{}\", 42);".into(),
+            ],
+        };
+        let lines = highlight(&block);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(&("let".to_string(), theme().code_keyword())));
+        assert!(lines[0].contains(&("\"hi\"".to_string(), theme().code_string())));
+        assert!(lines[0].contains(&("// note".to_string(), theme().code_comment())));
+        assert!(lines[1].contains(&("println!".to_string(), theme().code_keyword())));
+        assert!(lines[1].contains(&("42".to_string(), theme().code_number())));
+    }
+
+    #[test]
+    fn sql_keywords_are_case_insensitive_and_block_comments_span_lines() {
+        let block = CodeBlock {
+            tag: "sql".into(),
+            lines: vec!["/* a".into(), "b */ Select * FROM t;".into()],
+        };
+        let lines = highlight(&block);
+        assert_eq!(lines[0], vec![("/* a".to_string(), theme().code_comment())]);
+        assert_eq!(lines[1][0], ("b */".to_string(), theme().code_comment()));
+        assert!(lines[1].contains(&("Select".to_string(), theme().code_keyword())));
+        assert!(lines[1].contains(&("FROM".to_string(), theme().code_keyword())));
+    }
+
+    #[test]
+    fn python_triple_quotes_and_shell_comments() {
+        let py = CodeBlock {
+            tag: "python".into(),
+            lines: vec!["x = \"\"\"a".into(), "b\"\"\"".into()],
+        };
+        let lines = highlight(&py);
+        assert_eq!(
+            lines[1],
+            vec![("b\"\"\"".to_string(), theme().code_string())]
+        );
+        let sh = CodeBlock {
+            tag: "sh".into(),
+            lines: vec!["echo hi # greet".into()],
+        };
+        assert!(highlight(&sh)[0].contains(&("# greet".to_string(), theme().code_comment())));
+    }
+
+    #[test]
+    fn unknown_language_renders_plain() {
+        let block = CodeBlock {
+            tag: String::new(),
+            lines: vec!["let x".into()],
+        };
+        assert_eq!(
+            highlight(&block),
+            vec![vec![("let x".to_string(), theme().text())]]
+        );
+    }
+
+    #[test]
+    fn render_numbers_lines_and_wraps_at_width() {
+        let block = CodeBlock {
+            tag: "js".into(),
+            lines: vec!["const abcdefgh = 1;".into(), "x".into()],
+        };
+        // gutter "1 │ " is 4 cells, leaving 10 for code.
+        let lines = render_lines(&block, 14);
+        let texts: Vec<String> = lines.iter().map(text_of).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "1 │ const abcd".to_string(),
+                "  │ efgh = 1;".to_string(),
+                "2 │ x".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_counts_wide_characters_as_two_cells() {
+        let rows = wrap_tokens(vec![("日本語".to_string(), theme().text())], 4);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], vec![("日本".to_string(), theme().text())]);
+    }
+}
@@ -0,0 +1,856 @@
+//! Question editor / authoring mode.
+//!
+//! Creates and edits `Question`s in a custom pack file (see `io::Pack`)
+//! without hand-editing JSON. Three screens:
+//!
+//! - **List**: every question in the pack, with a per-question issue count.
+//! - **Form**: one field per row (id, genre, question text / reading in
+//!   both languages, each choice's ja / en label and `ja_typings`, correct
+//!   answer, explanation in both languages, source and reference). The
+//!   side pane re-runs the shape validator and the prefix validator on
+//!   every keystroke.
+//! - **Preview**: the draft rendered through `QuizUI::render_preview`, so
+//!   inline code, the typewriter reveal and the choice fade-in look
+//!   exactly like a real run.
+//!
+//! Nothing touches disk until the author saves from the list screen.
+
+use crate::io::shape::{self, CHOICES_PER_QUESTION};
+use crate::io::{find_prefix_conflicts, format_conflict, Pack};
+use crate::types::{Choice, Language, Question};
+use crate::ui::image::search_dirs_for;
+use crate::ui::theme::theme;
+use crate::ui::{
+    HelpEntry, HelpLine, InputChannel, KeyEventSource, PaneFrame, QuizUI, RecvOutcome,
+};
+use crossterm::{
+    event::{KeyCode, KeyEvent, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+
+/// Genre pre-filled into new drafts.
+const DEFAULT_GENRE: &str = "custom";
+/// Languages the form exposes. Matches the bundled bank's two languages.
+const LANGS: [&str; 2] = ["ja", "en"];
+const CHOICE_LABELS: [&str; CHOICES_PER_QUESTION] = ["A", "B", "C", "D"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    List,
+    Form,
+    Preview,
+}
+
+/// One editable row of the form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Id,
+    Genre,
+    Text(&'static str),
+    Reading(&'static str),
+    Label(usize, &'static str),
+    /// `ja_typings` of a choice, edited as a comma-separated list.
+    Typings(usize),
+    /// Correct answer; cycled with ←/→ rather than typed.
+    Correct,
+    Explanation(&'static str),
+    Source,
+    Reference,
+}
+
+impl Field {
+    fn all() -> Vec<Field> {
+        let mut fields = vec![Field::Id, Field::Genre];
+        fields.extend(LANGS.iter().map(|l| Field::Text(l)));
+        fields.extend(LANGS.iter().map(|l| Field::Reading(l)));
+        for idx in 0..CHOICES_PER_QUESTION {
+            fields.extend(LANGS.iter().map(|l| Field::Label(idx, l)));
+            fields.push(Field::Typings(idx));
+        }
+        fields.push(Field::Correct);
+        fields.extend(LANGS.iter().map(|l| Field::Explanation(l)));
+        fields.extend([Field::Source, Field::Reference]);
+        fields
+    }
+
+    fn caption(&self) -> String {
+        match self {
+            Field::Id => "id".to_string(),
+            Field::Genre => "genre".to_string(),
+            Field::Text(lang) => format!("question.{lang}"),
+            Field::Reading(lang) => format!("reading.{lang}"),
+            Field::Label(idx, lang) => format!("{}.{lang}", CHOICE_LABELS[*idx]),
+            Field::Typings(idx) => format!("{}.typings", CHOICE_LABELS[*idx]),
+            Field::Correct => "correct".to_string(),
+            Field::Explanation(lang) => format!("explanation.{lang}"),
+            Field::Source => "source".to_string(),
+            Field::Reference => "reference".to_string(),
+        }
+    }
+
+    /// Current value of this field in `draft`, as the form displays it.
+    fn value(&self, draft: &Question) -> String {
+        match self {
+            Field::Id => draft.id.clone(),
+            Field::Genre => draft.genre.clone(),
+            Field::Text(lang) => draft.question_text.get(*lang).cloned().unwrap_or_default(),
+            Field::Reading(lang) => draft
+                .question_text_reading
+                .get(*lang)
+                .cloned()
+                .unwrap_or_default(),
+            Field::Label(idx, lang) => draft
+                .choices
+                .get(*idx)
+                .and_then(|c| c.labels.get(*lang))
+                .cloned()
+                .unwrap_or_default(),
+            Field::Typings(idx) => draft
+                .choices
+                .get(*idx)
+                .map(|c| c.ja_typings.join(", "))
+                .unwrap_or_default(),
+            Field::Correct => CHOICE_LABELS
+                .get(draft.correct_answer_index)
+                .copied()
+                .unwrap_or("?")
+                .to_string(),
+            Field::Explanation(lang) => draft.explanation.get(*lang).cloned().unwrap_or_default(),
+            Field::Source => draft.source.clone().unwrap_or_default(),
+            Field::Reference => draft.reference.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Write `raw` back into `draft`. Empty text removes the language key
+    /// instead of storing `""`, so saved packs don't carry blank entries
+    /// and the validator reports the field as missing.
+    fn apply(&self, draft: &mut Question, raw: &str) {
+        fn set_or_remove(map: &mut HashMap<String, String>, lang: &str, raw: &str) {
+            if raw.is_empty() {
+                map.remove(lang);
+            } else {
+                map.insert(lang.to_string(), raw.to_string());
+            }
+        }
+        match self {
+            Field::Id => draft.id = raw.to_string(),
+            Field::Genre => draft.genre = raw.to_string(),
+            Field::Text(lang) => set_or_remove(&mut draft.question_text, lang, raw),
+            Field::Reading(lang) => set_or_remove(&mut draft.question_text_reading, lang, raw),
+            Field::Label(idx, lang) => {
+                if let Some(choice) = draft.choices.get_mut(*idx) {
+                    set_or_remove(&mut choice.labels, lang, raw);
+                }
+            }
+            Field::Typings(idx) => {
+                if let Some(choice) = draft.choices.get_mut(*idx) {
+                    choice.ja_typings = parse_typings(raw);
+                }
+            }
+            Field::Correct => {}
+            Field::Explanation(lang) => set_or_remove(&mut draft.explanation, lang, raw),
+            Field::Source => draft.source = (!raw.is_empty()).then(|| raw.to_string()),
+            Field::Reference => draft.reference = (!raw.is_empty()).then(|| raw.to_string()),
+        }
+    }
+}
+
+/// Split the comma-separated typings field. Typings are lowercased like
+/// `DataLoader::get_choice_typing_texts` does at runtime, so what the
+/// author sees in the file is what the judge compares against.
+fn parse_typings(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Fresh draft with the right number of empty choices.
+fn blank_question(id: String) -> Question {
+    Question {
+        id,
+        genre: DEFAULT_GENRE.to_string(),
+        question_text: HashMap::new(),
+        question_text_reading: HashMap::new(),
+        choices: vec![Choice::default(); CHOICES_PER_QUESTION],
+        correct_answer_index: 0,
+        image_path: None,
+        ja_reviewed: false,
+        explanation: HashMap::new(),
+        source: None,
+        reference: None,
+    }
+}
+
+/// Live validator output for `draft`, formatted for the side pane. Runs
+/// the shape checks, the prefix-conflict validator, and an id-collision
+/// check against the other questions in the pack.
+fn draft_issues(draft: &Question, others: &[Question]) -> Vec<String> {
+    let mut issues: Vec<String> = shape::check_question(draft)
+        .into_iter()
+        .map(|i| i.message)
+        .collect();
+    if others.iter().any(|q| q.id == draft.id) {
+        issues.push(format!("id {:?} is already used in this pack", draft.id));
+    }
+    issues.extend(
+        find_prefix_conflicts(std::slice::from_ref(draft))
+            .iter()
+            .map(format_conflict),
+    );
+    issues
+}
+
+pub struct EditorUI {
+    pack_path: String,
+    questions: Vec<Question>,
+    screen: Screen,
+    /// Highlighted row on the list screen.
+    selected: usize,
+    /// Question being edited. Only copied back into `questions` when the
+    /// author commits the form with Enter.
+    draft: Question,
+    /// Index in `questions` the draft replaces; `None` for a new question.
+    draft_index: Option<usize>,
+    /// The draft as the form opened it, to tell whether it was edited.
+    draft_original: Question,
+    fields: Vec<Field>,
+    field_cursor: usize,
+    /// Raw text of the focused field. Kept separately from the draft so a
+    /// half-typed `ja_typings` list (e.g. a trailing comma) survives
+    /// round-tripping through `parse_typings`.
+    buffer: String,
+    preview: Option<QuizUI>,
+    preview_language: Language,
+    /// Set when the pack differs from what is on disk.
+    dirty: bool,
+    /// The key that last warned about unsaved changes; pressing it again
+    /// discards them. Any other key disarms it.
+    confirm_discard: Option<&'static str>,
+    /// One-line feedback shown in the input-echo row (save result etc.).
+    message: Option<String>,
+}
+
+impl EditorUI {
+    /// Open (or start) the pack at `pack_path`.
+    pub fn load(pack_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let questions = Pack::load(pack_path)?;
+        Ok(Self::from_questions(pack_path.to_string(), questions))
+    }
+
+    fn from_questions(pack_path: String, questions: Vec<Question>) -> Self {
+        let draft = blank_question(Pack::next_id(&questions));
+        Self {
+            draft_original: draft.clone(),
+            pack_path,
+            questions,
+            screen: Screen::List,
+            selected: 0,
+            draft,
+            draft_index: None,
+            fields: Field::all(),
+            field_cursor: 0,
+            buffer: String::new(),
+            preview: None,
+            preview_language: Language::Japanese,
+            dirty: false,
+            confirm_discard: None,
+            message: None,
+        }
+    }
+
+    pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+
+        let input = InputChannel::spawn();
+        let result = self.run_app(&mut terminal, &input);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        result
+    }
+
+    fn run_app<S: KeyEventSource>(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        input: &S,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // Same cadence as Quiz so the preview reveal animates smoothly.
+        const REDRAW: Duration = Duration::from_millis(30);
+
+        loop {
+            terminal.draw(|f| self.ui(f))?;
+            match input.recv_until(REDRAW) {
+                RecvOutcome::Key(key) => {
+                    if self.handle_key(key) {
+                        break;
+                    }
+                }
+                RecvOutcome::Timeout => {}
+                RecvOutcome::Disconnected => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` when the editor should exit.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl_c =
+            key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+        let leave_key = if ctrl_c {
+            Some("Ctrl+C")
+        } else if key.code == KeyCode::Esc && self.screen == Screen::List {
+            Some("Esc")
+        } else {
+            None
+        };
+        if self.confirm_discard.is_some() && self.confirm_discard != leave_key {
+            self.confirm_discard = None;
+        }
+        if ctrl_c {
+            return self.may_leave("Ctrl+C");
+        }
+        match self.screen {
+            Screen::List => self.handle_key_list(key),
+            Screen::Form => {
+                self.handle_key_form(key);
+                false
+            }
+            Screen::Preview => {
+                self.handle_key_preview(key);
+                false
+            }
+        }
+    }
+
+    /// Whether `key` may close the editor now. With unsaved changes the
+    /// first press only warns; pressing the same key again discards.
+    fn may_leave(&mut self, key: &'static str) -> bool {
+        if self.has_unsaved_changes() && self.confirm_discard != Some(key) {
+            self.confirm_discard = Some(key);
+            self.message = Some(if self.screen == Screen::List {
+                format!("Unsaved changes. {key} again to discard, s to save.")
+            } else {
+                // `s` would only type into the focused field here.
+                format!("Unsaved draft. {key} again to discard, Enter to keep it.")
+            });
+            return false;
+        }
+        true
+    }
+
+    /// The pack differs from disk, or the open form's draft from what the
+    /// form started with.
+    fn has_unsaved_changes(&self) -> bool {
+        self.dirty || (self.screen != Screen::List && self.draft != self.draft_original)
+    }
+
+    fn handle_key_list(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc => return self.may_leave("Esc"),
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < self.questions.len() => self.selected += 1,
+            KeyCode::Enter => {
+                if let Some(question) = self.questions.get(self.selected).cloned() {
+                    self.open_form(question, Some(self.selected));
+                }
+            }
+            KeyCode::Char('n') => {
+                let draft = blank_question(Pack::next_id(&self.questions));
+                self.open_form(draft, None);
+            }
+            KeyCode::Char('d') if self.selected < self.questions.len() => {
+                let removed = self.questions.remove(self.selected);
+                self.selected = self.selected.min(self.questions.len().saturating_sub(1));
+                self.dirty = true;
+                self.message = Some(format!("Deleted {}.", removed.id));
+            }
+            KeyCode::Char('s') => self.save(),
+            _ => {}
+        }
+        false
+    }
+
+    fn handle_key_form(&mut self, key: KeyEvent) {
+        self.message = None;
+        match key.code {
+            KeyCode::Esc => self.screen = Screen::List,
+            KeyCode::Enter => self.commit_draft(),
+            KeyCode::Up | KeyCode::BackTab => {
+                self.focus_field(self.field_cursor.saturating_sub(1));
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.focus_field((self.field_cursor + 1).min(self.fields.len() - 1));
+            }
+            KeyCode::F(2) => self.open_preview(),
+            KeyCode::Left | KeyCode::Right if self.current_field() == Field::Correct => {
+                let n = CHOICES_PER_QUESTION;
+                let idx = self.draft.correct_answer_index % n;
+                self.draft.correct_answer_index = if key.code == KeyCode::Left {
+                    (idx + n - 1) % n
+                } else {
+                    (idx + 1) % n
+                };
+                self.buffer = Field::Correct.value(&self.draft);
+            }
+            KeyCode::Backspace if self.current_field() != Field::Correct => {
+                self.buffer.pop();
+                self.current_field().apply(&mut self.draft, &self.buffer);
+            }
+            KeyCode::Char(c)
+                if self.current_field() != Field::Correct
+                    && !key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.buffer.push(c);
+                self.current_field().apply(&mut self.draft, &self.buffer);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_key_preview(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::F(2) => {
+                self.preview = None;
+                self.screen = Screen::Form;
+            }
+            // Replay the reveal from the start.
+            KeyCode::Char('r') => self.open_preview(),
+            KeyCode::Char('l') => {
+                self.preview_language = match self.preview_language {
+                    Language::Japanese => Language::English,
+                    Language::English => Language::Japanese,
+                };
+                self.open_preview();
+            }
+            _ => {}
+        }
+    }
+
+    fn current_field(&self) -> Field {
+        self.fields[self.field_cursor]
+    }
+
+    fn focus_field(&mut self, cursor: usize) {
+        self.field_cursor = cursor;
+        self.buffer = self.current_field().value(&self.draft);
+    }
+
+    fn open_form(&mut self, draft: Question, index: Option<usize>) {
+        self.draft_original = draft.clone();
+        self.draft = draft;
+        self.draft_index = index;
+        self.screen = Screen::Form;
+        self.message = None;
+        self.focus_field(0);
+    }
+
+    fn open_preview(&mut self) {
+        self.preview = Some(
+            QuizUI::preview(self.draft.clone(), self.preview_language.clone())
+                .with_image_dirs(search_dirs_for(&self.pack_path)),
+        );
+        self.screen = Screen::Preview;
+    }
+
+    /// Copy the draft back into the pack. Issues don't block the commit —
+    /// authors often save work in progress — but they stay visible on the
+    /// list screen until fixed.
+    fn commit_draft(&mut self) {
+        let draft = self.draft.clone();
+        match self.draft_index {
+            Some(idx) if idx < self.questions.len() => self.questions[idx] = draft,
+            _ => {
+                self.questions.push(draft);
+                self.selected = self.questions.len() - 1;
+            }
+        }
+        self.dirty = true;
+        self.screen = Screen::List;
+    }
+
+    /// Questions other than the draft's own slot, for the id-collision check.
+    fn other_questions(&self) -> Vec<Question> {
+        self.questions
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != self.draft_index)
+            .map(|(_, q)| q.clone())
+            .collect()
+    }
+
+    fn save(&mut self) {
+        self.message = Some(match Pack::save(&self.pack_path, &self.questions) {
+            Ok(()) => {
+                self.dirty = false;
+                format!(
+                    "Saved {} question(s) to {}",
+                    self.questions.len(),
+                    self.pack_path
+                )
+            }
+            Err(e) => format!("Save failed: {e}"),
+        });
+    }
+
+    fn ui(&mut self, f: &mut Frame) {
+        let frame = PaneFrame::quiz(f.area());
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(6)])
+            .split(frame.main);
+        let title = Paragraph::new(format!("type-globe - Editor ({})", self.pack_path))
+            .style(theme().title())
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(title, chunks[0]);
+
+        match self.screen {
+            Screen::List => self.render_list(f, chunks[1]),
+            Screen::Form => self.render_form(f, chunks[1]),
+            Screen::Preview => {
+                if let Some(preview) = self.preview.as_mut() {
+                    preview.render_preview(f, chunks[1]);
+                }
+            }
+        }
+        self.render_issues(f, frame.side);
+        self.render_input_echo(f, frame.input_echo);
+        self.help_line().render(f, frame.help_line);
+    }
+
+    fn render_list(&self, f: &mut Frame, area: Rect) {
+        let all_issues = shape::check_questions(&self.questions);
+        let items: Vec<ListItem> = if self.questions.is_empty() {
+            vec![ListItem::new(Line::from(Span::styled(
+                "(empty pack — press n to add a question)",
+                theme().dim(),
+            )))]
+        } else {
+            self.questions
+                .iter()
+                .map(|q| {
+                    let text = q
+                        .question_text
+                        .get("ja")
+                        .or_else(|| q.question_text.get("en"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let count = all_issues.iter().filter(|i| i.question_id == q.id).count();
+                    let badge = if count == 0 {
+                        Span::styled(" ok ", theme().correct())
+                    } else {
+                        Span::styled(format!(" {count}! "), theme().issue())
+                    };
+                    ListItem::new(Line::from(vec![
+                        badge,
+                        Span::styled(format!("{:<8}", q.id), theme().text()),
+                        Span::styled(format!("[{}] ", q.genre), theme().dim()),
+                        Span::styled(text, theme().text()),
+                    ]))
+                })
+                .collect()
+        };
+        let mut state = ListState::default();
+        if !self.questions.is_empty() {
+            state.select(Some(self.selected));
+        }
+        let list = List::new(items).highlight_style(theme().selected()).block(
+            Block::default()
+                .title(format!(" Questions ({}) ", self.questions.len()))
+                .borders(Borders::ALL)
+                .padding(Padding::horizontal(1)),
+        );
+        f.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_form(&self, f: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let value = if i == self.field_cursor {
+                    self.buffer.clone()
+                } else {
+                    field.value(&self.draft)
+                };
+                let value_style = if i == self.field_cursor {
+                    theme().selected()
+                } else {
+                    theme().text()
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<14}", field.caption()), theme().dim()),
+                    Span::styled(value, value_style),
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default();
+        state.select(Some(self.field_cursor));
+        let title = match self.draft_index {
+            Some(_) => format!(" Edit {} ", self.draft.id),
+            None => " New question ".to_string(),
+        };
+        let list = List::new(items).highlight_symbol("> ").block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .padding(Padding::horizontal(1)),
+        );
+        f.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// Side pane: live validator output for the draft (form / preview) or
+    /// a pack-wide summary (list).
+    fn render_issues(&self, f: &mut Frame, area: Rect) {
+        let issues: Vec<String> = match self.screen {
+            Screen::List => shape::check_questions(&self.questions)
+                .iter()
+                .map(shape::format_issue)
+                .collect(),
+            Screen::Form | Screen::Preview => draft_issues(&self.draft, &self.other_questions()),
+        };
+        let lines: Vec<Line> = if issues.is_empty() {
+            vec![Line::from(Span::styled("No issues", theme().correct()))]
+        } else {
+            issues
+                .into_iter()
+                .map(|i| Line::from(Span::styled(format!("- {i}"), theme().issue())))
+                .collect()
+        };
+        let body = Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(Block::default().title(" Validator ").borders(Borders::ALL));
+        f.render_widget(body, area);
+    }
+
+    fn render_input_echo(&self, f: &mut Frame, area: Rect) {
+        let line = if let Some(message) = &self.message {
+            Line::from(Span::styled(message.clone(), theme().text()))
+        } else if self.screen == Screen::Form {
+            Line::from(vec![
+                Span::styled(
+                    format!("{}> ", self.current_field().caption()),
+                    theme().dim(),
+                ),
+                Span::styled(format!("{}_", self.buffer), theme().input_echo()),
+            ])
+        } else {
+            Line::from("")
+        };
+        f.render_widget(Paragraph::new(line), area);
+    }
+
+    fn help_line(&self) -> HelpLine {
+        match self.screen {
+            Screen::List => HelpLine::new(vec![
+                HelpEntry::new("Esc", "Quit"),
+                HelpEntry::new("Enter", "Edit"),
+                HelpEntry::new("n", "New"),
+                HelpEntry::new("d", "Delete"),
+                HelpEntry::new("s", "Save"),
+            ]),
+            Screen::Form => HelpLine::new(vec![
+                HelpEntry::new("Esc", "Discard"),
+                HelpEntry::new("Enter", "Apply"),
+                HelpEntry::new("↑↓", "Field"),
+                HelpEntry::new("←→", "Correct"),
+                HelpEntry::new("F2", "Preview"),
+            ]),
+            Screen::Preview => HelpLine::new(vec![
+                HelpEntry::new("Esc", "Back"),
+                HelpEntry::new("r", "Replay"),
+                HelpEntry::new("l", "Language"),
+            ]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_str(ui: &mut EditorUI, s: &str) {
+        for c in s.chars() {
+            ui.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    fn focus(ui: &mut EditorUI, field: Field) {
+        let idx = ui.fields.iter().position(|f| *f == field).expect("field");
+        ui.focus_field(idx);
+    }
+
+    #[test]
+    fn new_question_gets_next_custom_id_and_four_choices() {
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        assert_eq!(ui.screen, Screen::Form);
+        assert_eq!(ui.draft.id, "c0001");
+        assert_eq!(ui.draft.choices.len(), CHOICES_PER_QUESTION);
+    }
+
+    #[test]
+    fn typing_edits_focused_field_and_enter_commits() {
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        focus(&mut ui, Field::Text("en"));
+        type_str(&mut ui, "Which `fn`?");
+        focus(&mut ui, Field::Label(0, "ja"));
+        type_str(&mut ui, "いぬ");
+        ui.handle_key(key(KeyCode::Enter));
+        assert_eq!(ui.screen, Screen::List);
+        assert!(ui.dirty);
+        assert_eq!(ui.questions.len(), 1);
+        assert_eq!(ui.questions[0].question_text["en"], "Which `fn`?");
+        assert_eq!(ui.questions[0].choices[0].labels["ja"], "いぬ");
+    }
+
+    #[test]
+    fn typings_field_keeps_trailing_comma_while_typing() {
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        focus(&mut ui, Field::Typings(1));
+        type_str(&mut ui, "Inu,");
+        assert_eq!(ui.buffer, "Inu,");
+        type_str(&mut ui, " wanko");
+        assert_eq!(ui.draft.choices[1].ja_typings, vec!["inu", "wanko"]);
+    }
+
+    #[test]
+    fn erasing_a_label_removes_the_language_key() {
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        focus(&mut ui, Field::Label(2, "en"));
+        type_str(&mut ui, "x");
+        ui.handle_key(key(KeyCode::Backspace));
+        assert!(!ui.draft.choices[2].labels.contains_key("en"));
+    }
+
+    #[test]
+    fn explanation_and_source_fields_edit_the_draft() {
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        focus(&mut ui, Field::Explanation("en"));
+        type_str(&mut ui, "Use `move`.");
+        focus(&mut ui, Field::Source);
+        type_str(&mut ui, "x");
+        ui.handle_key(key(KeyCode::Backspace));
+        assert_eq!(ui.draft.explanation["en"], "Use `move`.");
+        assert_eq!(ui.draft.source, None, "an emptied field unsets it");
+    }
+
+    #[test]
+    fn correct_field_cycles_with_arrows() {
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        focus(&mut ui, Field::Correct);
+        ui.handle_key(key(KeyCode::Left));
+        assert_eq!(ui.draft.correct_answer_index, 3);
+        ui.handle_key(key(KeyCode::Right));
+        ui.handle_key(key(KeyCode::Right));
+        assert_eq!(ui.draft.correct_answer_index, 1);
+        type_str(&mut ui, "z");
+        assert_eq!(ui.buffer, "B");
+    }
+
+    #[test]
+    fn esc_on_form_discards_draft() {
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        type_str(&mut ui, "x");
+        ui.handle_key(key(KeyCode::Esc));
+        assert_eq!(ui.screen, Screen::List);
+        assert!(ui.questions.is_empty());
+        assert!(!ui.dirty);
+    }
+
+    #[test]
+    fn esc_on_dirty_list_needs_confirmation() {
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        ui.handle_key(key(KeyCode::Enter));
+        assert!(!ui.handle_key(key(KeyCode::Esc)));
+        assert!(ui.handle_key(key(KeyCode::Esc)));
+    }
+
+    #[test]
+    fn ctrl_c_on_dirty_list_needs_confirmation() {
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        assert!(ui.handle_key(ctrl_c), "a clean pack leaves at once");
+
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        ui.handle_key(key(KeyCode::Enter));
+        assert!(!ui.handle_key(ctrl_c));
+        assert!(ui.message.as_deref().is_some_and(|m| m.contains("Ctrl+C")));
+        assert!(ui.handle_key(ctrl_c));
+    }
+
+    #[test]
+    fn ctrl_c_with_edited_draft_needs_confirmation() {
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        assert!(ui.handle_key(ctrl_c), "an untouched form leaves at once");
+
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        type_str(&mut ui, "x");
+        assert!(!ui.handle_key(ctrl_c));
+        let message = ui.message.clone().expect("warned");
+        assert!(message.contains("Enter"), "{message}");
+        assert!(!message.contains("s to save"), "{message}");
+
+        type_str(&mut ui, "y");
+        assert!(!ui.handle_key(ctrl_c), "typing disarms the confirmation");
+        assert!(ui.handle_key(ctrl_c));
+    }
+
+    #[test]
+    fn esc_then_ctrl_c_does_not_discard() {
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let mut ui = EditorUI::from_questions("pack.json".into(), Vec::new());
+        ui.handle_key(key(KeyCode::Char('n')));
+        ui.handle_key(key(KeyCode::Enter));
+        assert!(!ui.handle_key(key(KeyCode::Esc)));
+        assert!(
+            !ui.handle_key(ctrl_c),
+            "Ctrl+C re-arms instead of discarding"
+        );
+        assert!(!ui.handle_key(key(KeyCode::Esc)));
+        assert!(ui.handle_key(key(KeyCode::Esc)));
+    }
+
+    #[test]
+    fn draft_issues_flag_id_collision_with_rest_of_pack() {
+        let existing = blank_question("c0001".into());
+        let draft = blank_question("c0001".into());
+        let issues = draft_issues(&draft, &[existing]);
+        assert!(issues.iter().any(|i| i.contains("already used")));
+    }
+}
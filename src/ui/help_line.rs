@@ -8,28 +8,36 @@
 //! state, so help-line content can change as the UI transitions (e.g. quiz
 //! "answering" → "result" → "finished").
 
+use crate::ui::theme::theme;
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{Color, Style},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
+use std::borrow::Cow;
 
-const STYLE_KEY: Style = Style::new().fg(Color::Yellow);
-const STYLE_ACTION: Style = Style::new().fg(Color::Gray);
-const STYLE_SEP: Style = Style::new().fg(Color::DarkGray);
-
-/// One key-action pair shown in the help line.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// One key-action pair shown in the help line. `key` is owned when it is
+/// generated from the keymap (`ui::keymap`) and borrowed for fixed hints.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HelpEntry {
-    pub key: &'static str,
+    pub key: Cow<'static, str>,
     pub action: &'static str,
 }
 
 impl HelpEntry {
     pub const fn new(key: &'static str, action: &'static str) -> Self {
-        Self { key, action }
+        Self {
+            key: Cow::Borrowed(key),
+            action,
+        }
+    }
+
+    pub fn owned(key: String, action: &'static str) -> Self {
+        Self {
+            key: Cow::Owned(key),
+            action,
+        }
     }
 }
 
@@ -52,10 +60,13 @@ impl HelpLine {
         let mut spans: Vec<Span<'_>> = Vec::with_capacity(self.entries.len() * 3);
         for (i, entry) in self.entries.iter().enumerate() {
             if i > 0 {
-                spans.push(Span::styled("  ", STYLE_SEP));
+                spans.push(Span::styled("  ", theme().dim()));
             }
-            spans.push(Span::styled(format!("[{}]", entry.key), STYLE_KEY));
-            spans.push(Span::styled(format!(" {}", entry.action), STYLE_ACTION));
+            spans.push(Span::styled(format!("[{}]", entry.key), theme().help_key()));
+            spans.push(Span::styled(
+                format!(" {}", entry.action),
+                theme().help_action(),
+            ));
         }
         let paragraph = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
         f.render_widget(paragraph, area);
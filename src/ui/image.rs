@@ -0,0 +1,729 @@
+//! Question images (`Question::image_path`).
+//!
+//! The picture is drawn with the best graphics protocol the terminal
+//! speaks:
+//!
+//! - **kitty** — kitty / Ghostty graphics protocol (PNG payload, chunked).
+//! - **iTerm2** — OSC 1337 inline images (iTerm2, WezTerm).
+//! - **sixel** — foot, mlterm, and any `TERM` advertising sixel.
+//! - **half-block** — `▀` cells with fg = upper pixel, bg = lower pixel.
+//!   Works everywhere ratatui does, so it is the fallback and the mode the
+//!   editor preview always uses.
+//!
+//! Detection is env-based (no terminal query round-trip, which would race
+//! the input thread). `TYPE_GLOBE_GRAPHICS=kitty|iterm2|sixel|halfblock`
+//! overrides it. Inside tmux we fall back to half-blocks because the
+//! pass-through escapes differ per tmux setting.
+//!
+//! ratatui only knows about text cells, so the pixel protocols run in two
+//! steps: [`ImagePane::render`] draws the pane border and leaves the inner
+//! cells blank (a blank cell never gets repainted by ratatui's diff), then
+//! [`ImagePane::flush`] writes the escape sequence after `terminal.draw`.
+//! It only re-emits when the image or its rect changes, so the 30 ms redraw
+//! loop doesn't resend the picture every frame.
+//!
+//! Relative `image_path`s are resolved against the caller's search
+//! directories in order — the pack / question-file directory first, then
+//! the bundled `data/` — see [`resolve_image_path`].
+
+use crate::ui::theme::theme;
+use image::codecs::png::PngEncoder;
+use image::imageops::FilterType;
+use image::{ColorType, ImageEncoder, RgbImage};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Cell size assumed when the terminal doesn't report pixel dimensions.
+const FALLBACK_CELL: CellSize = CellSize {
+    width: 10,
+    height: 20,
+};
+/// Kitty requires payload chunks of at most 4096 base64 bytes.
+const KITTY_CHUNK: usize = 4096;
+
+/// How the picture reaches the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+    HalfBlock,
+}
+
+impl GraphicsProtocol {
+    /// Detect from the process environment.
+    pub fn detect() -> Self {
+        Self::from_env(|key| std::env::var(key).ok())
+    }
+
+    /// Detection against an arbitrary env lookup, so tests don't have to
+    /// mutate the real environment.
+    pub fn from_env(get: impl Fn(&str) -> Option<String>) -> Self {
+        if let Some(forced) = get("TYPE_GLOBE_GRAPHICS") {
+            match forced.to_ascii_lowercase().as_str() {
+                "kitty" => return Self::Kitty,
+                "iterm2" | "iterm" => return Self::Iterm2,
+                "sixel" => return Self::Sixel,
+                "halfblock" | "none" => return Self::HalfBlock,
+                _ => {}
+            }
+        }
+        if get("TMUX").is_some() {
+            return Self::HalfBlock;
+        }
+        let term = get("TERM").unwrap_or_default();
+        let term_program = get("TERM_PROGRAM").unwrap_or_default();
+        if get("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || term_program == "ghostty"
+        {
+            return Self::Kitty;
+        }
+        if term_program == "iTerm.app"
+            || term_program == "WezTerm"
+            || get("LC_TERMINAL").as_deref() == Some("iTerm2")
+        {
+            return Self::Iterm2;
+        }
+        if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") {
+            return Self::Sixel;
+        }
+        Self::HalfBlock
+    }
+}
+
+/// Pixel size of one terminal cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl CellSize {
+    /// Ask the terminal (`TIOCGWINSZ` pixel fields). Many terminals leave
+    /// those at zero, in which case we assume a 1:2 cell.
+    pub fn detect() -> Self {
+        match crossterm::terminal::window_size() {
+            Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+                Self {
+                    width: (size.width / size.columns).max(1) as u32,
+                    height: (size.height / size.rows).max(1) as u32,
+                }
+            }
+            _ => FALLBACK_CELL,
+        }
+    }
+}
+
+/// Directory of the bundled question bank, the last place relative image
+/// paths are looked up.
+const BUNDLED_DATA_DIR: &str = "data";
+
+/// Search directories for images referenced from `question_file`: the
+/// file's own directory first (so packs can ship pictures next to the
+/// JSON), then the bundled `data/`.
+pub fn search_dirs_for(question_file: &str) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(parent) = Path::new(question_file).parent() {
+        dirs.push(parent.to_path_buf());
+    }
+    let bundled = PathBuf::from(BUNDLED_DATA_DIR);
+    if !dirs.contains(&bundled) {
+        dirs.push(bundled);
+    }
+    dirs
+}
+
+/// Resolve `image_path` to a file on disk. Absolute paths are taken as-is;
+/// relative ones are tried against each of `search_dirs` in order.
+pub fn resolve_image_path(image_path: &str, search_dirs: &[PathBuf]) -> Option<PathBuf> {
+    let path = Path::new(image_path);
+    if path.is_absolute() {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    search_dirs
+        .iter()
+        .map(|dir| dir.join(path))
+        .find(|candidate| candidate.is_file())
+}
+
+/// A decoded question image.
+pub struct QuestionImage {
+    pixels: RgbImage,
+}
+
+impl QuestionImage {
+    pub fn open(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            pixels: image::open(path)?.to_rgb8(),
+        })
+    }
+
+    /// Largest rect inside `area` that keeps the image's aspect ratio,
+    /// centred. Small images are scaled up — flags are often tiny PNGs.
+    pub fn fit(&self, area: Rect, cell: CellSize) -> Rect {
+        let (w, h) = self.pixels.dimensions();
+        if area.width == 0 || area.height == 0 || w == 0 || h == 0 {
+            return Rect::new(area.x, area.y, 0, 0);
+        }
+        let area_w = area.width as f64 * cell.width as f64;
+        let area_h = area.height as f64 * cell.height as f64;
+        let scale = (area_w / w as f64).min(area_h / h as f64);
+        let cols = ((w as f64 * scale / cell.width as f64) as u16).clamp(1, area.width);
+        let rows = ((h as f64 * scale / cell.height as f64) as u16).clamp(1, area.height);
+        Rect::new(
+            area.x + (area.width - cols) / 2,
+            area.y + (area.height - rows) / 2,
+            cols,
+            rows,
+        )
+    }
+
+    /// Half-block rendering: two vertical pixels per cell.
+    pub fn half_block_lines(&self, cols: u16, rows: u16) -> Vec<Line<'static>> {
+        let small = self.scaled(cols as u32, rows as u32 * 2);
+        (0..rows as u32)
+            .map(|row| {
+                let spans: Vec<Span<'static>> = (0..cols as u32)
+                    .map(|col| {
+                        let top = small.get_pixel(col, row * 2);
+                        let bottom = small.get_pixel(col, row * 2 + 1);
+                        Span::styled(
+                            "▀",
+                            Style::new()
+                                .fg(theme().color(Color::Rgb(top[0], top[1], top[2])))
+                                .bg(theme().color(Color::Rgb(bottom[0], bottom[1], bottom[2]))),
+                        )
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect()
+    }
+
+    /// Escape sequence that paints the image over `rect` (cell
+    /// coordinates), wrapped in cursor save / restore so ratatui's idea of
+    /// the cursor position stays valid. `None` for [`GraphicsProtocol::HalfBlock`],
+    /// which goes through the normal buffer instead.
+    pub fn escape_sequence(
+        &self,
+        protocol: GraphicsProtocol,
+        rect: Rect,
+        cell: CellSize,
+    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let px_w = (rect.width as u32 * cell.width).max(1);
+        let px_h = (rect.height as u32 * cell.height).max(1);
+        let body = match protocol {
+            GraphicsProtocol::HalfBlock => return Ok(None),
+            GraphicsProtocol::Kitty => {
+                kitty_sequence(&self.png(px_w, px_h)?, rect.width, rect.height)
+            }
+            GraphicsProtocol::Iterm2 => {
+                let png = self.png(px_w, px_h)?;
+                format!(
+                    "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+                    png.len(),
+                    rect.width,
+                    rect.height,
+                    base64(&png)
+                )
+            }
+            GraphicsProtocol::Sixel => sixel(&self.scaled(px_w, px_h)),
+        };
+        Ok(Some(format!(
+            "\x1b7\x1b[{};{}H{body}\x1b8",
+            rect.y + 1,
+            rect.x + 1
+        )))
+    }
+
+    fn scaled(&self, width: u32, height: u32) -> RgbImage {
+        image::imageops::resize(
+            &self.pixels,
+            width.max(1),
+            height.max(1),
+            FilterType::Triangle,
+        )
+    }
+
+    fn png(&self, width: u32, height: u32) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let scaled = self.scaled(width, height);
+        let mut out = Vec::new();
+        PngEncoder::new(&mut out).write_image(
+            scaled.as_raw(),
+            scaled.width(),
+            scaled.height(),
+            ColorType::Rgb8,
+        )?;
+        Ok(out)
+    }
+}
+
+/// Per-question image state for `QuizUI`: the decoded picture plus what
+/// has already been written to the terminal.
+pub struct ImagePane {
+    search_dirs: Vec<PathBuf>,
+    protocol: GraphicsProtocol,
+    cell: CellSize,
+    /// `None` when the question has no image; `Err` carries the message
+    /// shown in place of an unreadable image so pack authors notice.
+    current: Option<Result<QuestionImage, String>>,
+    /// Half-block lines cached for the rect they were built for.
+    half_block: Option<(Rect, Vec<Line<'static>>)>,
+    /// Rect reserved for a pixel protocol by the last `render`.
+    pending: Option<Rect>,
+    /// Rect the terminal currently shows a pixel image in.
+    shown: Option<Rect>,
+    /// Set when the image changed since the last emit, so a new picture
+    /// landing in the same rect still gets written.
+    stale: bool,
+}
+
+impl ImagePane {
+    pub fn new(search_dirs: Vec<PathBuf>, protocol: GraphicsProtocol) -> Self {
+        let cell = if protocol == GraphicsProtocol::HalfBlock {
+            FALLBACK_CELL
+        } else {
+            CellSize::detect()
+        };
+        Self {
+            search_dirs,
+            protocol,
+            cell,
+            current: None,
+            half_block: None,
+            pending: None,
+            shown: None,
+            stale: false,
+        }
+    }
+
+    pub fn set_search_dirs(&mut self, search_dirs: Vec<PathBuf>) {
+        self.search_dirs = search_dirs;
+    }
+
+    /// Switch to a new question's image (or none).
+    pub fn load(&mut self, image_path: Option<&str>) {
+        self.current = image_path.map(|raw| {
+            let path = resolve_image_path(raw, &self.search_dirs)
+                .ok_or_else(|| format!("image not found: {raw}"))?;
+            QuestionImage::open(&path).map_err(|e| format!("image unreadable: {raw} ({e})"))
+        });
+        self.half_block = None;
+        self.stale = true;
+    }
+
+    /// `true` when the current question wants an image pane.
+    pub fn is_active(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Draw the pane frame (and the half-block picture, if that's the
+    /// protocol). Pixel protocols only reserve the rect here.
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let block = Block::default().title(" Image ").borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        self.pending = None;
+        match self.current.as_ref() {
+            None => {}
+            Some(Err(message)) => {
+                let paragraph = Paragraph::new(message.clone())
+                    .style(theme().dim())
+                    .alignment(Alignment::Center)
+                    .wrap(Wrap { trim: true });
+                f.render_widget(paragraph, inner);
+            }
+            Some(Ok(image)) => {
+                let rect = image.fit(inner, self.cell);
+                if rect.width == 0 || rect.height == 0 {
+                    return;
+                }
+                if self.protocol != GraphicsProtocol::HalfBlock {
+                    self.pending = Some(rect);
+                    return;
+                }
+                if self.half_block.as_ref().map(|(r, _)| *r) != Some(rect) {
+                    self.half_block = Some((rect, image.half_block_lines(rect.width, rect.height)));
+                }
+                if let Some((_, lines)) = self.half_block.as_ref() {
+                    f.render_widget(Paragraph::new(lines.clone()), rect);
+                }
+            }
+        }
+    }
+
+    /// Forget the reserved rect for frames that don't draw the pane
+    /// (summary / naming), so the next `flush` clears the picture.
+    pub fn hide(&mut self) {
+        self.pending = None;
+    }
+
+    /// Write pixel-protocol escapes after `terminal.draw`. Clearing a
+    /// kitty image is a delete command; sixel / iTerm2 pixels can only be
+    /// wiped by clearing the screen, which also makes ratatui repaint the
+    /// text on the next frame — the new image follows one frame later.
+    pub fn flush(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.protocol == GraphicsProtocol::HalfBlock
+            || (!self.stale && self.pending == self.shown)
+        {
+            return Ok(());
+        }
+        if self.shown.is_some() {
+            if self.protocol == GraphicsProtocol::Kitty {
+                self.release(terminal.backend_mut())?;
+            } else {
+                terminal.clear()?;
+                self.shown = None;
+                return Ok(());
+            }
+        }
+        self.stale = false;
+        let (Some(rect), Some(Ok(image))) = (self.pending, self.current.as_ref()) else {
+            self.shown = None;
+            return Ok(());
+        };
+        if let Some(seq) = image.escape_sequence(self.protocol, rect, self.cell)? {
+            let out = terminal.backend_mut();
+            out.write_all(seq.as_bytes())?;
+            out.flush()?;
+        }
+        self.shown = Some(rect);
+        Ok(())
+    }
+
+    /// Remove any kitty image before the alt screen is torn down; kitty
+    /// keeps placements alive otherwise.
+    pub fn release(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.protocol == GraphicsProtocol::Kitty && self.shown.is_some() {
+            out.write_all(b"\x1b_Ga=d,d=A,q=2\x1b\\")?;
+            out.flush()?;
+        }
+        self.shown = None;
+        Ok(())
+    }
+}
+
+/// Kitty graphics: PNG (`f=100`), transmit-and-display (`a=T`), sized to
+/// `cols`×`rows` cells, responses suppressed (`q=2`) so the terminal's
+/// acknowledgement doesn't show up as key input, cursor left in place
+/// (`C=1`).
+fn kitty_sequence(png: &[u8], cols: u16, rows: u16) -> String {
+    let payload = base64(png);
+    let chunks: Vec<&str> = payload
+        .as_bytes()
+        .chunks(KITTY_CHUNK)
+        .map(|c| std::str::from_utf8(c).unwrap_or_default())
+        .collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            out.push_str(&format!(
+                "\x1b_Gf=100,a=T,c={cols},r={rows},q=2,C=1,m={more};{chunk}\x1b\\"
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    out
+}
+
+/// Sixel encoding against a fixed 6×6×6 colour cube. Good enough for flags
+/// and logos, and avoids a per-image palette quantiser.
+fn sixel(pixels: &RgbImage) -> String {
+    fn level(v: u8) -> usize {
+        (v as usize * 5 + 127) / 255
+    }
+    fn palette_index(p: &image::Rgb<u8>) -> usize {
+        level(p[0]) * 36 + level(p[1]) * 6 + level(p[2])
+    }
+
+    let (width, height) = pixels.dimensions();
+    let mut out = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    for idx in 0..216 {
+        let (r, g, b) = (idx / 36, (idx / 6) % 6, idx % 6);
+        out.push_str(&format!("#{idx};2;{};{};{}", r * 20, g * 20, b * 20));
+    }
+    for band in (0..height).step_by(6) {
+        let band_rows = (height - band).min(6);
+        // sixel bits per colour for each column of this band.
+        let mut columns: Vec<[u8; 216]> = vec![[0; 216]; width as usize];
+        let mut used = [false; 216];
+        for dy in 0..band_rows {
+            for x in 0..width {
+                let idx = palette_index(pixels.get_pixel(x, band + dy));
+                columns[x as usize][idx] |= 1 << dy;
+                used[idx] = true;
+            }
+        }
+        let mut first = true;
+        for (idx, _) in used.iter().enumerate().filter(|(_, u)| **u) {
+            if !first {
+                out.push('$');
+            }
+            first = false;
+            out.push_str(&format!("#{idx}"));
+            let mut run_char = '\0';
+            let mut run_len = 0u32;
+            for column in &columns {
+                let c = (63 + column[idx]) as char;
+                if c == run_char {
+                    run_len += 1;
+                } else {
+                    push_sixel_run(&mut out, run_char, run_len);
+                    run_char = c;
+                    run_len = 1;
+                }
+            }
+            push_sixel_run(&mut out, run_char, run_len);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_run(out: &mut String, c: char, len: u32) {
+    match len {
+        0 => {}
+        1..=3 => (0..len).for_each(|_| out.push(c)),
+        _ => out.push_str(&format!("!{len}{c}")),
+    }
+}
+
+/// Standard (RFC 4648) base64 with padding — the only encoding both
+/// kitty and iTerm2 accept.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 {
+            ALPHABET[(n >> 6) as usize & 63] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            ALPHABET[n as usize & 63] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| map.get(key).cloned()
+    }
+
+    fn solid(width: u32, height: u32, rgb: [u8; 3]) -> QuestionImage {
+        QuestionImage {
+            pixels: RgbImage::from_pixel(width, height, image::Rgb(rgb)),
+        }
+    }
+
+    #[test]
+    fn base64_matches_rfc4648_vectors() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn detects_protocol_from_env() {
+        use GraphicsProtocol::*;
+        assert_eq!(GraphicsProtocol::from_env(env(&[])), HalfBlock);
+        assert_eq!(
+            GraphicsProtocol::from_env(env(&[("TERM", "xterm-kitty")])),
+            Kitty
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(env(&[("TERM_PROGRAM", "iTerm.app")])),
+            Iterm2
+        );
+        assert_eq!(GraphicsProtocol::from_env(env(&[("TERM", "foot")])), Sixel);
+        // tmux hides the outer terminal's capabilities.
+        assert_eq!(
+            GraphicsProtocol::from_env(env(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/x")])),
+            HalfBlock
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(env(&[
+                ("TERM", "xterm-kitty"),
+                ("TYPE_GLOBE_GRAPHICS", "sixel")
+            ])),
+            Sixel
+        );
+    }
+
+    #[test]
+    fn fit_keeps_aspect_and_centres() {
+        let cell = CellSize {
+            width: 10,
+            height: 20,
+        };
+        // 2:1 image in a 40x10-cell (400x200 px) area → 40x10 cells.
+        let wide = solid(200, 100, [0, 0, 0]);
+        assert_eq!(
+            wide.fit(Rect::new(0, 0, 40, 10), cell),
+            Rect::new(0, 0, 40, 10)
+        );
+        // Square image in the same area → 20x10 cells, centred.
+        let square = solid(100, 100, [0, 0, 0]);
+        assert_eq!(
+            square.fit(Rect::new(0, 0, 40, 10), cell),
+            Rect::new(10, 0, 20, 10)
+        );
+    }
+
+    #[test]
+    fn half_block_uses_top_and_bottom_pixels() {
+        let mut pixels = RgbImage::from_pixel(2, 2, image::Rgb([255, 0, 0]));
+        pixels.put_pixel(0, 1, image::Rgb([0, 0, 255]));
+        pixels.put_pixel(1, 1, image::Rgb([0, 0, 255]));
+        let lines = QuestionImage { pixels }.half_block_lines(2, 1);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].spans.len(), 2);
+        let style = lines[0].spans[0].style;
+        assert_eq!(style.fg, Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(style.bg, Some(Color::Rgb(0, 0, 255)));
+    }
+
+    #[test]
+    fn escape_sequences_are_positioned_and_framed() {
+        let img = solid(4, 4, [255, 255, 255]);
+        let cell = CellSize {
+            width: 2,
+            height: 4,
+        };
+        let rect = Rect::new(3, 2, 2, 1);
+        let kitty = img
+            .escape_sequence(GraphicsProtocol::Kitty, rect, cell)
+            .expect("encode")
+            .expect("kitty emits");
+        assert!(kitty.starts_with("\x1b7\x1b[3;4H\x1b_Gf=100,a=T,c=2,r=1,q=2,C=1,m=0;"));
+        assert!(kitty.ends_with("\x1b\\\x1b8"));
+        let iterm = img
+            .escape_sequence(GraphicsProtocol::Iterm2, rect, cell)
+            .expect("encode")
+            .expect("iterm emits");
+        assert!(iterm.contains("\x1b]1337;File=inline=1;"));
+        let sixel = img
+            .escape_sequence(GraphicsProtocol::Sixel, rect, cell)
+            .expect("encode")
+            .expect("sixel emits");
+        // 4x4 px of pure white = palette entry 215, one band, run of 4.
+        assert!(sixel.contains("\"1;1;4;4"));
+        assert!(sixel.contains("#215!4N-"));
+        assert!(img
+            .escape_sequence(GraphicsProtocol::HalfBlock, rect, cell)
+            .expect("encode")
+            .is_none());
+    }
+
+    #[test]
+    fn kitty_payload_is_chunked() {
+        let png = vec![0u8; KITTY_CHUNK]; // base64 grows it past one chunk
+        let seq = kitty_sequence(&png, 1, 1);
+        assert!(seq.contains("m=1;"));
+        assert!(seq.contains("\x1b_Gm=0;"));
+    }
+
+    #[test]
+    fn resolve_tries_search_dirs_in_order() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let root = std::env::temp_dir().join(format!("type-globe-image-{nanos}"));
+        let (pack, bundled) = (root.join("pack"), root.join("data"));
+        std::fs::create_dir_all(pack.join("flags")).expect("mkdir");
+        std::fs::create_dir_all(bundled.join("flags")).expect("mkdir");
+        std::fs::write(bundled.join("flags/jp.png"), b"x").expect("write");
+        let dirs = vec![pack.clone(), bundled.clone()];
+        assert_eq!(
+            resolve_image_path("flags/jp.png", &dirs),
+            Some(bundled.join("flags/jp.png"))
+        );
+        std::fs::write(pack.join("flags/jp.png"), b"x").expect("write");
+        assert_eq!(
+            resolve_image_path("flags/jp.png", &dirs),
+            Some(pack.join("flags/jp.png"))
+        );
+        assert_eq!(resolve_image_path("flags/fr.png", &dirs), None);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn search_dirs_put_question_file_dir_first() {
+        assert_eq!(
+            search_dirs_for("packs/flags.json"),
+            vec![PathBuf::from("packs"), PathBuf::from("data")]
+        );
+        assert_eq!(
+            search_dirs_for("data/questions_ja.json"),
+            vec![PathBuf::from("data")]
+        );
+    }
+
+    #[test]
+    fn pane_decodes_png_from_search_dir() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir = std::env::temp_dir().join(format!("type-globe-image-png-{nanos}"));
+        std::fs::create_dir_all(&dir).expect("mkdir");
+        let png = solid(3, 2, [0, 128, 255]).png(3, 2).expect("encode");
+        std::fs::write(dir.join("dot.png"), png).expect("write");
+        let mut pane = ImagePane::new(vec![dir.clone()], GraphicsProtocol::HalfBlock);
+        pane.load(Some("dot.png"));
+        let Some(Ok(image)) = pane.current.as_ref() else {
+            panic!("image should decode");
+        };
+        assert_eq!(image.pixels.dimensions(), (3, 2));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn pane_reports_missing_image() {
+        let mut pane = ImagePane::new(Vec::new(), GraphicsProtocol::HalfBlock);
+        assert!(!pane.is_active());
+        pane.load(Some("nowhere.png"));
+        assert!(pane.is_active());
+        assert!(matches!(pane.current, Some(Err(ref m)) if m.contains("nowhere.png")));
+        pane.load(None);
+        assert!(!pane.is_active());
+    }
+}
@@ -0,0 +1,749 @@
+//! Configurable key bindings.
+//!
+//! Every screen resolves keys through an action enum ([`QuizAction`],
+//! [`ListenAction`], [`MenuAction`], [`RecordsAction`],
+//! [`SettingsAction`], plus
+//! [`StealthAction`] shared by Quiz and Listening) instead of matching
+//! `KeyCode`s directly, and builds its help line from the same bindings,
+//! so the hints can't drift from what the keys actually do.
+//!
+//! Bindings are read at startup from `keymap.yaml` in the data dir. Each
+//! listed action replaces that action's default keys; anything unlisted
+//! keeps its default:
+//!
+//! ```yaml
+//! quiz:
+//!   skip: F2
+//! menu:
+//!   up: [Up, k, w]
+//!   down: [Down, j, s]
+//! ```
+//!
+//! Keys are written `Esc`, `Enter`, `Tab`, `Bksp`, `Space`, `Up`, `F5`,
+//! a single character, or any of those behind `Ctrl+` / `Alt+`. Ctrl+C
+//! is reserved: it always quits, whatever the keymap says. Binding a
+//! printable key in Quiz or Listening takes that character away from
+//! answer input. A missing file means the defaults.
+
+use crate::ui::HelpEntry;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// One key, optionally with Ctrl / Alt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text.trim();
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if lower.starts_with("ctrl+") && rest.len() > 5 {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[5..];
+            } else if lower.starts_with("alt+") && rest.len() > 4 {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[4..];
+            } else {
+                break;
+            }
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "bksp" | "backspace" => KeyCode::Backspace,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "del" | "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{text}'")),
+                },
+            },
+        };
+        if code == KeyCode::Char('c') && modifiers == KeyModifiers::CONTROL {
+            return Err("Ctrl+C is reserved for quitting".to_string());
+        }
+        Ok(Self { code, modifiers })
+    }
+
+    /// Shift is ignored: it's already folded into the character for
+    /// `Char` keys, and no default binding uses it otherwise.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        key.code == self.code
+            && key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT) == self.modifiers
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    /// The form shown in the help line (`Esc`, `Bksp`, `↑`, `Ctrl+s`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::Esc => f.write_str("Esc"),
+            KeyCode::Enter => f.write_str("Enter"),
+            KeyCode::Tab => f.write_str("Tab"),
+            KeyCode::BackTab => f.write_str("BackTab"),
+            KeyCode::Backspace => f.write_str("Bksp"),
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::Home => f.write_str("Home"),
+            KeyCode::End => f.write_str("End"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            KeyCode::Delete => f.write_str("Del"),
+            KeyCode::Insert => f.write_str("Ins"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// A screen's action enum.
+pub trait Action: Copy + PartialEq + fmt::Debug + 'static {
+    /// Top-level key in `keymap.yaml`.
+    const SECTION: &'static str;
+    const ALL: &'static [Self];
+    /// Key under the section in `keymap.yaml`.
+    fn name(self) -> &'static str;
+    /// Help-line caption.
+    fn caption(self) -> &'static str;
+    fn default_keys(self) -> &'static [&'static str];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuizAction {
+    /// Leave the run (Esc also skips saving on the summary screens).
+    Quit,
+    Skip,
+    /// Take the next hint (`game::hint`) for the active question.
+    Hint,
+    /// Speak the question again when `read_aloud` is on.
+    ReadAloud,
+    /// Silence the sound effects until pressed again.
+    Mute,
+    Erase,
+    /// Summary → name entry, and save on name entry.
+    Confirm,
+    /// Scroll the Summary's explanation cards.
+    ScrollUp,
+    ScrollDown,
+}
+
+impl Action for QuizAction {
+    const SECTION: &'static str = "quiz";
+    const ALL: &'static [Self] = &[
+        Self::Quit,
+        Self::Skip,
+        Self::Hint,
+        Self::ReadAloud,
+        Self::Mute,
+        Self::Erase,
+        Self::Confirm,
+        Self::ScrollUp,
+        Self::ScrollDown,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Skip => "skip",
+            Self::Hint => "hint",
+            Self::ReadAloud => "read_aloud",
+            Self::Mute => "mute",
+            Self::Erase => "erase",
+            Self::Confirm => "confirm",
+            Self::ScrollUp => "scroll_up",
+            Self::ScrollDown => "scroll_down",
+        }
+    }
+
+    fn caption(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::Skip => "Skip",
+            Self::Hint => "Hint",
+            Self::ReadAloud => "Read",
+            Self::Mute => "Mute",
+            Self::Erase => "Erase",
+            Self::Confirm => "Confirm",
+            Self::ScrollUp => "Scroll up",
+            Self::ScrollDown => "Scroll down",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Quit => &["Esc"],
+            Self::Skip => &["Tab"],
+            Self::Hint => &["F3"],
+            Self::ReadAloud => &["F4"],
+            Self::Mute => &["F7"],
+            Self::Erase => &["Bksp"],
+            Self::Confirm => &["Enter"],
+            Self::ScrollUp => &["Up"],
+            Self::ScrollDown => &["Down"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListenAction {
+    Quit,
+    Replay,
+    /// Replay at [`SLOW_RATE`](crate::audio::speech::SLOW_RATE).
+    SlowReplay,
+    /// Silence the sound effects until pressed again; speech keeps playing.
+    Mute,
+    Erase,
+    /// Result → menu.
+    Confirm,
+}
+
+impl Action for ListenAction {
+    const SECTION: &'static str = "listen";
+    const ALL: &'static [Self] = &[
+        Self::Quit,
+        Self::Replay,
+        Self::SlowReplay,
+        Self::Mute,
+        Self::Erase,
+        Self::Confirm,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Replay => "replay",
+            Self::SlowReplay => "slow_replay",
+            Self::Mute => "mute",
+            Self::Erase => "erase",
+            Self::Confirm => "confirm",
+        }
+    }
+
+    fn caption(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::Replay => "Replay",
+            Self::SlowReplay => "Slow",
+            Self::Mute => "Mute",
+            Self::Erase => "Erase",
+            Self::Confirm => "Menu",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Quit => &["Esc"],
+            Self::Replay => &["Space"],
+            Self::SlowReplay => &["Tab"],
+            Self::Mute => &["F7"],
+            Self::Erase => &["Bksp"],
+            Self::Confirm => &["Enter"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    Up,
+    Down,
+    Confirm,
+    Back,
+    Quit,
+}
+
+impl Action for MenuAction {
+    const SECTION: &'static str = "menu";
+    const ALL: &'static [Self] = &[Self::Up, Self::Down, Self::Confirm, Self::Back, Self::Quit];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Confirm => "confirm",
+            Self::Back => "back",
+            Self::Quit => "quit",
+        }
+    }
+
+    fn caption(self) -> &'static str {
+        match self {
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::Confirm => "Confirm",
+            Self::Back => "Back",
+            Self::Quit => "Quit",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Up => &["Up", "k"],
+            Self::Down => &["Down", "j"],
+            Self::Confirm => &["Enter"],
+            Self::Back => &["Esc"],
+            Self::Quit => &["q"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordsAction {
+    Close,
+}
+
+impl Action for RecordsAction {
+    const SECTION: &'static str = "records";
+    const ALL: &'static [Self] = &[Self::Close];
+
+    fn name(self) -> &'static str {
+        "close"
+    }
+
+    fn caption(self) -> &'static str {
+        "Menu"
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        &["Esc", "Enter", "q"]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsAction {
+    Up,
+    Down,
+    Decrease,
+    Increase,
+    Edit,
+    Save,
+    Back,
+}
+
+impl Action for SettingsAction {
+    const SECTION: &'static str = "settings";
+    const ALL: &'static [Self] = &[
+        Self::Up,
+        Self::Down,
+        Self::Decrease,
+        Self::Increase,
+        Self::Edit,
+        Self::Save,
+        Self::Back,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Decrease => "decrease",
+            Self::Increase => "increase",
+            Self::Edit => "edit",
+            Self::Save => "save",
+            Self::Back => "back",
+        }
+    }
+
+    fn caption(self) -> &'static str {
+        match self {
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::Decrease => "Decrease",
+            Self::Increase => "Increase",
+            Self::Edit => "Edit",
+            Self::Save => "Save",
+            Self::Back => "Back",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Up => &["Up", "k"],
+            Self::Down => &["Down", "j"],
+            Self::Decrease => &["Left", "h"],
+            Self::Increase => &["Right", "l"],
+            Self::Edit => &["Enter"],
+            Self::Save => &["s"],
+            Self::Back => &["Esc"],
+        }
+    }
+}
+
+/// Stealth view keys, active on both Quiz and Listening.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StealthAction {
+    Toggle,
+    Boss,
+}
+
+impl Action for StealthAction {
+    const SECTION: &'static str = "stealth";
+    const ALL: &'static [Self] = &[Self::Toggle, Self::Boss];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Toggle => "toggle",
+            Self::Boss => "boss",
+        }
+    }
+
+    fn caption(self) -> &'static str {
+        match self {
+            Self::Toggle => "Stealth",
+            Self::Boss => "Boss",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Toggle => &["F8"],
+            Self::Boss => &["F12"],
+        }
+    }
+}
+
+/// The keys bound to each action of one screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bindings<A: Action> {
+    keys: Vec<(A, Vec<KeyBinding>)>,
+}
+
+impl<A: Action> Default for Bindings<A> {
+    fn default() -> Self {
+        let keys = A::ALL
+            .iter()
+            .map(|&action| {
+                let keys = action
+                    .default_keys()
+                    .iter()
+                    .map(|k| KeyBinding::parse(k).expect("default key parses"))
+                    .collect();
+                (action, keys)
+            })
+            .collect();
+        Self { keys }
+    }
+}
+
+impl<A: Action> Bindings<A> {
+    pub fn action_for(&self, key: &KeyEvent) -> Option<A> {
+        self.keys
+            .iter()
+            .find(|(_, keys)| keys.iter().any(|k| k.matches(key)))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: A) -> &[KeyBinding] {
+        self.keys
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// `Esc/Enter/q` style label for the help line.
+    pub fn label(&self, action: A) -> String {
+        self.keys(action)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    pub fn help(&self, action: A) -> HelpEntry {
+        self.help_as(action, action.caption())
+    }
+
+    /// Help entry with a caption that differs from the action's default
+    /// (Quiz's quit key reads "Skip" on the summary screens).
+    pub fn help_as(&self, action: A, caption: &'static str) -> HelpEntry {
+        HelpEntry::owned(self.label(action), caption)
+    }
+
+    /// One entry for a pair of opposite actions: `↑/↓, k/j`.
+    pub fn help_pair(&self, first: A, second: A, caption: &'static str) -> HelpEntry {
+        let (a, b) = (self.keys(first), self.keys(second));
+        let mut parts: Vec<String> = a.iter().zip(b).map(|(x, y)| format!("{x}/{y}")).collect();
+        let paired = parts.len();
+        parts.extend(
+            a[paired..]
+                .iter()
+                .chain(&b[paired..])
+                .map(ToString::to_string),
+        );
+        HelpEntry::owned(parts.join(", "), caption)
+    }
+
+    fn set(&mut self, action: A, keys: Vec<KeyBinding>) {
+        if let Some(slot) = self.keys.iter_mut().find(|(a, _)| *a == action) {
+            slot.1 = keys;
+        }
+    }
+
+    fn apply(
+        &mut self,
+        overrides: &BTreeMap<String, Keys>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        for (name, keys) in overrides {
+            let action = A::ALL
+                .iter()
+                .copied()
+                .find(|a| a.name() == name)
+                .ok_or_else(|| format!("unknown action '{}.{name}'", A::SECTION))?;
+            let parsed = keys
+                .as_slice()
+                .iter()
+                .map(|k| KeyBinding::parse(k).map_err(|e| format!("{}.{name}: {e}", A::SECTION)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if parsed.is_empty() {
+                return Err(format!("{}.{name}: no keys", A::SECTION).into());
+            }
+            self.set(action, parsed);
+        }
+        Ok(())
+    }
+
+    fn qualified(&self) -> impl Iterator<Item = (String, KeyBinding)> + '_ {
+        self.keys.iter().flat_map(|(action, keys)| {
+            keys.iter()
+                .map(move |k| (format!("{}.{}", A::SECTION, action.name()), *k))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Keymap {
+    pub quiz: Bindings<QuizAction>,
+    pub listen: Bindings<ListenAction>,
+    pub menu: Bindings<MenuAction>,
+    pub records: Bindings<RecordsAction>,
+    pub settings: Bindings<SettingsAction>,
+    pub stealth: Bindings<StealthAction>,
+}
+
+impl Keymap {
+    /// Load `path`. A missing file is the default keymap, not an error.
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        if !Path::new(path).exists() {
+            return Ok(Self::default());
+        }
+        Self::from_yaml(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let file: BTreeMap<String, BTreeMap<String, Keys>> =
+            serde_yaml::from_str::<Option<_>>(yaml)?.unwrap_or_default();
+        let mut keymap = Self::default();
+        for (section, overrides) in &file {
+            match section.as_str() {
+                QuizAction::SECTION => keymap.quiz.apply(overrides)?,
+                ListenAction::SECTION => keymap.listen.apply(overrides)?,
+                MenuAction::SECTION => keymap.menu.apply(overrides)?,
+                RecordsAction::SECTION => keymap.records.apply(overrides)?,
+                SettingsAction::SECTION => keymap.settings.apply(overrides)?,
+                StealthAction::SECTION => keymap.stealth.apply(overrides)?,
+                other => return Err(format!("unknown keymap section '{other}'").into()),
+            }
+        }
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    /// A key may only mean one thing on a screen. Stealth keys are live
+    /// on Quiz and Listening, so they're checked against both.
+    fn check_conflicts(&self) -> Result<(), String> {
+        let groups: [Vec<(String, KeyBinding)>; 5] = [
+            self.quiz
+                .qualified()
+                .chain(self.stealth.qualified())
+                .collect(),
+            self.listen
+                .qualified()
+                .chain(self.stealth.qualified())
+                .collect(),
+            self.menu.qualified().collect(),
+            self.records.qualified().collect(),
+            self.settings.qualified().collect(),
+        ];
+        for group in &groups {
+            for (i, (name, key)) in group.iter().enumerate() {
+                if let Some((other, _)) = group[i + 1..].iter().find(|(o, k)| k == key && o != name)
+                {
+                    return Err(format!("key '{key}' is bound to both {name} and {other}"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One key or a list of keys.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Keys {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            Self::One(key) => std::slice::from_ref(key),
+            Self::Many(keys) => keys,
+        }
+    }
+}
+
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+/// Install the keymap for this process. Only the first call wins.
+pub fn install(keymap: Keymap) {
+    let _ = KEYMAP.set(keymap);
+}
+
+/// The active keymap.
+pub fn keymap() -> &'static Keymap {
+    KEYMAP.get_or_init(Keymap::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        for (text, shown) in [
+            ("Esc", "Esc"),
+            ("bksp", "Bksp"),
+            ("Space", "Space"),
+            ("F12", "F12"),
+            ("up", "↑"),
+            ("q", "q"),
+            ("Ctrl+s", "Ctrl+s"),
+            ("alt+Enter", "Alt+Enter"),
+        ] {
+            assert_eq!(KeyBinding::parse(text).expect(text).to_string(), shown);
+        }
+        assert!(KeyBinding::parse("F13").is_err());
+        assert!(KeyBinding::parse("Hyper").is_err());
+        assert!(KeyBinding::parse("Ctrl+c").is_err());
+    }
+
+    #[test]
+    fn matching_ignores_shift_but_not_ctrl() {
+        let q = KeyBinding::parse("Q").expect("parse");
+        assert!(q.matches(&press(KeyCode::Char('Q'), KeyModifiers::SHIFT)));
+        let ctrl_s = KeyBinding::parse("Ctrl+s").expect("parse");
+        assert!(ctrl_s.matches(&press(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+        assert!(!ctrl_s.matches(&press(KeyCode::Char('s'), KeyModifiers::NONE)));
+        let s = KeyBinding::parse("s").expect("parse");
+        assert!(!s.matches(&press(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn defaults_match_the_original_hard_coded_keys() {
+        let keymap = Keymap::default();
+        let key = |code| press(code, KeyModifiers::NONE);
+        assert_eq!(
+            keymap.quiz.action_for(&key(KeyCode::Tab)),
+            Some(QuizAction::Skip)
+        );
+        assert_eq!(
+            keymap.listen.action_for(&key(KeyCode::Char(' '))),
+            Some(ListenAction::Replay)
+        );
+        assert_eq!(
+            keymap.menu.action_for(&key(KeyCode::Char('k'))),
+            Some(MenuAction::Up)
+        );
+        assert_eq!(
+            keymap.stealth.action_for(&key(KeyCode::F(12))),
+            Some(StealthAction::Boss)
+        );
+        assert_eq!(keymap.quiz.action_for(&key(KeyCode::Char('a'))), None);
+        assert_eq!(keymap.records.label(RecordsAction::Close), "Esc/Enter/q");
+        assert_eq!(
+            keymap.settings.action_for(&key(KeyCode::Right)),
+            Some(SettingsAction::Increase)
+        );
+        assert_eq!(
+            keymap
+                .menu
+                .help_pair(MenuAction::Up, MenuAction::Down, "Select")
+                .key,
+            "↑/↓, k/j"
+        );
+    }
+
+    #[test]
+    fn yaml_overrides_replace_only_listed_actions() {
+        let keymap = Keymap::from_yaml("quiz:\n  skip: F2\nmenu:\n  up: [Up, w]\n").expect("parse");
+        assert_eq!(keymap.quiz.label(QuizAction::Skip), "F2");
+        assert_eq!(keymap.quiz.label(QuizAction::Quit), "Esc");
+        assert_eq!(keymap.menu.label(MenuAction::Up), "↑/w");
+        assert_eq!(
+            keymap
+                .quiz
+                .action_for(&press(KeyCode::Tab, KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(Keymap::from_yaml("").expect("empty"), Keymap::default());
+    }
+
+    #[test]
+    fn invalid_keymaps_are_errors() {
+        assert!(Keymap::from_yaml("game:\n  quit: Esc\n").is_err());
+        assert!(Keymap::from_yaml("quiz:\n  pause: p\n").is_err());
+        assert!(Keymap::from_yaml("quiz:\n  skip: Hyper\n").is_err());
+        assert!(Keymap::from_yaml("quiz:\n  skip: []\n").is_err());
+        // Same key on two actions of one screen.
+        assert!(Keymap::from_yaml("menu:\n  quit: Enter\n").is_err());
+        assert!(Keymap::from_yaml("settings:\n  save: Left\n").is_err());
+        // Stealth keys collide with Quiz / Listening keys too.
+        assert!(Keymap::from_yaml("stealth:\n  toggle: Tab\n").is_err());
+        // …but the same key on different screens is fine.
+        assert!(Keymap::from_yaml("records:\n  close: Tab\n").is_ok());
+    }
+
+    #[test]
+    fn missing_keymap_file_is_the_default() {
+        let keymap = Keymap::load("/nonexistent/type-globe/keymap.yaml").expect("load");
+        assert_eq!(keymap, Keymap::default());
+    }
+}
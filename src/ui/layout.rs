@@ -19,6 +19,12 @@
 //! always-on 1-line `help_line` at the very bottom rendered by the
 //! `ui::HelpLine` component (#18). For rpg mode, a 5-line `log` slot
 //! sits between the input echo and the help line.
+//!
+//! ## Image questions
+//!
+//! `PaneFrame::quiz_with_image` carves an `image` pane off the right of
+//! `main` for questions that carry an `image_path`. The side pane keeps its
+//! fixed width, so the status column doesn't jump between questions.
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
@@ -37,6 +43,9 @@ pub struct PaneFrame {
     pub help_line: Rect,
     /// Hack-and-slash battle log pane. `None` for quiz-style modes.
     pub log: Option<Rect>,
+    /// Picture pane for image questions. `None` unless built with
+    /// [`PaneFrame::quiz_with_image`].
+    pub image: Option<Rect>,
 }
 
 const SIDE_WIDTH: u16 = 24;
@@ -44,6 +53,8 @@ const MAIN_MIN_WIDTH: u16 = 40;
 const INPUT_ECHO_HEIGHT: u16 = 1;
 const HELP_LINE_HEIGHT: u16 = 1;
 const HACK_LOG_HEIGHT: u16 = 5;
+/// Share of the main column given to the picture in image questions.
+const IMAGE_WIDTH_PERCENT: u16 = 40;
 
 impl PaneFrame {
     /// 3-pane layout for quiz-style modes (Quiz, Time Attack 25, Records).
@@ -66,9 +77,34 @@ impl PaneFrame {
             input_echo: outer[1],
             help_line: outer[2],
             log: None,
+            image: None,
         }
     }
 
+    /// Quiz layout with an image pane to the right of the question text.
+    /// `main` keeps at least `MAIN_MIN_WIDTH` columns; on terminals too
+    /// narrow for both, the image pane shrinks first.
+    pub fn quiz_with_image(area: Rect) -> Self {
+        let mut frame = Self::quiz(area);
+        let (main, image) = Self::split_for_image(frame.main);
+        frame.main = main;
+        frame.image = Some(image);
+        frame
+    }
+
+    /// Split a main-column rect into `(text, image)`. Shared with the
+    /// editor preview, which draws a question into an arbitrary rect.
+    pub fn split_for_image(main: Rect) -> (Rect, Rect) {
+        let split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(MAIN_MIN_WIDTH),
+                Constraint::Percentage(IMAGE_WIDTH_PERCENT),
+            ])
+            .split(main);
+        (split[0], split[1])
+    }
+
     /// 4-pane layout for the listening RPG.
     ///
     /// TODO(#11): wire this up once the rpg UI lands; the `#[allow(dead_code)]`
@@ -94,6 +130,7 @@ impl PaneFrame {
             input_echo: outer[1],
             log: Some(outer[2]),
             help_line: outer[3],
+            image: None,
         }
     }
 }
@@ -129,6 +166,20 @@ mod tests {
         assert_eq!(frame.help_line.width, inner_width);
         assert!(frame.input_echo.y < frame.help_line.y);
         assert!(frame.log.is_none());
+        assert!(frame.image.is_none());
+    }
+
+    #[test]
+    fn quiz_with_image_splits_main_and_keeps_side() {
+        let area = Rect::new(0, 0, 120, 30);
+        let plain = PaneFrame::quiz(area);
+        let frame = PaneFrame::quiz_with_image(area);
+        let image = frame.image.expect("image pane");
+        assert_eq!(frame.side, plain.side);
+        assert_eq!(frame.main.x, plain.main.x);
+        assert_eq!(frame.main.width + image.width, plain.main.width);
+        assert_eq!(image.x, frame.main.x + frame.main.width);
+        assert_eq!(image.height, plain.main.height);
     }
 
     #[test]
@@ -9,41 +9,43 @@
 //!
 //! Per `docs/spec.md`, the audio is the only presentation: no text,
 //! no choices. The visible elements are limited to:
-//! - a pulsing `♪` (jiwa pulse) while audio is in flight,
+//! - a pulsing `♪` (jiwa pulse) while audio is in flight, started and
+//!   stopped by the backend's utterance begin / end events (#44),
 //! - the input echo of what the player has typed,
 //! - status placeholders (kind / Floor / Run time placeholder),
 //! - a battle-log pane (used only on the result screen for v0.2.0).
 
-use crate::audio::TtsEngine;
+use crate::audio::{Cue, CueEngine, SpeechBackend, SpeechEvent, Utterance, SLOW_RATE};
 use crate::game::listening::{acceptable_listening_inputs, is_valid_listening_prefix};
 use crate::game::{ListeningSession, SubmissionResult};
 use crate::types::{AnswerKind, Language};
-use crate::ui::{HelpEntry, HelpLine, InputChannel, PaneFrame, RecvOutcome};
+use crate::ui::keymap::{keymap, ListenAction};
+use crate::ui::stealth::{Level, LogEvent, LogScreen, Stealth};
+use crate::ui::theme::theme;
+use crate::ui::{HelpLine, InputChannel, KeyEventSource, PaneFrame, RecvOutcome};
 use crossterm::{
     event::{KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use jiwa::{PulseHandle, PulseOpts, Rgb};
+use jiwa::PulseHandle;
 use ratatui::{
-    backend::CrosstermBackend,
+    backend::{Backend, CrosstermBackend},
     layout::{Alignment, Rect},
-    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Padding, Paragraph},
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
     Frame, Terminal,
 };
 use std::io;
 use std::time::{Duration, Instant};
 
-const STYLE_TITLE: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-const STYLE_NORMAL: Style = Style::new().fg(Color::White);
-const STYLE_DIM: Style = Style::new().fg(Color::DarkGray);
-const STYLE_CORRECT: Style = Style::new().fg(Color::Green).add_modifier(Modifier::BOLD);
-const STYLE_INCORRECT: Style = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
-const STYLE_INPUT_ECHO: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
-const STYLE_LABEL: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
 const INPUT_REJECT_FLASH_MS: u64 = 180;
+/// How long typing waits for the first play to begin before giving up
+/// on it (a backend that accepted the prompt but never made a sound).
+pub(crate) const SPEECH_START_TIMEOUT: Duration = Duration::from_secs(3);
+/// How long the first play may run before typing opens anyway, for a
+/// backend that never reports the end of the prompt.
+const SPEECH_END_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone, PartialEq)]
 enum Phase {
@@ -51,38 +53,67 @@ enum Phase {
     Result,
 }
 
+/// Typing opens once the prompt has been heard in full, so nobody
+/// answers from the first syllable. Replays don't close it again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InputGate {
+    Open,
+    /// The first play was requested at this instant; no audio yet.
+    Waiting(Instant),
+    /// The first play is coming out, since this instant.
+    Hearing(Instant),
+}
+
 pub struct ListenUI {
     session: ListeningSession,
     /// `None` when the caller passed `--no-tts` (#48).
-    tts: Option<TtsEngine>,
+    speech: Option<Box<dyn SpeechBackend>>,
     language: Language,
     phase: Phase,
-    /// `♪` pulse for the active prompt — anchors per-frame color.
-    /// `None` after submit; the result screen reveals the answer text
-    /// instead of pulsing the symbol.
+    /// `♪` pulse while an utterance is playing — anchors per-frame
+    /// color. `None` between utterances, without speech and after
+    /// submit; the symbol then stays still.
     pulse: Option<PulseHandle>,
     started_at: Instant,
-    /// Number of times the player has triggered audio (initial play +
-    /// each Space replay). Per spec there is no penalty, but exposing
-    /// the count for the next UI iteration is cheap and mirrors what
-    /// the RPG run is going to want for telemetry.
+    /// Number of times the prompt has been played (initial play + each
+    /// replay), counted when the audio begins. Without speech each
+    /// replay attempt counts instead. Per spec there is no penalty, but
+    /// exposing the count for the next UI iteration is cheap and mirrors
+    /// what the RPG run is going to want for telemetry.
     plays: u32,
+    gate: InputGate,
     rejected_char: Option<char>,
     reject_flash_until: Option<Instant>,
+    /// Log-viewer disguise and boss key (`ui::stealth`).
+    stealth: Stealth,
+    /// Typing and result cues (#48). `None` unless the caller hands in
+    /// an engine, so tests and `--no-tts` runs stay quiet.
+    cues: Option<CueEngine>,
+    /// Last speech failure, shown in the status pane. Printing it would
+    /// land inside the alternate screen.
+    message: Option<String>,
 }
 
 impl ListenUI {
-    pub fn new(session: ListeningSession, tts: TtsEngine, language: Language) -> Self {
+    pub fn new(
+        session: ListeningSession,
+        speech: Box<dyn SpeechBackend>,
+        language: Language,
+    ) -> Self {
         Self {
             session,
-            tts: Some(tts),
+            speech: Some(speech),
             language,
             phase: Phase::Playing,
-            pulse: Some(PulseHandle::start("♪", PulseOpts::cyan_breath())),
+            pulse: None,
             started_at: Instant::now(),
             plays: 0,
+            gate: InputGate::Open,
             rejected_char: None,
             reject_flash_until: None,
+            stealth: Stealth::new(false),
+            cues: None,
+            message: None,
         }
     }
 
@@ -91,17 +122,34 @@ impl ListenUI {
     pub fn new_without_tts(session: ListeningSession, language: Language) -> Self {
         Self {
             session,
-            tts: None,
+            speech: None,
             language,
             phase: Phase::Playing,
-            pulse: Some(PulseHandle::start("♪", PulseOpts::cyan_breath())),
+            pulse: None,
             started_at: Instant::now(),
             plays: 0,
+            gate: InputGate::Open,
             rejected_char: None,
             reject_flash_until: None,
+            stealth: Stealth::new(false),
+            cues: None,
+            message: None,
         }
     }
 
+    /// Start in the stealth log view (`--stealth`). The toggle key still
+    /// switches back.
+    pub fn with_stealth(mut self, enabled: bool) -> Self {
+        self.stealth = Stealth::new(enabled);
+        self
+    }
+
+    /// Play keystroke, mistype and result cues through `cues`.
+    pub fn with_cues(mut self, cues: Option<CueEngine>) -> Self {
+        self.cues = cues;
+        self
+    }
+
     pub fn run(&mut self) -> Result<Option<SubmissionResult>, Box<dyn std::error::Error>> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
@@ -109,38 +157,52 @@ impl ListenUI {
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
+        let input = InputChannel::spawn();
+        let result = self.run_session(&mut terminal, &input);
+        drop(input);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        result
+    }
+
+    /// Speak, play and stop, on any terminal backend and key source so
+    /// tests can drive a whole prompt headless (#45).
+    fn run_session<B: Backend, S: KeyEventSource>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        input: &S,
+    ) -> Result<Option<SubmissionResult>, Box<dyn std::error::Error>> {
         // Speak the prompt once on entry. Failure here is non-fatal —
         // the player can still try Space-replay, and the result screen
         // works even if no audio came out (helps debug TTS issues).
-        if let Some(tts) = self.tts.as_mut() {
-            if let Err(err) = tts.speak(&self.session.prompt().text_reading, &self.language) {
-                eprintln!("warning: initial TTS speak failed: {err}");
-            } else {
-                self.plays += 1;
+        if self.speech.is_some() {
+            match self.speak_prompt(1.0) {
+                Ok(()) => self.gate = InputGate::Waiting(Instant::now()),
+                Err(err) => self.message = Some(format!("TTS failed: {err}")),
             }
         }
 
-        let result = self.run_app(&mut terminal);
+        let result = self.run_app(terminal, input);
 
         // Stop any in-flight utterance so the terminal returns silently.
-        if let Some(tts) = self.tts.as_mut() {
-            let _ = tts.stop();
+        if let Some(speech) = self.speech.as_mut() {
+            let _ = speech.stop();
         }
-        disable_raw_mode()?;
-        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-        terminal.show_cursor()?;
-
         result
     }
 
-    fn run_app(
+    fn run_app<B: Backend, S: KeyEventSource>(
         &mut self,
-        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        terminal: &mut Terminal<B>,
+        input: &S,
     ) -> Result<Option<SubmissionResult>, Box<dyn std::error::Error>> {
         const REDRAW: Duration = Duration::from_millis(30);
-        let input = InputChannel::spawn();
 
         loop {
+            self.poll_speech(Instant::now());
             terminal.draw(|f| self.ui(f))?;
             match input.recv_until(REDRAW) {
                 RecvOutcome::Key(key) => {
@@ -156,68 +218,157 @@ impl ListenUI {
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> bool {
-        if matches!(key.code, KeyCode::Esc) {
+        let action = keymap().listen.action_for(&key);
+        if action == Some(ListenAction::Quit) {
             return true;
         }
         if matches!(key.code, KeyCode::Char('c')) && key.modifiers.contains(KeyModifiers::CONTROL) {
             return true;
         }
+        if self.stealth.handle_key(&key) {
+            return false;
+        }
 
         match self.phase {
-            Phase::Result => return self.handle_key_result(key),
+            Phase::Result => return action == Some(ListenAction::Confirm),
             Phase::Playing => {}
         }
 
-        match key.code {
+        match (action, key.code) {
             // Per `docs/spec.md`: `[Space] Replay sound`. The spec
             // doesn't reserve another key for "literal space inside a
             // phrase / sentence answer", so v0.2.0 foundation ships
             // word-only practice reliably; phrase / sentence input
             // mapping is revisited as part of the run-loop work in
             // #32-#37.
-            KeyCode::Char(' ')
-                if !key
-                    .modifiers
-                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
-            {
-                self.replay();
+            (Some(ListenAction::Replay), _) => self.replay(),
+            (Some(ListenAction::SlowReplay), _) => self.replay_at(SLOW_RATE),
+            (Some(ListenAction::Mute), _) => {
+                if let Some(engine) = self.cues.as_mut() {
+                    engine.toggle_mute();
+                }
             }
-            KeyCode::Enter => {}
-            KeyCode::Backspace => {
+            (Some(ListenAction::Erase), _) => {
                 self.session.pop_char();
                 self.clear_reject_flash();
             }
-            KeyCode::Char(c)
+            (None, KeyCode::Char(c))
                 if !key
                     .modifiers
                     .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
             {
-                self.handle_playing_char(c);
+                if self.gate == InputGate::Open {
+                    self.handle_playing_char(c);
+                } else {
+                    self.play_cue(Cue::Mistype);
+                    self.note_rejected_char(c);
+                }
             }
             _ => {}
         }
         false
     }
 
-    fn handle_key_result(&mut self, key: KeyEvent) -> bool {
-        matches!(key.code, KeyCode::Enter)
+    /// Say the current prompt through whichever backend takes it.
+    /// A no-op without speech.
+    fn speak_prompt(&mut self, rate: f32) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(speech) = self.speech.as_mut() else {
+            return Ok(());
+        };
+        let prompt = self.session.prompt();
+        speech.speak(&Utterance {
+            id: &prompt.id,
+            text: &prompt.text_reading,
+            language: &self.language,
+            rate,
+        })
     }
 
     fn replay(&mut self) {
-        if let Some(tts) = self.tts.as_mut() {
-            if let Err(err) = tts.speak(&self.session.prompt().text_reading, &self.language) {
-                eprintln!("warning: TTS replay failed: {err}");
-                return;
+        self.replay_at(1.0);
+    }
+
+    /// Say the prompt again at `rate` (1.0 normal, [`SLOW_RATE`] slow).
+    fn replay_at(&mut self, rate: f32) {
+        if let Err(err) = self.speak_prompt(rate) {
+            // A chain stops the old utterance before trying the new one,
+            // so its `End` never comes: open up rather than wait for it.
+            self.message = Some(format!("TTS replay failed: {err}"));
+            self.gate = InputGate::Open;
+            self.pulse = None;
+            if let Some(engine) = self.cues.as_mut() {
+                engine.set_ducked(false);
+            }
+            return;
+        }
+        self.message = None;
+        // With speech the play is counted when it begins; without, no
+        // event will come, so the attempt itself counts.
+        if self.speech.is_none() {
+            self.plays += 1;
+        }
+    }
+
+    /// Drain the backend's utterance events and give up on a first play
+    /// that never started or never reported its end.
+    fn poll_speech(&mut self, now: Instant) {
+        let events = match self.speech.as_mut() {
+            Some(speech) => speech.poll_events(),
+            None => Vec::new(),
+        };
+        for event in events {
+            self.on_speech_event(event, now);
+        }
+        let timeout = match self.gate {
+            InputGate::Waiting(since) => Some((since, SPEECH_START_TIMEOUT)),
+            InputGate::Hearing(since) => Some((since, SPEECH_END_TIMEOUT)),
+            InputGate::Open => None,
+        };
+        if let Some((since, limit)) = timeout {
+            if now.saturating_duration_since(since) >= limit {
+                self.gate = InputGate::Open;
+                self.pulse = None;
+            }
+        }
+    }
+
+    fn on_speech_event(&mut self, event: SpeechEvent, now: Instant) {
+        // Duck the clicks under the prompt, in any phase, so a late
+        // `End` still lifts it.
+        if let Some(engine) = self.cues.as_mut() {
+            engine.set_ducked(event == SpeechEvent::Begin);
+        }
+        if self.phase != Phase::Playing {
+            return;
+        }
+        match event {
+            SpeechEvent::Begin => {
+                self.plays += 1;
+                // A fresh pulse per utterance so the breathing anchors
+                // to the audio that just started.
+                self.pulse = Some(PulseHandle::start("♪", theme().pulse_opts()));
+                if matches!(self.gate, InputGate::Waiting(_)) {
+                    self.gate = InputGate::Hearing(now);
+                }
+            }
+            SpeechEvent::End => {
+                self.pulse = None;
+                self.gate = InputGate::Open;
+                self.session.audio_finished(now);
             }
         }
-        // plays counts replay attempts regardless of TTS availability.
-        self.plays += 1;
-        // Restart the visual pulse on each replay so the breathing
-        // anchors to the new utterance.
-        self.pulse = Some(PulseHandle::start("♪", PulseOpts::cyan_breath()));
     }
 
     fn ui(&mut self, f: &mut Frame) {
+        if self.stealth.is_boss() {
+            self.stealth.render_boss(f, f.area());
+            return;
+        }
+        if self.stealth.is_log() {
+            let screen = self.stealth_screen();
+            self.stealth.render_log(f, f.area(), &screen);
+            return;
+        }
         let frame = PaneFrame::rpg(f.area());
         self.render_main_pane(f, frame.main);
         self.render_status_pane(f, frame.side);
@@ -228,6 +379,69 @@ impl ListenUI {
         self.render_help_line(f, frame.help_line);
     }
 
+    /// Stealth log view: the audio stream status while playing, the
+    /// verification result afterwards. The expected answer only appears
+    /// once the normal view would show it too (result phase).
+    fn stealth_screen(&self) -> LogScreen {
+        let mut screen = LogScreen {
+            status: "データ監視中".to_string(),
+            quit_key: keymap().listen.label(ListenAction::Quit),
+            ..LogScreen::default()
+        };
+        match self.phase {
+            Phase::Playing => {
+                let (stream, status) = match &self.speech {
+                    Some(speech) if speech.is_speaking() => (speech.name(), "playing"),
+                    Some(speech) => (speech.name(), "idle"),
+                    None => ("none", "muted"),
+                };
+                screen.events.push(LogEvent::new(
+                    Level::Info,
+                    "audio",
+                    format!("stream={stream}0 status={status} plays={}", self.plays),
+                ));
+                if self.reject_flash_is_active() {
+                    screen.events.push(LogEvent::new(
+                        Level::Warn,
+                        "filter",
+                        format!("no route matches {:?}", self.rejected_char.unwrap_or(' ')),
+                    ));
+                }
+                if let Some(message) = &self.message {
+                    screen
+                        .events
+                        .push(LogEvent::new(Level::Warn, "audio", message.clone()));
+                }
+                screen.prompt = self.session.input().to_string();
+            }
+            Phase::Result => {
+                if let Some(result) = self.session.result() {
+                    let (level, verdict) = if result.is_correct {
+                        (Level::Info, "ok")
+                    } else {
+                        (Level::Error, "mismatch")
+                    };
+                    screen.events.push(LogEvent::new(
+                        level,
+                        "verify",
+                        format!(
+                            "{verdict} expected={:?} got={:?} latency={}",
+                            result.expected,
+                            self.session.input(),
+                            reaction_label(result.reaction)
+                        ),
+                    ));
+                }
+                screen.events.push(LogEvent::new(
+                    Level::Info,
+                    "verify",
+                    "Enter: return to menu",
+                ));
+            }
+        }
+        screen
+    }
+
     fn render_main_pane(&self, f: &mut Frame, area: Rect) {
         let title_text = match self.phase {
             Phase::Playing => "type-globe - Listening",
@@ -244,7 +458,7 @@ impl ListenUI {
             .block(
                 Block::default()
                     .title(format!(" {title_text} "))
-                    .title_style(STYLE_TITLE)
+                    .title_style(theme().title())
                     .borders(Borders::ALL)
                     .padding(Padding::uniform(1)),
             );
@@ -258,21 +472,24 @@ impl ListenUI {
             let frame = pulse.snapshot(Instant::now());
             (frame.text, frame.color)
         } else {
-            ("♪".to_string(), Rgb(80, 200, 255))
+            ("♪".to_string(), theme().pulse.to)
         };
-        let Rgb(r, g, b) = color;
-        let symbol_span = Span::styled(symbol, Style::new().fg(Color::Rgb(r, g, b)));
+        let symbol_span = Span::styled(symbol, theme().frame_style(theme().pulse, color));
 
         vec![
             Line::from(""),
             Line::from(""),
             Line::from(symbol_span),
             Line::from(""),
-            Line::from(Span::styled("Listening...", STYLE_NORMAL)),
+            Line::from(Span::styled("Listening...", theme().text())),
             Line::from(""),
             Line::from(Span::styled(
-                "(audio only — exact match auto-confirms)",
-                STYLE_DIM,
+                if self.gate == InputGate::Open {
+                    "(audio only — exact match auto-confirms)"
+                } else {
+                    "(typing opens when the prompt ends)"
+                },
+                theme().dim(),
             )),
         ]
     }
@@ -282,26 +499,30 @@ impl ListenUI {
             return vec![Line::from("(no result)")];
         };
         let (verdict_text, verdict_style) = if result.is_correct {
-            ("Correct!".to_string(), STYLE_CORRECT)
+            ("Correct!".to_string(), theme().correct())
         } else {
-            ("Wrong.".to_string(), STYLE_INCORRECT)
+            ("Wrong.".to_string(), theme().incorrect())
         };
         vec![
             Line::from(""),
             Line::from(Span::styled(verdict_text, verdict_style)),
             Line::from(""),
             Line::from(vec![
-                Span::styled("Expected: ", STYLE_LABEL),
-                Span::styled(result.expected.clone(), STYLE_NORMAL),
+                Span::styled("Expected: ", theme().label()),
+                Span::styled(result.expected.clone(), theme().text()),
+            ]),
+            Line::from(vec![
+                Span::styled("You typed: ", theme().label()),
+                Span::styled(self.session.input().to_string(), theme().input_echo()),
             ]),
             Line::from(vec![
-                Span::styled("You typed: ", STYLE_LABEL),
-                Span::styled(self.session.input().to_string(), STYLE_INPUT_ECHO),
+                Span::styled("Reaction: ", theme().label()),
+                Span::styled(reaction_label(result.reaction), theme().text()),
             ]),
             Line::from(""),
             Line::from(Span::styled(
                 "Press Enter or Esc to return to the menu.",
-                STYLE_DIM,
+                theme().dim(),
             )),
         ]
     }
@@ -315,20 +536,30 @@ impl ListenUI {
             AnswerKind::Phrase => "phrase",
             AnswerKind::Sentence => "sentence",
         };
-        let lines = vec![
-            Line::from(Span::styled("Practice", STYLE_LABEL)),
-            Line::from(Span::styled("(RPG run: #32+)", STYLE_DIM)),
+        let mut lines = vec![
+            Line::from(Span::styled("Practice", theme().label())),
+            Line::from(Span::styled("(RPG run: #32+)", theme().dim())),
             Line::from(""),
             Line::from(format!("Kind   : {kind}")),
             Line::from(format!("Plays  : {}", self.plays)),
             Line::from(format!("Time   : {mins}:{secs:02}")),
         ];
-        let para = Paragraph::new(lines).alignment(Alignment::Left).block(
-            Block::default()
-                .title(" Status ")
-                .borders(Borders::ALL)
-                .padding(Padding::uniform(1)),
-        );
+        if let Some(message) = &self.message {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                message.clone(),
+                theme().incorrect(),
+            )));
+        }
+        let para = Paragraph::new(lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title(" Status ")
+                    .borders(Borders::ALL)
+                    .padding(Padding::uniform(1)),
+            );
         f.render_widget(para, area);
     }
 
@@ -346,32 +577,32 @@ impl ListenUI {
     fn render_log_pane(&self, f: &mut Frame, area: Rect) {
         let lines: Vec<Line<'static>> = match self.phase {
             Phase::Playing => vec![
-                Line::from(Span::styled("(no events)", STYLE_DIM)),
+                Line::from(Span::styled("(no events)", theme().dim())),
                 Line::from(Span::styled(
                     "Battle log fills in once #32-#37 land.",
-                    STYLE_DIM,
+                    theme().dim(),
                 )),
             ],
             Phase::Result => match self.session.result() {
                 Some(r) if r.is_correct => {
                     vec![
-                        Line::from(Span::styled("▸ Hit!", STYLE_CORRECT)),
+                        Line::from(Span::styled("▸ Hit!", theme().correct())),
                         Line::from(Span::styled(
                             format!("▸ Plays this prompt: {}", self.plays),
-                            STYLE_DIM,
+                            theme().dim(),
                         )),
                     ]
                 }
                 Some(_) => {
                     vec![
-                        Line::from(Span::styled("▸ Missed.", STYLE_INCORRECT)),
+                        Line::from(Span::styled("▸ Missed.", theme().incorrect())),
                         Line::from(Span::styled(
                             format!("▸ Plays this prompt: {}", self.plays),
-                            STYLE_DIM,
+                            theme().dim(),
                         )),
                     ]
                 }
-                None => vec![Line::from(Span::styled("(no result)", STYLE_DIM))],
+                None => vec![Line::from(Span::styled("(no result)", theme().dim()))],
             },
         };
         let para = Paragraph::new(lines)
@@ -381,13 +612,26 @@ impl ListenUI {
     }
 
     fn render_help_line(&self, f: &mut Frame, area: Rect) {
+        let keys = &keymap().listen;
         let help = match self.phase {
-            Phase::Playing => HelpLine::new(vec![
-                HelpEntry::new("Esc", "Quit"),
-                HelpEntry::new("Space", "Replay"),
-                HelpEntry::new("Bksp", "Erase"),
-            ]),
-            Phase::Result => HelpLine::new(vec![HelpEntry::new("Enter", "Menu")]),
+            Phase::Playing => {
+                let mut entries = vec![
+                    keys.help(ListenAction::Quit),
+                    keys.help(ListenAction::Replay),
+                    keys.help(ListenAction::SlowReplay),
+                    keys.help(ListenAction::Erase),
+                ];
+                match self.cues.as_ref() {
+                    Some(engine) if engine.is_muted() => {
+                        entries.push(keys.help_as(ListenAction::Mute, "Unmute"))
+                    }
+                    Some(_) => entries.push(keys.help(ListenAction::Mute)),
+                    None => {}
+                }
+                entries.push(Stealth::help_entry());
+                HelpLine::new(entries)
+            }
+            Phase::Result => HelpLine::new(vec![keys.help(ListenAction::Confirm)]),
         };
         help.render(f, area);
     }
@@ -403,20 +647,20 @@ impl ListenUI {
             Span::styled(
                 prompt.to_string(),
                 if flash_active {
-                    STYLE_INCORRECT
+                    theme().incorrect()
                 } else {
-                    STYLE_DIM
+                    theme().dim()
                 },
             ),
-            Span::styled(self.session.input().to_string(), STYLE_CORRECT),
+            Span::styled(self.session.input().to_string(), theme().correct()),
         ];
         if flash_active {
             if let Some(c) = self.rejected_char {
-                spans.push(Span::styled(c.to_string(), STYLE_INCORRECT));
+                spans.push(Span::styled(c.to_string(), theme().incorrect()));
             }
-            spans.push(Span::styled("_", STYLE_INCORRECT));
+            spans.push(Span::styled("_", theme().incorrect()));
         } else {
-            spans.push(Span::styled("_", STYLE_INPUT_ECHO));
+            spans.push(Span::styled("_", theme().input_echo()));
         }
         Line::from(spans)
     }
@@ -429,24 +673,41 @@ impl ListenUI {
             &attempted,
             &self.session.prompt().text_reading,
         ) {
+            self.play_cue(Cue::Mistype);
             self.note_rejected_char(c);
             return;
         }
 
         self.session.push_char(c);
         self.clear_reject_flash();
+        self.play_cue(Cue::Keystroke);
 
         let typed = self.session.input().to_lowercase();
         if acceptable_listening_inputs(&self.language, &self.session.prompt().text_reading)
             .iter()
             .any(|candidate| candidate == &typed)
         {
-            self.session.submit();
+            let correct = self.session.submit().is_correct;
             self.pulse = None;
             self.phase = Phase::Result;
-            if let Some(tts) = self.tts.as_mut() {
-                let _ = tts.stop();
+            if let Some(speech) = self.speech.as_mut() {
+                let _ = speech.stop();
             }
+            // The prompt was cut off, so the result cue plays in full.
+            if let Some(engine) = self.cues.as_mut() {
+                engine.set_ducked(false);
+            }
+            self.play_cue(if correct {
+                Cue::EnemyDefeat
+            } else {
+                Cue::Wrong
+            });
+        }
+    }
+
+    fn play_cue(&self, cue: Cue) {
+        if let Some(engine) = self.cues.as_ref() {
+            engine.play(cue);
         }
     }
 
@@ -478,13 +739,19 @@ impl ListenUI {
     }
 }
 
+/// `1.24s`, or `-` when there was no finished utterance to time from.
+fn reaction_label(reaction: Option<Duration>) -> String {
+    reaction.map_or_else(|| "-".into(), |d| format!("{:.2}s", d.as_secs_f32()))
+}
+
 /// Build a one-line message for callers that want to surface a TTS
 /// init failure to the player without crashing the run. Shared so the
 /// menu and any future entry points format it consistently.
-pub fn tts_unavailable_message(err: &dyn std::error::Error) -> String {
+pub fn tts_unavailable_message(err: &dyn std::fmt::Display) -> String {
     format!(
         "Listening mode is unavailable on this system: {err}\n\
-         (On Linux, install and start `speech-dispatcher`.)\n\
+         (On Linux, install and start `speech-dispatcher`, install `espeak-ng`,\n\
+         or add pre-rendered audio; see `speech:` in settings.yaml.)\n\
          Press Enter to return to the menu."
     )
 }
@@ -496,8 +763,12 @@ pub fn tts_unavailable_message(err: &dyn std::error::Error) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::audio::mock::MockSpeech;
     use crate::game::ListeningSession;
     use crate::types::{AnswerKind, Language, ListeningPrompt};
+    use ratatui::backend::TestBackend;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
 
     fn stub_prompt() -> ListeningPrompt {
         ListeningPrompt {
@@ -512,12 +783,100 @@ mod tests {
         ListeningSession::new(stub_prompt(), Language::English)
     }
 
-    // --- TC-12: new_without_tts → tts field is None ---
+    /// Keys handed to the run loop one per frame. Running out reads as
+    /// a disconnected terminal, which ends the loop.
+    struct Keys(RefCell<VecDeque<KeyEvent>>);
+
+    impl KeyEventSource for Keys {
+        fn recv_until(&self, _timeout: Duration) -> RecvOutcome {
+            match self.0.borrow_mut().pop_front() {
+                Some(key) => RecvOutcome::Key(key),
+                None => RecvOutcome::Disconnected,
+            }
+        }
+    }
+
+    fn keys(script: &str, then: &[KeyCode]) -> Keys {
+        let codes = script
+            .chars()
+            .map(KeyCode::Char)
+            .chain(then.iter().copied());
+        Keys(RefCell::new(
+            codes
+                .map(|code| KeyEvent::new(code, KeyModifiers::NONE))
+                .collect(),
+        ))
+    }
+
+    fn run_headless(ui: &mut ListenUI, keys: &Keys) -> Option<SubmissionResult> {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("terminal");
+        ui.run_session(&mut terminal, keys).expect("run")
+    }
+
+    #[test]
+    fn run_speaks_the_prompt_and_opens_typing_when_it_ends() {
+        let (mock, log) = MockSpeech::new();
+        let mut ui = ListenUI::new(stub_session(), Box::new(mock), Language::English);
+        // The first `a` lands while the prompt is still playing.
+        let result = run_headless(&mut ui, &keys("aapple", &[KeyCode::Enter])).expect("answered");
+
+        assert!(result.is_correct);
+        assert!(result.reaction.is_some());
+        let spoken = log.spoken();
+        assert_eq!(spoken.len(), 1);
+        assert_eq!(
+            (spoken[0].id.as_str(), spoken[0].language.as_str()),
+            ("test", "en")
+        );
+        assert_eq!(spoken[0].rate, 1.0);
+        assert_eq!(ui.plays, 1);
+        assert!(log.stops() >= 1, "the run stops speech on the way out");
+    }
+
+    #[test]
+    fn replays_are_recorded_with_their_rate() {
+        let (mock, log) = MockSpeech::new();
+        let mock = mock.with_length(Some(1));
+        let mut ui = ListenUI::new(stub_session(), Box::new(mock), Language::English);
+        let script = keys("", &[KeyCode::Char(' '), KeyCode::Tab]);
+        assert_eq!(run_headless(&mut ui, &script), None);
+
+        let spoken = log.spoken();
+        let rates: Vec<f32> = spoken.iter().map(|s| s.rate).collect();
+        assert_eq!(rates, [1.0, 1.0, SLOW_RATE]);
+        assert!(spoken.windows(2).all(|w| w[0].at <= w[1].at));
+        assert_eq!(ui.plays, 3);
+    }
+
+    #[test]
+    fn a_failing_backend_leaves_the_prompt_playable() {
+        let (mock, log) = MockSpeech::new();
+        log.set_failing(true);
+        let mut ui = ListenUI::new(stub_session(), Box::new(mock), Language::English);
+        let result = run_headless(&mut ui, &keys("apple", &[KeyCode::Enter])).expect("answered");
+
+        assert!(result.is_correct);
+        assert_eq!(result.reaction, None);
+        assert!(log.spoken().is_empty());
+        assert_eq!(ui.plays, 0);
+    }
+
+    #[test]
+    fn quitting_mid_prompt_stops_speech_without_a_result() {
+        let (mock, log) = MockSpeech::new();
+        let mock = mock.with_length(None);
+        let mut ui = ListenUI::new(stub_session(), Box::new(mock), Language::Japanese);
+        assert_eq!(run_headless(&mut ui, &keys("", &[KeyCode::Esc])), None);
+        assert_eq!(log.spoken()[0].language, "ja");
+        assert!(log.stops() >= 1);
+    }
+
+    // --- TC-12: new_without_tts → speech field is None ---
     #[test]
     fn new_without_tts_has_no_tts_engine() {
         let ui = ListenUI::new_without_tts(stub_session(), Language::English);
         assert!(
-            ui.tts.is_none(),
+            ui.speech.is_none(),
             "tts should be None when built without TTS"
         );
     }
@@ -527,11 +886,11 @@ mod tests {
     fn new_with_tts_engine_has_some_tts() {
         // We cannot guarantee TtsEngine::new() succeeds in all CI environments,
         // so we skip this test if TTS initialisation fails.
-        match crate::audio::TtsEngine::new() {
+        match crate::audio::tts::TtsEngine::new() {
             Ok(tts) => {
-                let ui = ListenUI::new(stub_session(), tts, Language::English);
+                let ui = ListenUI::new(stub_session(), Box::new(tts), Language::English);
                 assert!(
-                    ui.tts.is_some(),
+                    ui.speech.is_some(),
                     "tts should be Some when built with a TTS engine"
                 );
             }
@@ -555,4 +914,89 @@ mod tests {
         ui.replay();
         assert_eq!(ui.plays, 2, "plays should be +2 after second replay()");
     }
+
+    #[test]
+    fn slow_replay_key_counts_as_a_play() {
+        let mut ui = ListenUI::new_without_tts(stub_session(), Language::English);
+        assert!(!ui.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE)));
+        assert_eq!(ui.plays, 1);
+        assert!(ui.session.input().is_empty(), "Tab is not typed");
+    }
+
+    fn type_str(ui: &mut ListenUI, text: &str) {
+        for c in text.chars() {
+            ui.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn utterance_events_drive_pulse_gate_and_plays() {
+        let (mock, log) = MockSpeech::new();
+        let mock = mock.with_length(None);
+        let mut ui = ListenUI::new(stub_session(), Box::new(mock), Language::English);
+        ui.speak_prompt(1.0).expect("speak");
+        ui.gate = InputGate::Waiting(Instant::now());
+
+        type_str(&mut ui, "a");
+        assert_eq!(ui.session.input(), "", "typing waits for the prompt");
+        ui.poll_speech(Instant::now());
+        assert!(matches!(ui.gate, InputGate::Hearing(_)));
+        assert!(ui.pulse.is_some());
+        assert_eq!(ui.plays, 1);
+
+        log.finish();
+        ui.poll_speech(Instant::now());
+        assert_eq!(ui.gate, InputGate::Open);
+        assert!(ui.pulse.is_none());
+        type_str(&mut ui, "apple");
+        let result = ui.session.result().expect("auto-confirmed");
+        assert!(result.reaction.is_some());
+    }
+
+    #[test]
+    fn a_first_play_that_never_starts_opens_the_gate() {
+        let start = Instant::now();
+        let mut ui = ListenUI::new_without_tts(stub_session(), Language::English);
+        ui.gate = InputGate::Waiting(start);
+        ui.poll_speech(start + Duration::from_secs(1));
+        assert!(matches!(ui.gate, InputGate::Waiting(_)));
+        ui.poll_speech(start + SPEECH_START_TIMEOUT);
+        assert_eq!(ui.gate, InputGate::Open);
+        assert_eq!(ui.plays, 0);
+    }
+
+    #[test]
+    fn a_failed_replay_while_hearing_opens_the_gate() {
+        let (mock, log) = MockSpeech::new();
+        let mock = mock.with_length(None);
+        let mut ui = ListenUI::new(stub_session(), Box::new(mock), Language::English);
+        ui.speak_prompt(1.0).expect("speak");
+        ui.gate = InputGate::Waiting(Instant::now());
+        ui.poll_speech(Instant::now());
+        assert!(matches!(ui.gate, InputGate::Hearing(_)));
+
+        log.set_failing(true);
+        ui.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!(ui.gate, InputGate::Open);
+        assert!(ui.pulse.is_none());
+        assert!(ui.message.as_deref().is_some_and(|m| m.contains("failed")));
+        type_str(&mut ui, "apple");
+        assert!(ui.session.result().is_some(), "typing is accepted");
+    }
+
+    #[test]
+    fn a_play_that_never_ends_opens_the_gate() {
+        let start = Instant::now();
+        let (mock, _log) = MockSpeech::new();
+        let mut ui = ListenUI::new(
+            stub_session(),
+            Box::new(mock.with_length(None)),
+            Language::English,
+        );
+        ui.gate = InputGate::Hearing(start);
+        ui.poll_speech(start + SPEECH_START_TIMEOUT);
+        assert!(matches!(ui.gate, InputGate::Hearing(_)));
+        ui.poll_speech(start + SPEECH_END_TIMEOUT);
+        assert_eq!(ui.gate, InputGate::Open);
+    }
 }
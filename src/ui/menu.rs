@@ -1,15 +1,16 @@
 use crate::types::{GameMode, Language};
-use crate::ui::{HelpEntry, HelpLine};
+use crate::ui::keymap::{keymap, MenuAction};
+use crate::ui::theme::theme;
+use crate::ui::HelpLine;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent},
+    event::{self, Event, KeyEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use jiwa::{lerp_rgb, Rgb};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph},
     Frame, Terminal,
@@ -17,10 +18,6 @@ use ratatui::{
 use std::io;
 use std::time::{Duration, Instant};
 
-const STYLE_TITLE: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-const STYLE_SELECTED: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
-const STYLE_NORMAL: Style = Style::new();
-
 /// Cadence used by the menu while a description is fading in. Short enough
 /// that the lerp reads as continuous motion; the rest of the time the
 /// blocking event read takes over so the menu is otherwise idle.
@@ -29,8 +26,6 @@ const REDRAW_TICK: Duration = Duration::from_millis(30);
 /// final color after a selection change (Issue #72 follow-up: jiwa across
 /// the whole UI).
 const DETAIL_FADE_MS: u64 = 320;
-const DETAIL_FADE_FROM: Rgb = Rgb(60, 60, 60);
-const DETAIL_FADE_TO: Rgb = Rgb(220, 220, 220);
 
 struct LanguageOption {
     label: &'static str,
@@ -59,7 +54,7 @@ const LANGUAGE_OPTIONS: [LanguageOption; 2] = [
     },
 ];
 
-const MODE_OPTIONS: [ModeOption; 4] = [
+const MODE_OPTIONS: [ModeOption; 6] = [
     ModeOption {
         label: "Quiz",
         description: [
@@ -67,6 +62,13 @@ const MODE_OPTIONS: [ModeOption; 4] = [
             "基本プレイ。問題に答えて type-globe の土台を鍛えます。",
         ],
     },
+    ModeOption {
+        label: "Practice",
+        description: [
+            "Quiz without Records: skipped questions come back, and misses are explained afterwards.",
+            "記録なしのクイズ。スキップした問題は再出題され、間違いは後から正解つきで解説されます。",
+        ],
+    },
     ModeOption {
         label: "Time Attack 25",
         description: [
@@ -88,6 +90,13 @@ const MODE_OPTIONS: [ModeOption; 4] = [
             "3 モードぶんの自己ベスト記録を横断して見る画面です。",
         ],
     },
+    ModeOption {
+        label: "Settings",
+        description: [
+            "Default language, run length, sound, reveal speed, theme and TTS voice. Saved to settings.yaml.",
+            "既定の言語・出題数・効果音・表示速度・テーマ・読み上げ音声を設定し、settings.yaml に保存します。",
+        ],
+    },
 ];
 
 pub struct MenuUI {
@@ -96,8 +105,8 @@ pub struct MenuUI {
     step: MenuStep,
     should_quit: bool,
     /// Wall-clock instant of the last selection or step change. The
-    /// detail panel fades in from `DETAIL_FADE_FROM` to `DETAIL_FADE_TO`
-    /// over `DETAIL_FADE_MS` starting from this instant, so each new
+    /// detail panel runs the theme's `menu_detail_fade` over
+    /// `DETAIL_FADE_MS` starting from this instant, so each new
     /// selection's description shows up with a soft jiwa rather than
     /// snapping into place.
     selection_changed_at: Instant,
@@ -136,6 +145,15 @@ impl MenuUI {
         result
     }
 
+    /// Start on the mode list with `language` preselected — the
+    /// `language` from `settings.yaml`. `None` asks first as before.
+    pub fn with_language(mut self, language: Option<Language>) -> Self {
+        if let Some(language) = language {
+            self.return_to_mode_selection(language);
+        }
+        self
+    }
+
     pub fn return_to_mode_selection(&mut self, language: Language) {
         self.selected_language = match language {
             Language::Japanese => 0,
@@ -179,17 +197,15 @@ impl MenuUI {
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> Option<(Language, GameMode)> {
-        const MODE_COUNT: usize = 4;
-
         let prev_language = self.selected_language;
         let prev_mode = self.selected_mode;
         let prev_step = self.step.clone();
 
-        match key.code {
-            KeyCode::Char('q') => {
+        match keymap().menu.action_for(&key) {
+            Some(MenuAction::Quit) => {
                 self.should_quit = true;
             }
-            KeyCode::Up | KeyCode::Char('k') => match self.step {
+            Some(MenuAction::Up) => match self.step {
                 MenuStep::LanguageSelection => {
                     self.selected_language = self.selected_language.saturating_sub(1);
                 }
@@ -197,19 +213,19 @@ impl MenuUI {
                     self.selected_mode = self.selected_mode.saturating_sub(1);
                 }
             },
-            KeyCode::Down | KeyCode::Char('j') => match self.step {
+            Some(MenuAction::Down) => match self.step {
                 MenuStep::LanguageSelection => {
                     if self.selected_language < 1 {
                         self.selected_language += 1;
                     }
                 }
                 MenuStep::ModeSelection => {
-                    if self.selected_mode + 1 < MODE_COUNT {
+                    if self.selected_mode + 1 < MODE_OPTIONS.len() {
                         self.selected_mode += 1;
                     }
                 }
             },
-            KeyCode::Enter => match self.step {
+            Some(MenuAction::Confirm) => match self.step {
                 MenuStep::LanguageSelection => {
                     self.step = MenuStep::ModeSelection;
                 }
@@ -221,15 +237,17 @@ impl MenuUI {
                     };
                     let mode = match self.selected_mode {
                         0 => GameMode::Quiz,
-                        1 => GameMode::TimeAttack25,
-                        2 => GameMode::Rpg,
-                        3 => GameMode::Records,
+                        1 => GameMode::Practice,
+                        2 => GameMode::TimeAttack25,
+                        3 => GameMode::Rpg,
+                        4 => GameMode::Records,
+                        5 => GameMode::Settings,
                         _ => GameMode::Quiz,
                     };
                     return Some((language, mode));
                 }
             },
-            KeyCode::Esc if self.step == MenuStep::ModeSelection => {
+            Some(MenuAction::Back) if self.step == MenuStep::ModeSelection => {
                 self.step = MenuStep::LanguageSelection;
             }
             _ => {}
@@ -268,7 +286,7 @@ impl MenuUI {
 
     fn render_title(&self, f: &mut Frame, area: Rect) {
         let title = Paragraph::new("type-globe")
-            .style(STYLE_TITLE)
+            .style(theme().title())
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
         f.render_widget(title, area);
@@ -280,9 +298,9 @@ impl MenuUI {
             .enumerate()
             .map(|(i, language)| {
                 let style = if i == self.selected_language {
-                    STYLE_SELECTED
+                    theme().selected()
                 } else {
-                    STYLE_NORMAL
+                    Style::new()
                 };
                 ListItem::new(Line::from(Span::styled(language.label, style)))
             })
@@ -296,7 +314,7 @@ impl MenuUI {
                     .borders(Borders::ALL)
                     .padding(Padding::uniform(1)),
             )
-            .highlight_style(STYLE_SELECTED);
+            .highlight_style(theme().selected());
 
         let mut state = ListState::default();
         state.select(Some(self.selected_language));
@@ -316,9 +334,9 @@ impl MenuUI {
             .enumerate()
             .map(|(i, mode)| {
                 let style = if i == self.selected_mode {
-                    STYLE_SELECTED
+                    theme().selected()
                 } else {
-                    STYLE_NORMAL
+                    Style::new()
                 };
                 ListItem::new(Line::from(Span::styled(mode.label, style)))
             })
@@ -331,7 +349,7 @@ impl MenuUI {
                     .borders(Borders::ALL)
                     .padding(Padding::uniform(1)),
             )
-            .highlight_style(STYLE_SELECTED);
+            .highlight_style(theme().selected());
 
         let mut state = ListState::default();
         state.select(Some(self.selected_mode));
@@ -341,24 +359,24 @@ impl MenuUI {
     }
 
     fn help_line(&self) -> HelpLine {
+        let keys = &keymap().menu;
         match self.step {
             MenuStep::LanguageSelection => HelpLine::new(vec![
-                HelpEntry::new("j/k, ↑/↓", "Select"),
-                HelpEntry::new("Enter", "Confirm"),
-                HelpEntry::new("q", "Quit"),
+                keys.help_pair(MenuAction::Up, MenuAction::Down, "Select"),
+                keys.help(MenuAction::Confirm),
+                keys.help(MenuAction::Quit),
             ]),
             MenuStep::ModeSelection => HelpLine::new(vec![
-                HelpEntry::new("j/k, ↑/↓", "Select"),
-                HelpEntry::new("Enter", "Confirm"),
-                HelpEntry::new("Esc", "Back"),
-                HelpEntry::new("q", "Quit"),
+                keys.help_pair(MenuAction::Up, MenuAction::Down, "Select"),
+                keys.help(MenuAction::Confirm),
+                keys.help(MenuAction::Back),
+                keys.help(MenuAction::Quit),
             ]),
         }
     }
 
     fn render_detail_panel(&self, f: &mut Frame, area: Rect, description: [&str; 2]) {
-        let color = self.detail_fade_color();
-        let style = Style::new().fg(color);
+        let style = self.detail_fade_style();
         let lines = vec![
             Line::from(Span::styled(description[0].to_string(), style)),
             Line::from(Span::styled(description[1].to_string(), style)),
@@ -375,17 +393,16 @@ impl MenuUI {
         f.render_widget(detail, area);
     }
 
-    /// Linear interpolation from `DETAIL_FADE_FROM` to `DETAIL_FADE_TO`
-    /// based on time elapsed since the last selection / step change.
-    fn detail_fade_color(&self) -> Color {
+    /// Position along the theme's `menu_detail_fade` based on time
+    /// elapsed since the last selection / step change.
+    fn detail_fade_style(&self) -> Style {
         let elapsed_ms = self.selection_changed_at.elapsed().as_millis() as u64;
         let alpha = if DETAIL_FADE_MS == 0 {
             1.0
         } else {
             (elapsed_ms as f32 / DETAIL_FADE_MS as f32).clamp(0.0, 1.0)
         };
-        let Rgb(r, g, b) = lerp_rgb(DETAIL_FADE_FROM, DETAIL_FADE_TO, alpha);
-        Color::Rgb(r, g, b)
+        theme().fade_style(theme().menu_detail_fade, alpha)
     }
 
     /// True while the detail panel's fade-in is still progressing, so the
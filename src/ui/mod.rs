@@ -1,13 +1,22 @@
+pub mod code_block;
+pub mod editor;
 pub mod help_line;
+pub mod image;
 pub mod inline_code;
 pub mod input_loop;
+pub mod keymap;
 pub mod layout;
 pub mod listen;
 pub mod menu;
+pub mod plain;
 pub mod quiz;
 pub mod records;
+pub mod settings;
 pub mod status;
+pub mod stealth;
+pub mod theme;
 
+pub use editor::EditorUI;
 pub use help_line::{HelpEntry, HelpLine};
 pub use input_loop::{
     DemoInputSource, InputChannel, KeyEventSource, MultiplexedSource, RecvOutcome,
@@ -15,8 +24,10 @@ pub use input_loop::{
 pub use layout::PaneFrame;
 pub use listen::{tts_unavailable_message, ListenUI};
 pub use menu::MenuUI;
+pub use plain::{PlainListen, PlainQuiz};
 pub use quiz::QuizUI;
 pub use records::RecordsUI;
+pub use settings::SettingsUI;
 // TODO(#11): drop this allow once rpg UI wires up ProgressBar / StatusItem.
 #[allow(unused_imports)]
 pub use status::{ProgressBar, StatusItem, StatusPane};
@@ -0,0 +1,683 @@
+//! Plain-text play for screen readers and pipes (#47).
+//!
+//! With `--plain`, or when stdout is not a terminal, Quiz and Listening
+//! skip the TUI: no alternate screen, colour, fades or box drawing.
+//! What the TUI draws is announced as lines of text — the question,
+//! progress, a rejected answer, the score — and answers are read a whole
+//! line at a time, so a terminal screen reader speaks each event once and
+//! a script can drive a run through a pipe. The rules are the TUI's: the
+//! same `QuizGame` and `ListeningSession` judge every answer.
+
+use crate::audio::{SpeechBackend, SpeechEvent, Utterance, SLOW_RATE};
+use crate::game::hint::Hint;
+use crate::game::{ListeningSession, QuizGame, QuizVariant, SubmissionResult};
+use crate::types::{GameMode, Language, Question};
+use crate::ui::code_block::{self, QuestionPart};
+use crate::ui::inline_code;
+use crate::ui::listen::SPEECH_START_TIMEOUT;
+use crate::ui::quiz::{save_record, CHOICE_LABELS, NAME_MAX_CHARS};
+use rand::seq::SliceRandom;
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+
+// Lines that are commands rather than answers. An empty line repeats the
+// question, or replays the prompt in Listening.
+const HINT: &str = "/hint";
+const SKIP: &str = "/skip";
+const SLOW: &str = "/slow";
+const QUIT: &str = "/quit";
+
+/// How often a Listening play is polled for its end.
+const SPEECH_POLL: Duration = Duration::from_millis(20);
+
+/// One line of input, trimmed; `None` at end of input.
+fn read_line(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+/// `text` without inline-code backticks, which a screen reader would
+/// otherwise spell out.
+fn plain(text: &str) -> String {
+    inline_code::strip_and_locate(text).0
+}
+
+pub struct PlainQuiz {
+    game: QuizGame,
+    records_file_path: String,
+    practice: bool,
+    /// The current question's choices in display order, shuffled per
+    /// question as in the TUI (#72).
+    choice_order: Vec<usize>,
+    /// Practice: the wrong choice the player went for on this question.
+    wrong_attempt: Option<usize>,
+}
+
+impl PlainQuiz {
+    /// Sample a run of `variant`'s shape from `pool`; see
+    /// [`QuizUI::for_variant`](crate::ui::QuizUI::for_variant).
+    pub fn for_variant(
+        pool: &[Question],
+        language: Language,
+        records_file_path: String,
+        variant: &QuizVariant,
+    ) -> Self {
+        let mut game = QuizGame::for_variant(pool, language, variant);
+        game.start();
+        Self {
+            game,
+            records_file_path,
+            practice: false,
+            choice_order: Vec::new(),
+            wrong_attempt: None,
+        }
+    }
+
+    /// `hints_in_records`; practice runs always allow hints.
+    pub fn with_hints(mut self, enabled: bool) -> Self {
+        self.game.set_hints_enabled(enabled || self.practice);
+        self
+    }
+
+    /// Practice run: nothing is saved and skipped questions come back.
+    pub fn with_practice(mut self, practice: bool) -> Self {
+        self.practice = practice;
+        self.game.set_practice(practice);
+        if practice {
+            self.game.set_hints_enabled(true);
+        }
+        self
+    }
+
+    /// Play the run, answers from `input`, announcements to `out`.
+    /// Returns the final score; a quit (or end of input) skips the
+    /// summary and the Records prompt.
+    pub fn run(
+        &mut self,
+        input: &mut impl BufRead,
+        out: &mut impl Write,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        writeln!(
+            out,
+            "{}. Type each answer and press Enter. Empty line: repeat the question. \
+             {HINT}: hint. {SKIP}: skip. {QUIT}: quit.",
+            self.game.variant().label()
+        )?;
+        while self.game.get_current_question().is_some() {
+            let deadline = self.start_question();
+            self.announce_question(out)?;
+            if !self.play_question(input, out, deadline)? {
+                writeln!(out, "Quit.")?;
+                return Ok(self.game.get_final_score());
+            }
+        }
+        self.announce_summary(out)?;
+        if !self.practice {
+            self.offer_save(input, out)?;
+        }
+        Ok(self.game.get_final_score())
+    }
+
+    /// Shuffle the new question's choices and start its clock. Returns
+    /// the Timed deadline.
+    fn start_question(&mut self) -> Option<Instant> {
+        self.wrong_attempt = None;
+        let count = self
+            .game
+            .get_current_question()
+            .map_or(0, |q| q.choices.len());
+        self.choice_order = (0..count).collect();
+        self.choice_order.shuffle(&mut rand::thread_rng());
+        self.game
+            .current_time_limit()
+            .map(|limit| Instant::now() + limit)
+    }
+
+    /// Take lines until the question is answered or skipped. `false`
+    /// when the player quits.
+    fn play_question(
+        &mut self,
+        input: &mut impl BufRead,
+        out: &mut impl Write,
+        deadline: Option<Instant>,
+    ) -> io::Result<bool> {
+        loop {
+            let Some(line) = read_line(input)? else {
+                return Ok(false);
+            };
+            // There is no countdown to interrupt a blocking read, so a
+            // Timed answer is checked against the limit once it arrives.
+            if deadline.is_some_and(|deadline| Instant::now() > deadline) && line != QUIT {
+                writeln!(out, "Time is up.")?;
+                self.skip(out)?;
+                return Ok(true);
+            }
+            match line.as_str() {
+                QUIT => return Ok(false),
+                "" => self.announce_question(out)?,
+                HINT => self.announce_hint(out)?,
+                SKIP => {
+                    self.skip(out)?;
+                    return Ok(true);
+                }
+                typed => {
+                    if self.answer(typed, out)? {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+    }
+
+    fn announce_question(&self, out: &mut impl Write) -> io::Result<()> {
+        let Some(question) = self.game.get_current_question() else {
+            return Ok(());
+        };
+        let (done, total) = self.game.get_progress();
+        if *self.game.variant() == QuizVariant::Survival {
+            writeln!(out, "Question {}.", done + 1)?;
+        } else {
+            writeln!(out, "Question {} of {total}.", done + 1)?;
+        }
+        let text = self.game.get_question_text(question);
+        for part in code_block::split_fenced(&text) {
+            match part {
+                QuestionPart::Prose(prose) => {
+                    for line in prose.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                        writeln!(out, "{}", plain(line))?;
+                    }
+                }
+                QuestionPart::Code(block) => {
+                    if block.tag.is_empty() {
+                        writeln!(out, "Code:")?;
+                    } else {
+                        writeln!(out, "Code ({}):", block.tag)?;
+                    }
+                    for line in &block.lines {
+                        writeln!(out, "{line}")?;
+                    }
+                    writeln!(out, "End of code.")?;
+                }
+            }
+        }
+        let ruled_out: Vec<usize> = self
+            .game
+            .current_hints()
+            .iter()
+            .filter_map(|hint| match hint {
+                Hint::EliminateChoice(idx) => Some(*idx),
+                _ => None,
+            })
+            .collect();
+        for &idx in &self.choice_order {
+            let note = if ruled_out.contains(&idx) {
+                " (ruled out)"
+            } else {
+                ""
+            };
+            writeln!(out, "{}{note}", self.choice_name(idx))?;
+        }
+        if let Some(limit) = self.game.current_time_limit() {
+            writeln!(out, "Time limit: {:.1} seconds.", limit.as_secs_f32())?;
+        }
+        Ok(())
+    }
+
+    /// `B: Osaka` — the choice's letter in display order and its label.
+    fn choice_name(&self, idx: usize) -> String {
+        let label = self
+            .game
+            .get_current_question()
+            .and_then(|q| self.game.get_choice_texts(q).into_iter().nth(idx))
+            .unwrap_or_default();
+        let letter = self
+            .choice_order
+            .iter()
+            .position(|&i| i == idx)
+            .and_then(|pos| CHOICE_LABELS.get(pos))
+            .copied()
+            .unwrap_or("?");
+        format!("{letter}: {}", plain(&label))
+    }
+
+    fn announce_hint(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let Some(hint) = self.game.take_hint() else {
+            return writeln!(out, "No more hints.");
+        };
+        let text = match &hint {
+            Hint::EliminateChoice(idx) => format!("not {}", self.choice_name(*idx)),
+            other => other.text().unwrap_or_default(),
+        };
+        writeln!(out, "Hint (-{}): {text}.", hint.cost())
+    }
+
+    fn skip(&mut self, out: &mut impl Write) -> io::Result<()> {
+        // Practice explains the skip; a scored run never shows the answer.
+        let answer = self
+            .practice
+            .then(|| {
+                let question = self.game.get_current_question()?;
+                Some(self.choice_name(question.correct_answer_index))
+            })
+            .flatten();
+        self.game.skip_question();
+        match answer {
+            Some(answer) => writeln!(out, "Skipped. The answer is {answer}."),
+            None => writeln!(out, "Skipped."),
+        }
+    }
+
+    /// Judge a typed line. `true` once the question is answered; a wrong
+    /// line is announced and the question stays, as the TUI rejects the
+    /// keystroke that goes wrong.
+    fn answer(&mut self, typed: &str, out: &mut impl Write) -> io::Result<bool> {
+        if self.game.is_complete_correct_typed(typed) {
+            let went_for = self.wrong_attempt.map(|idx| self.choice_name(idx));
+            self.game.answer_question_typed(typed);
+            writeln!(out, "Correct. Score {}.", self.game.get_final_score())?;
+            if let Some(went_for) = went_for {
+                writeln!(out, "You first went for {went_for}.")?;
+            }
+            return Ok(true);
+        }
+        if self.practice {
+            if let Some(idx) = self.game.wrong_choice_for_prefix(typed) {
+                self.wrong_attempt = Some(idx);
+            }
+        }
+        let good: String = typed
+            .chars()
+            .scan(String::new(), |prefix, c| {
+                prefix.push(c);
+                Some(prefix.clone())
+            })
+            .take_while(|prefix| self.game.is_valid_correct_typed_prefix(prefix))
+            .last()
+            .unwrap_or_default();
+        if good.len() == typed.len() {
+            writeln!(out, "Not yet: \"{typed}\" is only the start of the answer.")?;
+        } else {
+            let at = good.chars().count() + 1;
+            writeln!(
+                out,
+                "Not the answer: \"{typed}\" goes wrong at character {at}."
+            )?;
+        }
+        Ok(false)
+    }
+
+    fn announce_summary(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "Finished. Score {}.", self.game.get_final_score())?;
+        writeln!(
+            out,
+            "Correct: {} of {} ({:.0}%).",
+            self.game.get_correct_count(),
+            self.game.questions_played(),
+            self.game.get_accuracy() * 100.0
+        )?;
+        if let Some(time) = self.game.get_total_time() {
+            writeln!(
+                out,
+                "Time: {:.1} seconds. Speed: {} CPM, {} WPM.",
+                time.as_secs_f32(),
+                self.game.get_cpm(),
+                self.game.get_wpm()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Ask for a name and file the score; an empty line skips saving.
+    fn offer_save(&self, input: &mut impl BufRead, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "Name for Records (Enter to skip): ")?;
+        out.flush()?;
+        let Some(name) = read_line(input)? else {
+            return writeln!(out);
+        };
+        let name: String = name.chars().take(NAME_MAX_CHARS).collect();
+        if name.is_empty() {
+            return writeln!(out, "Not saved.");
+        }
+        match save_record(&self.game, &self.records_file_path, &name) {
+            Ok(()) => writeln!(out, "Saved."),
+            Err(err) => writeln!(out, "warning: failed to save records: {err}"),
+        }
+    }
+}
+
+pub struct PlainListen {
+    session: ListeningSession,
+    /// `None` with `rpg --no-tts`.
+    speech: Option<Box<dyn SpeechBackend>>,
+    language: Language,
+    /// Plays counted as in `ListenUI`: when the audio begins, or per
+    /// attempt without speech.
+    plays: u32,
+}
+
+impl PlainListen {
+    pub fn new(
+        session: ListeningSession,
+        speech: Option<Box<dyn SpeechBackend>>,
+        language: Language,
+    ) -> Self {
+        Self {
+            session,
+            speech,
+            language,
+            plays: 0,
+        }
+    }
+
+    /// Play the prompt, take one answer line and announce the verdict.
+    /// `None` when the player quits first.
+    pub fn run(
+        &mut self,
+        input: &mut impl BufRead,
+        out: &mut impl Write,
+    ) -> Result<Option<SubmissionResult>, Box<dyn std::error::Error>> {
+        writeln!(
+            out,
+            "Listening. Type what you hear and press Enter. Empty line: replay. \
+             {SLOW}: replay slowly. {QUIT}: quit."
+        )?;
+        if self.speech.is_none() {
+            writeln!(out, "Speech is off; the prompt will not play.")?;
+        }
+        self.play(1.0, out)?;
+        let result = loop {
+            let Some(line) = read_line(input)? else {
+                break None;
+            };
+            match line.as_str() {
+                QUIT => break None,
+                "" => self.play(1.0, out)?,
+                SLOW => self.play(SLOW_RATE, out)?,
+                typed => {
+                    for c in typed.chars() {
+                        self.session.push_char(c);
+                    }
+                    break Some(self.session.submit().clone());
+                }
+            }
+        };
+        if let Some(speech) = self.speech.as_mut() {
+            let _ = speech.stop();
+        }
+        match &result {
+            Some(result) => self.announce_result(result, out)?,
+            None => writeln!(out, "Quit.")?,
+        }
+        Ok(result)
+    }
+
+    /// Say the prompt and wait for it to end, so nothing printed next is
+    /// read out over it.
+    fn play(&mut self, rate: f32, out: &mut impl Write) -> io::Result<()> {
+        let Some(speech) = self.speech.as_mut() else {
+            self.plays += 1;
+            return Ok(());
+        };
+        let prompt = self.session.prompt();
+        let spoken = speech.speak(&Utterance {
+            id: &prompt.id,
+            text: &prompt.text_reading,
+            language: &self.language,
+            rate,
+        });
+        if let Err(err) = spoken {
+            return writeln!(out, "Speech failed: {err}");
+        }
+        let asked = Instant::now();
+        let mut begun = false;
+        loop {
+            for event in speech.poll_events() {
+                match event {
+                    SpeechEvent::Begin => {
+                        begun = true;
+                        self.plays += 1;
+                    }
+                    SpeechEvent::End => {
+                        self.session.audio_finished(Instant::now());
+                        return Ok(());
+                    }
+                }
+            }
+            if !begun && asked.elapsed() >= SPEECH_START_TIMEOUT {
+                return Ok(());
+            }
+            std::thread::sleep(SPEECH_POLL);
+        }
+    }
+
+    fn announce_result(&self, result: &SubmissionResult, out: &mut impl Write) -> io::Result<()> {
+        let prompt = self.session.prompt();
+        let answer = if prompt.text_display == result.expected {
+            result.expected.clone()
+        } else {
+            format!("{} ({})", prompt.text_display, result.expected)
+        };
+        if result.is_correct {
+            writeln!(out, "Correct: {answer}.")?;
+        } else {
+            writeln!(
+                out,
+                "Wrong. You typed \"{}\"; the answer is {answer}.",
+                self.session.input()
+            )?;
+        }
+        if let Some(reaction) = result.reaction {
+            writeln!(out, "Reaction: {:.2} seconds.", reaction.as_secs_f32())?;
+        }
+        writeln!(out, "Plays: {}.", self.plays)
+    }
+}
+
+/// The plain menu: the modes that have a plain form. `None` quits.
+pub fn choose_mode(input: &mut impl BufRead, out: &mut impl Write) -> io::Result<Option<GameMode>> {
+    const MODES: [(GameMode, &str); 3] = [
+        (GameMode::Quiz, "Quiz"),
+        (GameMode::Practice, "Practice"),
+        (GameMode::Rpg, "Listening RPG"),
+    ];
+    loop {
+        for (i, (_, label)) in MODES.iter().enumerate() {
+            writeln!(out, "{}: {label}", i + 1)?;
+        }
+        write!(out, "Mode (1-{}, q to quit): ", MODES.len())?;
+        out.flush()?;
+        let Some(line) = read_line(input)? else {
+            return Ok(None);
+        };
+        if line == "q" || line == QUIT {
+            return Ok(None);
+        }
+        let chosen = line
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| MODES.get(i));
+        match chosen {
+            Some((mode, _)) => return Ok(Some(mode.clone())),
+            None => writeln!(out, "Type a number from the list.")?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::mock::MockSpeech;
+    use crate::types::{AnswerKind, Choice, ListeningPrompt};
+    use std::collections::HashMap;
+
+    fn question(text: &str, labels: &[&str]) -> Question {
+        let choices = labels
+            .iter()
+            .map(|label| Choice {
+                labels: HashMap::from([("en".to_string(), label.to_string())]),
+                ja_typings: Vec::new(),
+            })
+            .collect();
+        Question {
+            id: "q-plain".into(),
+            genre: "test".into(),
+            question_text: HashMap::from([("en".to_string(), text.to_string())]),
+            question_text_reading: HashMap::new(),
+            choices,
+            correct_answer_index: 0,
+            image_path: None,
+            ja_reviewed: false,
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
+        }
+    }
+
+    fn quiz(question: Question) -> PlainQuiz {
+        PlainQuiz::for_variant(
+            &[question],
+            Language::English,
+            String::new(),
+            &QuizVariant::Count(1),
+        )
+    }
+
+    fn play(quiz: &mut PlainQuiz, lines: &str) -> (u32, String) {
+        let mut out = Vec::new();
+        let score = quiz.run(&mut lines.as_bytes(), &mut out).expect("run");
+        (score, String::from_utf8(out).expect("utf-8"))
+    }
+
+    #[test]
+    fn quiz_announces_progress_mistypes_and_score() {
+        let mut quiz = quiz(question(
+            "Capital of `Japan`?\n```sh\necho tokyo\n```",
+            &["Tokyo", "Osaka"],
+        ));
+        let (score, out) = play(&mut quiz, "tok\ntox\n\ntokyo\n\n");
+
+        assert_eq!(score, 100);
+        assert!(out.contains(
+            "Question 1 of 1.\nCapital of Japan?\nCode (sh):\necho tokyo\nEnd of code.\n"
+        ));
+        assert!(out.contains("Not yet: \"tok\" is only the start of the answer."));
+        assert!(out.contains("Not the answer: \"tox\" goes wrong at character 3."));
+        assert_eq!(
+            out.matches("Question 1 of 1.").count(),
+            2,
+            "empty line repeats"
+        );
+        assert!(out.contains("Correct. Score 100.\nFinished. Score 100.\nCorrect: 1 of 1 (100%)."));
+        assert!(out.ends_with("Name for Records (Enter to skip): Not saved.\n"));
+        assert!(!out.contains('\u{1b}'), "no escape sequences");
+    }
+
+    #[test]
+    fn practice_hints_and_skips_explain_the_answer() {
+        let mut quiz = quiz(question("Capital?", &["Tokyo", "Osaka"])).with_practice(true);
+        let (score, out) = play(&mut quiz, "/hint\n\n/skip\n/skip\n");
+
+        assert_eq!(score, 0);
+        assert!(out.contains("Hint (-20): not "), "{out}");
+        assert!(out.contains(": Osaka.\n"), "{out}");
+        assert!(out.contains("Skipped. The answer is "), "{out}");
+        assert!(out.contains(": Tokyo."), "{out}");
+        assert!(out.contains("Question 2 of 2."), "a skip comes back once");
+        assert_eq!(out.matches("(ruled out)").count(), 1, "{out}");
+        assert!(!out.contains("Name for Records"), "practice never saves");
+    }
+
+    #[test]
+    fn quit_and_end_of_input_skip_the_summary() {
+        for lines in ["/quit\n", ""] {
+            let mut quiz = quiz(question("Capital?", &["Tokyo", "Osaka"]));
+            let (_, out) = play(&mut quiz, lines);
+            assert!(out.ends_with("Quit.\n"), "{out}");
+            assert!(!out.contains("Finished."));
+        }
+    }
+
+    #[test]
+    fn a_named_run_is_saved_to_records() {
+        let path =
+            std::env::temp_dir().join(format!("type-globe-plain-{}.yaml", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut quiz = PlainQuiz::for_variant(
+            &[question("Capital?", &["Tokyo", "Osaka"])],
+            Language::English,
+            path.to_string_lossy().into_owned(),
+            &QuizVariant::Count(1),
+        );
+        let (_, out) = play(&mut quiz, "tokyo\nAda\n");
+
+        assert!(out.ends_with("Saved.\n"), "{out}");
+        let records = crate::io::Storage::load_records(&path.to_string_lossy()).expect("records");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(records.quiz_count[&1][0].name, "Ada");
+    }
+
+    fn listen(speech: Option<Box<dyn SpeechBackend>>) -> PlainListen {
+        let prompt = ListeningPrompt {
+            id: "p-plain".into(),
+            text_reading: "apple".into(),
+            text_display: "apple".into(),
+            kind: AnswerKind::Word,
+        };
+        PlainListen::new(
+            ListeningSession::new(prompt, Language::English),
+            speech,
+            Language::English,
+        )
+    }
+
+    #[test]
+    fn listening_plays_replays_and_judges_a_line() {
+        let (mock, log) = MockSpeech::new();
+        let mut ui = listen(Some(Box::new(mock)));
+        let mut out = Vec::new();
+        let result = ui
+            .run(&mut "\n/slow\napple\n".as_bytes(), &mut out)
+            .expect("run")
+            .expect("submitted");
+        let out = String::from_utf8(out).expect("utf-8");
+
+        assert!(result.is_correct);
+        assert!(result.reaction.is_some(), "timed from the end of a play");
+        let rates: Vec<f32> = log.spoken().iter().map(|s| s.rate).collect();
+        assert_eq!(rates, [1.0, 1.0, SLOW_RATE]);
+        assert!(out.contains("Correct: apple.\n"), "{out}");
+        assert!(out.ends_with("Plays: 3.\n"), "{out}");
+        assert_eq!(log.stops(), 1);
+    }
+
+    #[test]
+    fn listening_without_speech_says_so_and_shows_the_answer() {
+        let mut ui = listen(None);
+        let mut out = Vec::new();
+        let result = ui
+            .run(&mut "pear\n".as_bytes(), &mut out)
+            .expect("run")
+            .expect("submitted");
+        let out = String::from_utf8(out).expect("utf-8");
+
+        assert!(!result.is_correct);
+        assert!(out.contains("Speech is off"));
+        assert!(out.contains("Wrong. You typed \"pear\"; the answer is apple."));
+    }
+
+    #[test]
+    fn menu_takes_a_number_and_retries_on_anything_else() {
+        let mut out = Vec::new();
+        let mode = choose_mode(&mut "x\n2\n".as_bytes(), &mut out).expect("menu");
+        assert!(matches!(mode, Some(GameMode::Practice)));
+        let out = String::from_utf8(out).expect("utf-8");
+        assert!(out.contains("Type a number from the list."));
+
+        let mode = choose_mode(&mut "q\n".as_bytes(), &mut Vec::new()).expect("menu");
+        assert!(mode.is_none());
+    }
+}
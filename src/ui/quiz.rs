@@ -1,28 +1,37 @@
-use crate::audio::{Cue, CueEngine};
-use crate::game::QuizGame;
-use crate::io::Storage;
+use crate::audio::{Cue, CueEngine, SoundTheme, SpeechBackend, Utterance, COMBO_MIN};
+use crate::game::hint::{Hint, HintLog};
+use crate::game::quiz::QUIZ_RUN_LENGTH;
+use crate::game::{QuizGame, QuizVariant};
+use crate::io::{DataLoader, Storage};
+use crate::settings::{ReadAloud, RevealSpeed};
 use crate::types::{Language, Question, ScoreEntry};
+use crate::ui::code_block::{self, CodeBlock};
+use crate::ui::image::{GraphicsProtocol, ImagePane};
 use crate::ui::inline_code;
+use crate::ui::keymap::{keymap, QuizAction};
+use crate::ui::stealth::{Level, LogEvent, LogScreen, Stealth};
+use crate::ui::theme::theme;
 use crate::ui::{
     DemoInputSource, HelpEntry, HelpLine, InputChannel, KeyEventSource, MultiplexedSource,
-    PaneFrame, RecvOutcome, StatusPane,
+    PaneFrame, RecvOutcome, StatusItem, StatusPane,
 };
 use crossterm::{
     event::{KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use jiwa::{lerp_rgb, RevealHandle, RevealOpts, Rgb};
+use jiwa::RevealHandle;
 use rand::seq::SliceRandom;
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Padding, Paragraph},
     Frame, Terminal,
 };
 use std::io;
+use std::path::PathBuf;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 fn now_rfc3339() -> String {
@@ -59,21 +68,8 @@ fn days_to_ymd(days: u64) -> (u64, u64, u64) {
 
 /// Maximum characters the player can type into the name-entry field.
 /// Sized to fit comfortably in the side pane / Records list rendering.
-const NAME_MAX_CHARS: usize = 16;
-
-const STYLE_TITLE: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-const STYLE_NORMAL: Style = Style::new().fg(Color::White);
-const STYLE_DIM: Style = Style::new().fg(Color::DarkGray);
-const STYLE_CORRECT: Style = Style::new().fg(Color::Green).add_modifier(Modifier::BOLD);
-const STYLE_INCORRECT: Style = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
-const STYLE_INPUT_ECHO: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
-/// Foreground color applied to Markdown inline-code spans in question and
-/// choice text (Issue #97). The Bold modifier is added on top per-span;
-/// this constant only carries the color so the reveal animation can mix
-/// it with the per-grapheme fade color.
-// Issue #97 nit: shifted toward orange so the inline-code highlight is
-// unambiguously distinct from `Color::Yellow` (the input-echo color).
-const INLINE_CODE_COLOR: Color = Color::Rgb(255, 200, 60);
+pub(crate) const NAME_MAX_CHARS: usize = 16;
+
 const INPUT_REJECT_FLASH_MS: u64 = 180;
 /// How long after the question reveal starts before the choices block begins
 /// fading in (Issue #72). Roughly the time it takes for the eye to land on
@@ -84,10 +80,15 @@ const CHOICES_REVEAL_DELAY_MS: u64 = 500;
 /// color. The four choices appear as a single group (Issue #72), so this
 /// is the only timing knob the choices reveal needs.
 const CHOICES_FADE_MS: u64 = 320;
+/// Choices pane height when a code block shares the column: 4 choices plus
+/// border and `Padding::uniform(1)`.
+const CHOICES_PANE_HEIGHT: u16 = 8;
+/// Choice letters in display order. Labels A/B/C/D match docs/spec.md.
+pub(crate) const CHOICE_LABELS: [&str; 4] = ["A", "B", "C", "D"];
 
 /// High-level state machine for one Quiz session. Issue #70 removed the
 /// per-question result interstitial, so playing → summary → records-name-
-/// entry → done is the entire flow.
+/// entry → done is the entire flow. Practice runs stop at the summary.
 #[derive(Debug, Clone, PartialEq)]
 enum Phase {
     Playing,
@@ -95,6 +96,29 @@ enum Phase {
     NamingForRecord,
 }
 
+/// Practice mode's after-the-fact explanation of a question the player
+/// skipped or started typing a wrong choice for.
+#[derive(Debug, Clone, PartialEq)]
+struct Review {
+    question: String,
+    /// Label of the wrong choice the rejected input was heading for;
+    /// `None` for a plain skip.
+    went_for: Option<String>,
+    answer: String,
+    skipped: bool,
+}
+
+impl Review {
+    fn text(&self) -> String {
+        let outcome = match (&self.went_for, self.skipped) {
+            (Some(wrong), true) => format!("skipped after trying {wrong}"),
+            (Some(wrong), false) => format!("tried {wrong} first"),
+            (None, _) => "skipped".to_string(),
+        };
+        format!("{} — {outcome}; answer: {}", self.question, self.answer)
+    }
+}
+
 /// Result of one demo session (#106 review: M-2/S-1).
 ///
 /// The caller (`run_quiz_demo` in `main.rs`) needs to distinguish three
@@ -155,6 +179,10 @@ pub struct QuizUI {
     /// with the reveal snapshot indices so we can style code graphemes
     /// without disturbing the per-character fade-in.
     code_ranges: Vec<(usize, usize)>,
+    /// Fenced code blocks pulled out of the current question text. The
+    /// reveal only animates the prose around them; the blocks get their
+    /// own highlighted pane between the question and the choices.
+    code_blocks: Vec<CodeBlock>,
     /// Permutation of the active question's choices (Issue #72). Indices
     /// reference the original `question.choices`; `correct_answer_index`
     /// stays meaningful because the typing match is identity-based, not
@@ -186,32 +214,185 @@ pub struct QuizUI {
     /// loop too (S-1) rather than restarting another session right
     /// after the user already asked to leave.
     user_aborted: bool,
+    /// Picture for questions with an `image_path`. Reloaded alongside the
+    /// reveal whenever the question changes.
+    images: ImagePane,
+    /// Log-viewer disguise and boss key (`ui::stealth`).
+    stealth: Stealth,
+    /// `settings.yaml` `reveal_speed`, applied to the theme's reveal timing.
+    reveal_speed: RevealSpeed,
+    /// Timed runs: the current question's limit and the instant it runs
+    /// out. The countdown starts once both the question text and the
+    /// choices are fully on screen, so slow reveal settings cost nothing.
+    countdown: Option<(Duration, Instant)>,
+    /// When the boss screen went up. The countdown is frozen behind it
+    /// and its deadline moves on by the hidden time once it comes down.
+    boss_since: Option<Instant>,
+    /// Countdown second the last `CountdownTick` played for, so each of
+    /// the final seconds ticks once.
+    last_tick: Option<u32>,
+    /// Correct answers in a row; from `COMBO_MIN` on each one also plays
+    /// the combo cue. A skip breaks it.
+    streak: u32,
+    /// Practice run (`quiz --practice`): skips are retried later, misses
+    /// are explained, and nothing is written to Records.
+    practice: bool,
+    /// Practice: the wrong choice the player's rejected input was heading
+    /// for on the current question.
+    wrong_attempt: Option<usize>,
+    /// Practice: explanations so far, newest last.
+    reviews: Vec<Review>,
+    /// First visible line of the Summary's explanation cards.
+    summary_scroll: u16,
+    /// `read_aloud` in `settings.yaml` (#46): what gets spoken for each
+    /// question, through `speech`.
+    read_aloud: ReadAloud,
+    /// `None` with read-aloud off, or once speaking has failed.
+    speech: Option<Box<dyn SpeechBackend>>,
 }
 
 impl QuizUI {
-    /// Build a UI by sampling a 10-question run from `pool`. Mirrors
-    /// `QuizGame::from_pool` so main.rs doesn't have to reach into the
-    /// game module directly.
-    pub fn from_pool(pool: &[Question], language: Language, records_file_path: String) -> Self {
-        let mut quiz_game = QuizGame::from_pool(pool, language);
-        quiz_game.start();
-        Self::wrap_started_game(quiz_game, records_file_path)
-    }
-
-    /// Variant of [`from_pool`] that lets the caller pin the run length.
-    /// The auto-demo (#106) uses this to honour `--demo-count`.
+    /// Build a UI by sampling a `count`-question run from `pool` — the
+    /// `run_length` setting, or `--demo-count` for the auto-demo (#106).
+    /// Mirrors `QuizGame::from_pool_with_count` so main.rs doesn't have
+    /// to reach into the game module directly.
     pub fn from_pool_with_count(
         pool: &[Question],
         language: Language,
         records_file_path: String,
         count: usize,
     ) -> Self {
-        let mut quiz_game = QuizGame::from_pool_with_count(pool, language, count);
+        Self::for_variant(
+            pool,
+            language,
+            records_file_path,
+            &QuizVariant::Count(count),
+        )
+    }
+
+    /// Build a UI for any run shape (`quiz --count` / `--survival` /
+    /// `--marathon`). The score is filed under the variant's own Records
+    /// list.
+    pub fn for_variant(
+        pool: &[Question],
+        language: Language,
+        records_file_path: String,
+        variant: &QuizVariant,
+    ) -> Self {
+        let mut quiz_game = QuizGame::for_variant(pool, language, variant);
+        quiz_game.start();
+        Self::wrap_started_game(quiz_game, records_file_path, CueEngine::new())
+    }
+
+    /// Single-question, silent instance used by the question editor's
+    /// preview pane. Goes through the same reveal / inline-code / choice
+    /// shuffle path as a real run so the author sees exactly what a
+    /// player will. Never persists records (empty records path, and the
+    /// editor only ever calls [`render_preview`](Self::render_preview)).
+    pub fn preview(question: Question, language: Language) -> Self {
+        let mut quiz_game = QuizGame::new(vec![question], language);
         quiz_game.start();
-        Self::wrap_started_game(quiz_game, records_file_path)
+        let mut ui = Self::wrap_started_game(quiz_game, String::new(), None);
+        // The editor never calls `ImagePane::flush`, so pixel protocols
+        // would leave the pane blank — always use half-blocks here.
+        ui.images = ImagePane::new(Vec::new(), GraphicsProtocol::HalfBlock);
+        ui
+    }
+
+    /// Allow hints (F3) in this run; `hints_in_records` in `settings.yaml`.
+    /// Practice runs never save, so they always allow hints.
+    pub fn with_hints(mut self, enabled: bool) -> Self {
+        self.quiz_game.set_hints_enabled(enabled || self.practice);
+        self
+    }
+
+    /// Practice run: nothing is saved, skipped questions come back, misses
+    /// are explained on the Summary, and hints are always on.
+    pub fn with_practice(mut self, practice: bool) -> Self {
+        self.practice = practice;
+        self.quiz_game.set_practice(practice);
+        if practice {
+            self.quiz_game.set_hints_enabled(true);
+        }
+        self
+    }
+
+    /// Directories relative `image_path`s are resolved against, in order.
+    /// See [`crate::ui::image::search_dirs_for`].
+    pub fn with_image_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.images.set_search_dirs(dirs);
+        self
+    }
+
+    /// Start in the stealth log view (`--stealth`). The toggle key still
+    /// switches back.
+    pub fn with_stealth(mut self, enabled: bool) -> Self {
+        self.stealth = Stealth::new(enabled);
+        self
+    }
+
+    /// Question reveal pace from `settings.yaml`.
+    pub fn with_reveal_speed(mut self, speed: RevealSpeed) -> Self {
+        self.reveal_speed = speed;
+        self
+    }
+
+    /// Speak each question through `speech` (#46). `ReadAloud::Off`
+    /// drops the backend.
+    pub fn with_read_aloud(
+        mut self,
+        read_aloud: ReadAloud,
+        speech: Option<Box<dyn SpeechBackend>>,
+    ) -> Self {
+        self.read_aloud = read_aloud;
+        self.speech = speech.filter(|_| read_aloud != ReadAloud::Off);
+        self
+    }
+
+    /// Sound-effect gain from `settings.yaml`. Zero (sound off) closes
+    /// the audio device instead of playing silent cues.
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.cues = match self.cues.take() {
+            Some(engine) if volume > 0.0 => Some(engine.with_volume(volume)),
+            _ => None,
+        };
+        self
     }
 
-    fn wrap_started_game(quiz_game: QuizGame, records_file_path: String) -> Self {
+    /// `settings.yaml` `keystroke_volume`: the typing clicks' share of
+    /// the cue volume.
+    pub fn with_keystroke_volume(mut self, scale: f32) -> Self {
+        self.cues = self
+            .cues
+            .take()
+            .map(|engine| engine.with_keystroke_volume(scale));
+        self
+    }
+
+    /// Cue palette from `settings.yaml` `sound_theme`.
+    pub fn with_sound_theme(mut self, theme: SoundTheme) -> Self {
+        self.cues = self.cues.take().map(|engine| engine.with_theme(theme));
+        self
+    }
+
+    /// Draw the question + choices block into `area`, (re-)starting the
+    /// reveal on first call. Used by the editor preview pane.
+    pub fn render_preview(&mut self, f: &mut Frame, area: Rect) {
+        self.ensure_reveal_for_current_question();
+        if self.images.is_active() {
+            let (text, image) = PaneFrame::split_for_image(area);
+            self.render_question(f, text);
+            self.images.render(f, image);
+        } else {
+            self.render_question(f, area);
+        }
+    }
+
+    fn wrap_started_game(
+        quiz_game: QuizGame,
+        records_file_path: String,
+        cues: Option<CueEngine>,
+    ) -> Self {
         Self {
             quiz_game,
             input_buffer: String::new(),
@@ -222,14 +403,28 @@ impl QuizUI {
             reveal: None,
             reveal_for_question: None,
             code_ranges: Vec::new(),
+            code_blocks: Vec::new(),
             choice_order: Vec::new(),
             choices_reveal_starts_at: None,
+            countdown: None,
+            boss_since: None,
+            last_tick: None,
+            streak: 0,
             rejected_char: None,
             reject_flash_until: None,
-            cues: CueEngine::new(),
+            cues,
             pending_warnings: Vec::new(),
             no_target_abort: false,
             user_aborted: false,
+            images: ImagePane::new(Vec::new(), GraphicsProtocol::detect()),
+            stealth: Stealth::new(false),
+            reveal_speed: RevealSpeed::Normal,
+            practice: false,
+            wrong_attempt: None,
+            reviews: Vec::new(),
+            summary_scroll: 0,
+            read_aloud: ReadAloud::Off,
+            speech: None,
         }
     }
 
@@ -239,6 +434,39 @@ impl QuizUI {
         }
     }
 
+    /// Speak the current question as `read_aloud` asks, interrupting the
+    /// previous one. A failure is reported after the run and turns
+    /// read-aloud off rather than retrying on every question.
+    fn read_current_question(&mut self) {
+        let Some(speech) = self.speech.as_mut() else {
+            return;
+        };
+        let Some(question) = self.quiz_game.get_current_question() else {
+            let _ = speech.stop();
+            return;
+        };
+        let language = self.quiz_game.language();
+        let text = aloud_text(question, language, self.read_aloud, &self.choice_order);
+        let spoken = speech.speak(&Utterance {
+            id: &question.id,
+            text: &text,
+            language,
+            rate: 1.0,
+        });
+        if let Err(e) = spoken {
+            self.pending_warnings
+                .push(format!("warning: read-aloud turned off: {e}"));
+            self.speech = None;
+        }
+    }
+
+    /// Silence read-aloud on the way out of the run.
+    fn stop_speech(&mut self) {
+        if let Some(speech) = self.speech.as_mut() {
+            let _ = speech.stop();
+        }
+    }
+
     pub fn run(&mut self) -> Result<u32, Box<dyn std::error::Error>> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
@@ -248,7 +476,9 @@ impl QuizUI {
 
         let input = InputChannel::spawn();
         let result = self.run_app(&mut terminal, &input, None);
+        self.stop_speech();
 
+        self.images.release(terminal.backend_mut())?;
         disable_raw_mode()?;
         execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
         terminal.show_cursor()?;
@@ -285,7 +515,9 @@ impl QuizUI {
         let human = InputChannel::spawn();
         let source = MultiplexedSource { a: human, b: demo };
         let result = self.run_app(&mut terminal, &source, Some(&source.b));
+        self.stop_speech();
 
+        self.images.release(terminal.backend_mut())?;
         disable_raw_mode()?;
         execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
         terminal.show_cursor()?;
@@ -319,6 +551,7 @@ impl QuizUI {
 
         loop {
             terminal.draw(|f| self.ui(f))?;
+            self.images.flush(terminal)?;
 
             // Demo: keep the synthetic input source pointed at the
             // currently-active question. We re-prime whenever the
@@ -384,6 +617,10 @@ impl QuizUI {
                 }
                 RecvOutcome::Disconnected => break, // Worker thread exited.
             }
+            self.duck_under_speech();
+            self.hold_countdown_for_boss(Instant::now());
+            self.tick_countdown(Instant::now());
+            self.expire_countdown(Instant::now());
 
             // Demo: in Summary / Naming phases, the session has nothing
             // left to do — exit immediately so the demo loop driver can
@@ -423,15 +660,16 @@ impl QuizUI {
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> bool {
-        // Quit keys: Esc, and Ctrl+C as the standard terminal escape.
-        // Printable characters (including 'q') are part of typed selection
-        // and must reach the buffer.
+        // Quit keys: the keymap's quit (Esc by default), and Ctrl+C as the
+        // standard terminal escape. Printable characters (including 'q')
+        // are part of typed selection and must reach the buffer.
         //
         // S-1: record the explicit user abort so `run_with_demo` can
         // forward the signal up to the `--demo-loop` driver and break
         // the outer loop, rather than restarting another session right
         // after the user already asked to leave.
-        if matches!(key.code, KeyCode::Esc) {
+        let action = keymap().quiz.action_for(&key);
+        if action == Some(QuizAction::Quit) {
             self.user_aborted = true;
             return true;
         }
@@ -439,37 +677,37 @@ impl QuizUI {
             self.user_aborted = true;
             return true;
         }
+        if self.stealth.handle_key(&key) {
+            return false;
+        }
 
         match self.phase {
-            Phase::Summary => return self.handle_key_summary(key),
-            Phase::NamingForRecord => return self.handle_key_naming(key),
+            Phase::Summary => return self.handle_key_summary(action),
+            Phase::NamingForRecord => return self.handle_key_naming(key, action),
             Phase::Playing => {}
         }
 
-        match key.code {
-            KeyCode::Enter => {}
-            // The guard's side effect (skip_question) is intentional — Tab
+        match (action, key.code) {
+            // The guard's side effect (skip_question) is intentional —
             // skipping is the action, not a precondition. If skip fails
             // (no current question), the arm falls through to `_ => {}`.
-            KeyCode::Tab if self.quiz_game.skip_question() => {
-                self.input_buffer.clear();
-                self.clear_reject_flash();
-                // Skipping a question counts as the "wrong" outcome for
-                // sound purposes (Issue #73): the player gave up rather
-                // than landing the correct answer, so play "ブブー".
-                self.play_cue(Cue::Wrong);
-                if self.quiz_game.is_game_finished() {
-                    self.phase = Phase::Summary;
-                    return false;
+            (Some(QuizAction::Skip), _) => self.skip_current_question(),
+            (Some(QuizAction::Hint), _) => {
+                self.quiz_game.take_hint();
+            }
+            (Some(QuizAction::ReadAloud), _) => self.read_current_question(),
+            (Some(QuizAction::Mute), _) => {
+                if let Some(engine) = self.cues.as_mut() {
+                    engine.toggle_mute();
                 }
             }
-            KeyCode::Backspace => {
+            (Some(QuizAction::Erase), _) => {
                 self.input_buffer.pop();
                 self.clear_reject_flash();
             }
             // Drop any modifier-bearing chord (Ctrl+X / Alt+X) so it cannot
             // accidentally land in the typed answer.
-            KeyCode::Char(c)
+            (None, KeyCode::Char(c))
                 if !key
                     .modifiers
                     .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
@@ -481,29 +719,143 @@ impl QuizUI {
         false
     }
 
-    fn handle_key_summary(&mut self, key: KeyEvent) -> bool {
-        if key.code == KeyCode::Enter {
+    /// Tab, or a Timed question running out.
+    fn skip_current_question(&mut self) {
+        let review = self.review_for_current(true);
+        if !self.quiz_game.skip_question() {
+            return;
+        }
+        self.reviews.extend(review);
+        self.wrong_attempt = None;
+        self.streak = 0;
+        self.input_buffer.clear();
+        self.clear_reject_flash();
+        // Skipping a question counts as the "wrong" outcome for
+        // sound purposes (Issue #73): the player gave up rather
+        // than landing the correct answer, so play "ブブー".
+        self.play_cue(Cue::Wrong);
+        if self.quiz_game.is_game_finished() {
+            self.phase = Phase::Summary;
+        }
+    }
+
+    /// Practice: explain the current question if it was skipped or the
+    /// player went for a wrong choice first. Built before the game moves
+    /// on, while the question is still current.
+    fn review_for_current(&self, skipped: bool) -> Option<Review> {
+        if !self.practice || (!skipped && self.wrong_attempt.is_none()) {
+            return None;
+        }
+        let question = self.quiz_game.get_current_question()?;
+        let labels = self.quiz_game.get_choice_texts(question);
+        let plain = |idx: usize| {
+            labels
+                .get(idx)
+                .map(|label| inline_code::strip_and_locate(label).0)
+        };
+        Some(Review {
+            question: inline_code::strip_and_locate(&self.split_question_text(question).0).0,
+            went_for: self.wrong_attempt.and_then(plain),
+            answer: plain(question.correct_answer_index)?,
+            skipped,
+        })
+    }
+
+    /// Keep the cues down while a question is being read aloud.
+    fn duck_under_speech(&mut self) {
+        let speaking = self.speech.as_ref().is_some_and(|s| s.is_speaking());
+        if let Some(engine) = self.cues.as_mut() {
+            engine.set_ducked(speaking);
+        }
+    }
+
+    /// Freeze the countdown while the boss screen is up: the player
+    /// can't see the question, so the time behind it doesn't count.
+    fn hold_countdown_for_boss(&mut self, now: Instant) {
+        match (self.stealth.is_boss(), self.boss_since) {
+            (true, None) => self.boss_since = Some(now),
+            (false, Some(since)) => {
+                self.boss_since = None;
+                if let Some((_, deadline)) = self.countdown.as_mut() {
+                    *deadline += now.saturating_duration_since(since);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Tick once for each of the countdown's last three seconds.
+    fn tick_countdown(&mut self, now: Instant) {
+        if self.phase != Phase::Playing || self.stealth.is_boss() {
+            return;
+        }
+        let Some((left, _)) = self.countdown_seconds(now) else {
+            return;
+        };
+        if (1..=3).contains(&left) && self.last_tick != Some(left) {
+            self.last_tick = Some(left);
+            self.play_cue(Cue::CountdownTick);
+        }
+    }
+
+    /// Auto-skip the question once its Timed countdown has run out.
+    fn expire_countdown(&mut self, now: Instant) {
+        if self.phase != Phase::Playing || self.stealth.is_boss() {
+            return;
+        }
+        if self.countdown.is_some_and(|(_, deadline)| now >= deadline) {
+            self.countdown = None;
+            self.skip_current_question();
+        }
+    }
+
+    /// Whole seconds left on the countdown (rounded up) and the limit,
+    /// for the status bar.
+    fn countdown_seconds(&self, now: Instant) -> Option<(u32, u32)> {
+        let (limit, deadline) = self.countdown?;
+        let left = deadline.saturating_duration_since(now).min(limit);
+        Some((
+            left.as_millis().div_ceil(1000) as u32,
+            limit.as_millis().div_ceil(1000) as u32,
+        ))
+    }
+
+    fn handle_key_summary(&mut self, action: Option<QuizAction>) -> bool {
+        match action {
+            Some(QuizAction::ScrollUp) => {
+                self.summary_scroll = self.summary_scroll.saturating_sub(1);
+                return false;
+            }
+            Some(QuizAction::ScrollDown) => {
+                self.summary_scroll = self.summary_scroll.saturating_add(1);
+                return false;
+            }
+            _ => {}
+        }
+        if self.practice {
+            // Nothing to save: confirm just leaves.
+            return action == Some(QuizAction::Confirm);
+        }
+        if action == Some(QuizAction::Confirm) {
             self.phase = Phase::NamingForRecord;
             self.name_buffer.clear();
         }
         false
     }
 
-    /// Name-entry phase: printable chars accumulate, Backspace deletes,
-    /// Enter on a non-empty name saves and exits, Enter on an empty name
-    /// is a no-op (forces an explicit choice — Esc still skips the save).
-    fn handle_key_naming(&mut self, key: KeyEvent) -> bool {
+    /// Name-entry phase: printable chars accumulate, erase (Backspace)
+    /// deletes, confirm (Enter) on a non-empty name saves and exits,
+    /// confirm on an empty name is a no-op (forces an explicit choice —
+    /// quit still skips the save).
+    fn handle_key_naming(&mut self, key: KeyEvent, action: Option<QuizAction>) -> bool {
         if self.saved {
-            // Once saved, any Enter / printable key dismisses the
+            // Once saved, confirm or any printable key dismisses the
             // confirmation screen and returns to the menu.
-            if matches!(key.code, KeyCode::Enter | KeyCode::Char(_)) {
-                return true;
-            }
-            return false;
+            return action == Some(QuizAction::Confirm) || matches!(key.code, KeyCode::Char(_));
         }
 
-        match key.code {
-            KeyCode::Enter => {
+        match (action, key.code) {
+            (Some(QuizAction::Confirm), _) => {
                 if self.name_buffer.trim().is_empty() {
                     return false;
                 }
@@ -519,10 +871,10 @@ impl QuizUI {
                 }
                 self.saved = true;
             }
-            KeyCode::Backspace => {
+            (Some(QuizAction::Erase), _) => {
                 self.name_buffer.pop();
             }
-            KeyCode::Char(c)
+            (None, KeyCode::Char(c))
                 if !key
                     .modifiers
                     .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
@@ -536,17 +888,7 @@ impl QuizUI {
     }
 
     fn persist_record(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let mut records = Storage::load_records(&self.records_file_path)?;
-        let entry = ScoreEntry {
-            name: self.name_buffer.trim().to_string(),
-            score: self.quiz_game.get_final_score(),
-            cpm: self.quiz_game.get_cpm(),
-            wpm: self.quiz_game.get_wpm(),
-            ts: now_rfc3339(),
-        };
-        records.push_quiz(entry);
-        Storage::save_records(&self.records_file_path, &records)?;
-        Ok(())
+        save_record(&self.quiz_game, &self.records_file_path, &self.name_buffer)
     }
 
     fn ui(&mut self, f: &mut Frame) {
@@ -554,8 +896,29 @@ impl QuizUI {
             self.ensure_reveal_for_current_question();
         }
 
-        let frame = PaneFrame::quiz(f.area());
+        if self.stealth.is_boss() {
+            self.images.hide();
+            self.stealth.render_boss(f, f.area());
+            return;
+        }
+        if self.stealth.is_log() {
+            self.images.hide();
+            let screen = self.stealth_screen();
+            self.stealth.render_log(f, f.area(), &screen);
+            return;
+        }
+
+        let show_image = self.phase == Phase::Playing && self.images.is_active();
+        let frame = if show_image {
+            PaneFrame::quiz_with_image(f.area())
+        } else {
+            PaneFrame::quiz(f.area())
+        };
 
+        match frame.image {
+            Some(area) => self.images.render(f, area),
+            None => self.images.hide(),
+        }
         self.render_main_pane(f, frame.main);
         self.render_status_pane(f, frame.side);
         self.render_input_echo(f, frame.input_echo);
@@ -586,13 +949,32 @@ impl QuizUI {
         // or in `current_correct_typing_candidates`, so input validation
         // is unaffected.
         let mut next_code_ranges: Vec<(usize, usize)> = Vec::new();
+        let mut next_code_blocks: Vec<CodeBlock> = Vec::new();
         self.reveal = self.quiz_game.get_current_question().map(|question| {
-            let text = self.quiz_game.get_question_text(question);
-            let (stripped, ranges) = inline_code::strip_and_locate(&text);
+            let (prose, blocks) = self.split_question_text(question);
+            next_code_blocks = blocks;
+            let (stripped, ranges) = inline_code::strip_and_locate(&prose);
             next_code_ranges = ranges;
-            RevealHandle::start_at(&stripped, RevealOpts::soft_green(), now)
+            let opts = self.reveal_speed.apply(theme().reveal_opts());
+            RevealHandle::start_at(&stripped, opts, now)
+        });
+        let text_shown = self.reveal.as_ref().map_or(now, |r| {
+            let opts = self.reveal_speed.apply(theme().reveal_opts());
+            now + opts.char_interval * r.total_graphemes() as u32 + opts.fade_duration
         });
+        let choices_shown = now + Duration::from_millis(CHOICES_REVEAL_DELAY_MS + CHOICES_FADE_MS);
+        self.last_tick = None;
+        self.countdown = self
+            .quiz_game
+            .current_time_limit()
+            .map(|limit| (limit, text_shown.max(choices_shown) + limit));
         self.code_ranges = next_code_ranges;
+        self.code_blocks = next_code_blocks;
+        let image_path = self
+            .quiz_game
+            .get_current_question()
+            .and_then(|q| q.image_path.clone());
+        self.images.load(image_path.as_deref());
         // Issue #72: shuffle the four choices each question and stagger
         // their reveal so the player reads the question first, then the
         // choices fade in together a moment later.
@@ -607,6 +989,148 @@ impl QuizUI {
             self.choices_reveal_starts_at = None;
         }
         self.reveal_for_question = Some(current_idx);
+        self.read_current_question();
+    }
+
+    /// What the stealth log view shows: the same things the normal view
+    /// would (revealed question text, choices once they've faded in),
+    /// rewritten as log lines.
+    fn stealth_screen(&self) -> LogScreen {
+        let (current, total) = self.quiz_game.get_progress();
+        let mut screen = LogScreen {
+            status: match self.countdown_seconds(Instant::now()) {
+                Some((left, _)) => {
+                    format!(
+                        "データ監視中 {} ttl={left}s",
+                        self.progress_text(current, total)
+                    )
+                }
+                None => format!("データ監視中 {}", self.progress_text(current, total)),
+            },
+            quit_key: keymap().quiz.label(QuizAction::Quit),
+            ..LogScreen::default()
+        };
+        match self.phase {
+            Phase::Playing => {
+                let Some(question) = self.quiz_game.get_current_question() else {
+                    return screen;
+                };
+                let now = Instant::now();
+                let revealed: String = match self.reveal.as_ref() {
+                    Some(reveal) => reveal.snapshot(now).into_iter().map(|g| g.text).collect(),
+                    None => inline_code::strip_and_locate(&self.split_question_text(question).0).0,
+                };
+                screen.events.push(LogEvent::new(
+                    Level::Info,
+                    "query",
+                    format!("req={} q=\"{revealed}\"", question.id),
+                ));
+                for line in self.code_blocks.iter().flat_map(|b| b.lines.iter()) {
+                    screen.events.push(LogEvent::new(
+                        Level::Debug,
+                        "query.body",
+                        format!("| {line}"),
+                    ));
+                }
+                if self.choices_fade_alpha() > 0.0 {
+                    let choices = self.quiz_game.get_choice_texts(question);
+                    for (display_idx, &orig_idx) in self.choice_order.iter().enumerate() {
+                        let Some(choice) = choices.get(orig_idx) else {
+                            continue;
+                        };
+                        let text: String = inline_code::parse_inline_code(choice)
+                            .into_iter()
+                            .map(|seg| seg.text)
+                            .collect();
+                        let label = CHOICE_LABELS.get(display_idx).copied().unwrap_or("?");
+                        let dropped = self
+                            .quiz_game
+                            .current_hints()
+                            .contains(&Hint::EliminateChoice(orig_idx));
+                        screen.events.push(LogEvent::new(
+                            Level::Debug,
+                            "route",
+                            if dropped {
+                                format!("{label} -> {text} (disabled)")
+                            } else {
+                                format!("{label} -> {text}")
+                            },
+                        ));
+                    }
+                }
+                for text in self.quiz_game.current_hints().iter().filter_map(Hint::text) {
+                    screen.events.push(LogEvent::new(Level::Info, "hint", text));
+                }
+                if self.reject_flash_is_active() {
+                    screen.events.push(LogEvent::new(
+                        Level::Warn,
+                        "filter",
+                        format!("no route matches {:?}", self.rejected_char.unwrap_or(' ')),
+                    ));
+                }
+                screen.prompt = self.input_buffer.clone();
+            }
+            Phase::Summary => {
+                screen.events.push(LogEvent::new(
+                    Level::Info,
+                    "report",
+                    format!(
+                        "run complete score={} correct={}/{} cpm={} wpm={}",
+                        self.quiz_game.get_final_score(),
+                        self.quiz_game.get_correct_count(),
+                        self.quiz_game.questions_played(),
+                        self.quiz_game.get_cpm(),
+                        self.quiz_game.get_wpm(),
+                    ),
+                ));
+                for log in self.quiz_game.hint_log() {
+                    screen.events.push(LogEvent::new(
+                        Level::Info,
+                        "report",
+                        format!("hints req={} {}", log.question_id, hint_summary(log)),
+                    ));
+                }
+                for review in &self.reviews {
+                    screen
+                        .events
+                        .push(LogEvent::new(Level::Warn, "review", review.text()));
+                }
+                screen.events.push(LogEvent::new(
+                    Level::Info,
+                    "report",
+                    if self.practice {
+                        "dry run, nothing committed. Enter: close"
+                    } else {
+                        "Enter: commit record, Esc: discard"
+                    },
+                ));
+            }
+            Phase::NamingForRecord => {
+                let message = if self.saved {
+                    format!("record committed author={}", self.name_buffer.trim())
+                } else {
+                    "awaiting author name (Enter to commit)".to_string()
+                };
+                screen
+                    .events
+                    .push(LogEvent::new(Level::Info, "records", message));
+                screen.prompt = self.name_buffer.clone();
+            }
+        }
+        screen
+    }
+
+    /// Question text with fenced code blocks removed, plus the blocks.
+    /// Text without fences is returned untouched.
+    fn split_question_text(&self, question: &Question) -> (String, Vec<CodeBlock>) {
+        let text = self.quiz_game.get_question_text(question);
+        let parts = code_block::split_fenced(&text);
+        let blocks = code_block::code_blocks(&parts);
+        if blocks.is_empty() {
+            (text, blocks)
+        } else {
+            (code_block::prose_text(&parts), blocks)
+        }
     }
 
     /// Linear fade-in alpha for the choices block. Returns 0.0 while the
@@ -635,9 +1159,9 @@ impl QuizUI {
             Phase::Playing => self.render_playing_input_line(),
             Phase::Summary => Line::from(""),
             Phase::NamingForRecord => Line::from(vec![
-                Span::styled("name> ", STYLE_DIM),
-                Span::styled(self.name_buffer.clone(), STYLE_INPUT_ECHO),
-                Span::styled("_", STYLE_INPUT_ECHO),
+                Span::styled("name> ", theme().dim()),
+                Span::styled(self.name_buffer.clone(), theme().input_echo()),
+                Span::styled("_", theme().input_echo()),
             ]),
         };
         f.render_widget(Paragraph::new(line).alignment(Alignment::Left), area);
@@ -654,20 +1178,20 @@ impl QuizUI {
             Span::styled(
                 prompt.to_string(),
                 if flash_active {
-                    STYLE_INCORRECT
+                    theme().incorrect()
                 } else {
-                    STYLE_DIM
+                    theme().dim()
                 },
             ),
-            Span::styled(self.input_buffer.clone(), STYLE_CORRECT),
+            Span::styled(self.input_buffer.clone(), theme().correct()),
         ];
         if flash_active {
             if let Some(c) = self.rejected_char {
-                spans.push(Span::styled(c.to_string(), STYLE_INCORRECT));
+                spans.push(Span::styled(c.to_string(), theme().incorrect()));
             }
-            spans.push(Span::styled("_", STYLE_INCORRECT));
+            spans.push(Span::styled("_", theme().incorrect()));
         } else {
-            spans.push(Span::styled("_", STYLE_INPUT_ECHO));
+            spans.push(Span::styled("_", theme().input_echo()));
         }
         Line::from(spans)
     }
@@ -683,6 +1207,11 @@ impl QuizUI {
         // the last valid prefix at this point (we only push validated
         // characters), so leaving it alone is the correct behaviour.
         if !self.quiz_game.is_valid_correct_typed_prefix(&attempted) {
+            if self.practice {
+                if let Some(idx) = self.quiz_game.wrong_choice_for_prefix(&attempted) {
+                    self.wrong_attempt = Some(idx);
+                }
+            }
             self.note_rejected_char(c);
             // Mistype cue (Issue #73) — slightly louder than the
             // keystroke tick so the player can tell them apart.
@@ -714,11 +1243,18 @@ impl QuizUI {
     /// the elapsed time).
     fn submit_current_answer(&mut self) {
         let typed = self.input_buffer.clone();
+        let review = self.review_for_current(false);
         if self.quiz_game.answer_question_typed(&typed).is_some() {
+            self.reviews.extend(review);
+            self.wrong_attempt = None;
             // Issue #70 limits this code path to correct answers (the
             // input layer rejects wrong ones), so this is always the
             // "ピンポーン" cue. Issue #73.
             self.play_cue(Cue::Correct);
+            self.streak += 1;
+            if self.streak >= COMBO_MIN {
+                self.play_cue(Cue::Combo);
+            }
             self.input_buffer.clear();
             self.clear_reject_flash();
             if self.quiz_game.is_game_finished() {
@@ -756,38 +1292,86 @@ impl QuizUI {
 
     fn render_status_pane(&self, f: &mut Frame, area: Rect) {
         let elapsed = self.quiz_game.get_total_time().unwrap_or(Duration::ZERO);
-        let pane = StatusPane::quiz(
+        let mut pane = StatusPane::quiz(
             self.quiz_game.get_final_score(),
             elapsed,
             self.quiz_game.get_cpm(),
             self.quiz_game.get_wpm(),
         );
+        if let Some((left, limit)) = self.countdown_seconds(Instant::now()) {
+            pane.items.push(StatusItem::bar("Limit", left, limit));
+        }
         pane.render(f, area);
     }
 
     fn render_main_pane(&self, f: &mut Frame, area: Rect) {
+        let card = self.practice_card();
+        let card_height = card.as_ref().map_or(0, |lines| lines.len() as u16 + 2);
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(3), Constraint::Min(6)])
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(card_height),
+                Constraint::Min(6),
+            ])
             .split(area);
 
         let (current, total) = self.quiz_game.get_progress();
+        let label = match (self.practice, self.quiz_game.variant()) {
+            (false, variant) => variant.label(),
+            (true, QuizVariant::Count(QUIZ_RUN_LENGTH)) => "Practice".to_string(),
+            (true, variant) => format!("Practice · {}", variant.label()),
+        };
         let title_text = match self.phase {
-            Phase::Playing => format!("type-globe - Quiz {current}/{total}"),
-            Phase::Summary => "type-globe - Quiz".to_string(),
-            Phase::NamingForRecord => "type-globe - Quiz".to_string(),
+            Phase::Playing => {
+                format!(
+                    "type-globe - {label} {}",
+                    self.progress_text(current, total)
+                )
+            }
+            Phase::Summary => format!("type-globe - {label}"),
+            Phase::NamingForRecord => format!("type-globe - {label}"),
         };
+        let mut title_block = Block::default().borders(Borders::ALL);
+        // Practice: the last explanation sits on the title's bottom border
+        // while the next question plays.
+        if let Some(review) = self.reviews.last().filter(|_| self.phase == Phase::Playing) {
+            title_block = title_block.title_bottom(Span::styled(
+                format!(" Review: {} ", review.text()),
+                theme().dim(),
+            ));
+        }
         let title = Paragraph::new(title_text)
-            .style(STYLE_TITLE)
+            .style(theme().title())
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL));
+            .block(title_block);
         f.render_widget(title, chunks[0]);
+        if let Some(card) = card {
+            let body = Paragraph::new(card)
+                .wrap(ratatui::widgets::Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .title(" Previous question ")
+                        .borders(Borders::ALL),
+                );
+            f.render_widget(body, chunks[1]);
+        }
 
         match self.phase {
-            Phase::Summary => self.render_summary(f, chunks[1]),
-            Phase::NamingForRecord => self.render_naming(f, chunks[1]),
-            Phase::Playing => self.render_question(f, chunks[1]),
+            Phase::Summary => self.render_summary(f, chunks[2]),
+            Phase::NamingForRecord => self.render_naming(f, chunks[2]),
+            Phase::Playing => self.render_question(f, chunks[2]),
+        }
+    }
+
+    /// Practice: the explanation card of the question just played, shown
+    /// above the next one.
+    fn practice_card(&self) -> Option<Vec<Line<'static>>> {
+        if !self.practice || self.phase != Phase::Playing {
+            return None;
         }
+        let question = self.quiz_game.played_questions().last()?;
+        Some(explanation_card(question, self.quiz_game.language(), None))
     }
 
     /// Build the question's text as a styled `Line`, using the reveal
@@ -805,11 +1389,11 @@ impl QuizUI {
                 // inline-code graphemes with the Bold modifier so the
                 // code span is visually distinct from the start. Once
                 // the reveal is fully settled, the fg color of code
-                // graphemes is replaced with `INLINE_CODE_COLOR` for a
+                // graphemes is replaced with the theme's `inline_code` for a
                 // stronger highlight in the steady-state view.
                 //
                 // Followup idea (#97): interpolate the code fg toward
-                // `INLINE_CODE_COLOR` over the final fade window so the
+                // `inline_code` over the final fade window so the
                 // settle reads as a deliberate "pop" instead of a hard
                 // swap. Out of scope for the initial implementation.
                 let settled = reveal.is_done(Instant::now());
@@ -818,17 +1402,12 @@ impl QuizUI {
                     .enumerate()
                     .map(|(i, g)| {
                         let in_code = self.code_ranges.iter().any(|&(s, e)| i >= s && i < e);
-                        let style = if in_code {
-                            let fg = if settled {
-                                INLINE_CODE_COLOR
-                            } else {
-                                let Rgb(r, gc, b) = g.color;
-                                Color::Rgb(r, gc, b)
-                            };
-                            Style::new().fg(fg).add_modifier(Modifier::BOLD)
-                        } else {
-                            let Rgb(r, gc, b) = g.color;
-                            Style::new().fg(Color::Rgb(r, gc, b))
+                        let style = match (in_code, settled) {
+                            (true, true) => theme().inline_code(),
+                            (true, false) => theme()
+                                .frame_style(theme().question_fade, g.color)
+                                .add_modifier(Modifier::BOLD),
+                            (false, _) => theme().frame_style(theme().question_fade, g.color),
                         };
                         Span::styled(g.text, style)
                     })
@@ -839,8 +1418,8 @@ impl QuizUI {
         // Fallback path (no active reveal): parse the raw text directly
         // so backticks are stripped and code spans get the inline-code
         // style applied.
-        let text = self.quiz_game.get_question_text(question);
-        Line::from(spans_from_inline_code(&text, STYLE_NORMAL))
+        let (text, _) = self.split_question_text(question);
+        Line::from(spans_from_inline_code(&text, theme().text()))
     }
 
     fn render_question(&self, f: &mut Frame, area: Rect) {
@@ -855,6 +1434,19 @@ impl QuizUI {
                 // it absorbs whatever vertical space is left.
                 .constraints([Constraint::Length(5), Constraint::Min(6)])
                 .split(area);
+            // Code questions: the snippet sits between question and
+            // choices. Choices shrink to their natural height (4 rows +
+            // border + padding) and the code pane takes the rest.
+            let chunks = if self.code_blocks.is_empty() {
+                chunks
+            } else {
+                let code_area = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(3), Constraint::Length(CHOICES_PANE_HEIGHT)])
+                    .split(chunks[1]);
+                self.render_code_blocks(f, code_area[0]);
+                std::rc::Rc::from([chunks[0], code_area[1]])
+            };
 
             let question_line = self.question_reveal_line(question);
             let question_paragraph = Paragraph::new(question_line)
@@ -869,28 +1461,35 @@ impl QuizUI {
             f.render_widget(question_paragraph, chunks[0]);
 
             // Plain non-highlighted list — typed selection means there is no
-            // "currently focused" choice. Labels A/B/C/D match docs/spec.md.
-            // Issue #72: display order is the shuffled `choice_order`, not
-            // the original index, so the answer's position varies per
-            // question and the four choices fade in together after the
-            // question text is on screen.
-            const LABELS: [&str; 4] = ["A", "B", "C", "D"];
+            // "currently focused" choice. Issue #72: display order is the
+            // shuffled `choice_order`, not the original index, so the
+            // answer's position varies per question and the four choices
+            // fade in together after the question text is on screen.
             let alpha = self.choices_fade_alpha();
-            let label_color = lerp_rgb_color(Rgb(20, 60, 80), Rgb(80, 200, 255), alpha);
-            let text_color = lerp_rgb_color(Rgb(20, 20, 20), Rgb(255, 255, 255), alpha);
-            let label_style = Style::new().fg(label_color).add_modifier(Modifier::BOLD);
-            let text_style = Style::new().fg(text_color);
+            let label_style = theme()
+                .fade_style(theme().choice_label_fade, alpha)
+                .add_modifier(Modifier::BOLD);
+            let text_style = theme().fade_style(theme().choice_text_fade, alpha);
             let order: Vec<usize> = if self.choice_order.len() == choices.len() {
                 self.choice_order.clone()
             } else {
                 (0..choices.len()).collect()
             };
+            let hints = self.quiz_game.current_hints();
+            let struck = |idx: usize| hints.contains(&Hint::EliminateChoice(idx));
             let choice_items: Vec<ListItem> = order
                 .iter()
                 .enumerate()
                 .filter_map(|(display_idx, &orig_idx)| {
-                    let label = LABELS.get(display_idx).copied().unwrap_or("?");
+                    let label = CHOICE_LABELS.get(display_idx).copied().unwrap_or("?");
                     let choice = choices.get(orig_idx)?.clone();
+                    if struck(orig_idx) {
+                        let style = theme().dim().add_modifier(Modifier::CROSSED_OUT);
+                        return Some(ListItem::new(Line::from(Span::styled(
+                            format!("{label}) {}", inline_code::strip_and_locate(&choice).0),
+                            style,
+                        ))));
+                    }
                     // Issue #97: parse Markdown inline-code in the choice
                     // text so backticks are stripped and `code` spans get
                     // the inline-code highlight. Non-code text inherits
@@ -902,37 +1501,79 @@ impl QuizUI {
                 })
                 .collect();
 
-            let choices_list = List::new(choice_items).block(
-                Block::default()
-                    .title(" Choices ")
-                    .borders(Borders::ALL)
-                    .padding(Padding::uniform(1)),
-            );
+            let mut choices_block = Block::default()
+                .title(" Choices ")
+                .borders(Borders::ALL)
+                .padding(Padding::uniform(1));
+            let hint_text: Vec<String> = hints.iter().filter_map(Hint::text).collect();
+            if !hint_text.is_empty() {
+                choices_block = choices_block.title_bottom(Span::styled(
+                    format!(" Hint: {} ", hint_text.join(", ")),
+                    theme().section(),
+                ));
+            }
+            let choices_list = List::new(choice_items).block(choices_block);
 
             f.render_widget(choices_list, chunks[1]);
         } else {
             let no_question = Paragraph::new("No question available")
-                .style(Style::default().fg(Color::Red))
+                .style(theme().issue())
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::ALL));
             f.render_widget(no_question, area);
         }
     }
 
+    /// Draw the current question's fenced code blocks, highlighted and
+    /// line-numbered, separated by a blank row.
+    fn render_code_blocks(&self, f: &mut Frame, area: Rect) {
+        let tag = self
+            .code_blocks
+            .first()
+            .map(|b| b.tag.as_str())
+            .filter(|t| !t.is_empty());
+        let title = match tag {
+            Some(tag) => format!(" Code ({tag}) "),
+            None => " Code ".to_string(),
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .padding(Padding::horizontal(1));
+        let width = block.inner(area).width;
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        for (idx, code) in self.code_blocks.iter().enumerate() {
+            if idx > 0 {
+                lines.push(Line::default());
+            }
+            lines.extend(code_block::render_lines(code, width));
+        }
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
     fn render_help_line(&self, f: &mut Frame, area: Rect) {
         self.help_line().render(f, area);
     }
 
+    /// "3/10" for runs with a known length; Survival has none, so it
+    /// just counts up.
+    fn progress_text(&self, current: usize, total: usize) -> String {
+        match self.quiz_game.variant() {
+            QuizVariant::Survival => current.to_string(),
+            _ => format!("{current}/{total}"),
+        }
+    }
+
     fn render_summary(&self, f: &mut Frame, area: Rect) {
-        let total = self.quiz_game.get_progress().1 as u32;
+        let total = self.quiz_game.questions_played();
         let correct = self.quiz_game.get_correct_count();
         let accuracy_pct = (self.quiz_game.get_accuracy() * 100.0).round() as u32;
         let elapsed = self.quiz_game.get_total_time().unwrap_or(Duration::ZERO);
         let mins = elapsed.as_secs() / 60;
         let secs = elapsed.as_secs() % 60;
 
-        let lines = vec![
-            Line::from(Span::styled("Run complete", STYLE_CORRECT)),
+        let mut lines = vec![
+            Line::from(Span::styled("Run complete", theme().correct())),
             Line::from(""),
             Line::from(format!("  Score    : {}", self.quiz_game.get_final_score())),
             Line::from(format!("  Correct  : {correct} / {total}")),
@@ -940,33 +1581,101 @@ impl QuizUI {
             Line::from(format!("  CPM      : {}", self.quiz_game.get_cpm())),
             Line::from(format!("  WPM      : {}", self.quiz_game.get_wpm())),
             Line::from(format!("  Time     : {mins}:{secs:02}")),
-            Line::from(""),
-            Line::from(Span::styled(
-                "Press Enter to register a record (Esc to skip).",
-                STYLE_NORMAL,
+            Line::from(format!(
+                "  Hints    : {hinted} question(s), -{hint_cost} pts",
+                hinted = self.quiz_game.hint_log().len(),
+                hint_cost = self
+                    .quiz_game
+                    .hint_log()
+                    .iter()
+                    .map(HintLog::cost)
+                    .sum::<u32>(),
             )),
         ];
+        for log in self.quiz_game.hint_log() {
+            lines.push(Line::from(Span::styled(
+                format!("    {}: {}", log.question_id, hint_summary(log)),
+                theme().dim(),
+            )));
+        }
+        if !self.reviews.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Review", theme().section())));
+            for review in &self.reviews {
+                lines.push(Line::from(format!("  {}", review.text())));
+            }
+        }
+        let prompt = if self.practice {
+            "Practice run — nothing is saved. Press Enter to return."
+        } else {
+            "Press Enter to register a record (Esc to skip)."
+        };
+        lines.extend([
+            Line::from(""),
+            Line::from(Span::styled(prompt, theme().text())),
+        ]);
 
-        let body = Paragraph::new(lines).alignment(Alignment::Left).block(
-            Block::default()
-                .title(" Summary ")
-                .borders(Borders::ALL)
-                .padding(Padding::uniform(1)),
-        );
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(area);
+        let body = Paragraph::new(lines)
+            .alignment(Alignment::Left)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .block(
+                Block::default()
+                    .title(" Summary ")
+                    .borders(Borders::ALL)
+                    .padding(Padding::uniform(1)),
+            );
+        f.render_widget(body, columns[0]);
+        self.render_explanations(f, columns[1]);
+    }
+
+    /// One card per question played (a practice retry only once): the
+    /// question, its answer, and the optional explanation / source.
+    fn explanation_lines(&self) -> Vec<Line<'static>> {
+        let language = self.quiz_game.language();
+        let mut seen: Vec<&str> = Vec::new();
+        let mut lines = Vec::new();
+        for question in self.quiz_game.played_questions() {
+            if seen.contains(&question.id.as_str()) {
+                continue;
+            }
+            seen.push(&question.id);
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.extend(explanation_card(question, language, Some(seen.len())));
+        }
+        lines
+    }
+
+    fn render_explanations(&self, f: &mut Frame, area: Rect) {
+        let lines = self.explanation_lines();
+        let body = Paragraph::new(lines)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .scroll((self.summary_scroll, 0))
+            .block(
+                Block::default()
+                    .title(" Explanations ")
+                    .borders(Borders::ALL)
+                    .padding(Padding::horizontal(1)),
+            );
         f.render_widget(body, area);
     }
 
     fn render_naming(&self, f: &mut Frame, area: Rect) {
         let lines = if self.saved {
             vec![
-                Line::from(Span::styled("Record saved.", STYLE_CORRECT)),
+                Line::from(Span::styled("Record saved.", theme().correct())),
                 Line::from(""),
                 Line::from(format!("  Name  : {}", self.name_buffer.trim())),
                 Line::from(format!("  Score : {}", self.quiz_game.get_final_score())),
                 Line::from(""),
                 Line::from(Span::styled(
                     "Press any key to return to the menu.",
-                    STYLE_NORMAL,
+                    theme().text(),
                 )),
             ]
         } else {
@@ -975,12 +1684,12 @@ impl QuizUI {
                 Line::from(""),
                 Line::from(Span::styled(
                     format!("  name : {}_", self.name_buffer),
-                    STYLE_INPUT_ECHO,
+                    theme().input_echo(),
                 )),
                 Line::from(""),
                 Line::from(Span::styled(
                     format!("(max {NAME_MAX_CHARS} chars; Enter saves, Esc skips)"),
-                    STYLE_NORMAL,
+                    theme().text(),
                 )),
             ]
         };
@@ -995,31 +1704,145 @@ impl QuizUI {
     }
 
     fn help_line(&self) -> HelpLine {
+        let keys = &keymap().quiz;
         match self.phase {
-            Phase::Playing => HelpLine::new(vec![
-                HelpEntry::new("Esc", "Quit"),
-                HelpEntry::new("Tab", "Skip"),
-                HelpEntry::new("Auto", "Confirm"),
-                HelpEntry::new("Bksp", "Erase"),
+            Phase::Playing => {
+                let mut entries = vec![
+                    keys.help(QuizAction::Quit),
+                    keys.help(QuizAction::Skip),
+                    keys.help(QuizAction::Hint),
+                    // Answers confirm themselves on the last keystroke.
+                    HelpEntry::new("Auto", "Confirm"),
+                    keys.help(QuizAction::Erase),
+                ];
+                if self.speech.is_some() {
+                    entries.push(keys.help(QuizAction::ReadAloud));
+                }
+                match self.cues.as_ref() {
+                    Some(engine) if engine.is_muted() => {
+                        entries.push(keys.help_as(QuizAction::Mute, "Unmute"))
+                    }
+                    Some(_) => entries.push(keys.help(QuizAction::Mute)),
+                    None => {}
+                }
+                entries.push(Stealth::help_entry());
+                HelpLine::new(entries)
+            }
+            Phase::Summary if self.practice => HelpLine::new(vec![
+                keys.help_as(QuizAction::Confirm, "Menu"),
+                keys.help_pair(QuizAction::ScrollUp, QuizAction::ScrollDown, "Scroll"),
             ]),
             Phase::Summary => HelpLine::new(vec![
-                HelpEntry::new("Esc", "Skip"),
-                HelpEntry::new("Enter", "Register"),
+                keys.help_as(QuizAction::Quit, "Skip"),
+                keys.help_as(QuizAction::Confirm, "Register"),
+                keys.help_pair(QuizAction::ScrollUp, QuizAction::ScrollDown, "Scroll"),
             ]),
             Phase::NamingForRecord if self.saved => {
-                HelpLine::new(vec![HelpEntry::new("Enter", "Menu")])
+                HelpLine::new(vec![keys.help_as(QuizAction::Confirm, "Menu")])
             }
             Phase::NamingForRecord => HelpLine::new(vec![
-                HelpEntry::new("Esc", "Skip"),
-                HelpEntry::new("Enter", "Save"),
-                HelpEntry::new("Bksp", "Erase"),
+                keys.help_as(QuizAction::Quit, "Skip"),
+                keys.help_as(QuizAction::Confirm, "Save"),
+                keys.help(QuizAction::Erase),
             ]),
         }
     }
 }
 
+/// File a finished run under `name` in the variant's Records list. Shared
+/// with the plain-text quiz (`ui::plain`).
+pub(crate) fn save_record(
+    game: &QuizGame,
+    records_file_path: &str,
+    name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut records = Storage::load_records(records_file_path)?;
+    let entry = ScoreEntry {
+        name: name.trim().to_string(),
+        score: game.get_final_score(),
+        cpm: game.get_cpm(),
+        wpm: game.get_wpm(),
+        ts: now_rfc3339(),
+    };
+    game.variant().push_record(&mut records, entry);
+    Storage::save_records(records_file_path, &records)?;
+    Ok(())
+}
+
+/// What read-aloud says for `question` (#46): its reading form, then with
+/// `ReadAloud::Choices` each choice after its on-screen letter, in the
+/// shuffled `order`. Code fences and inline-code backticks are dropped,
+/// and the `ja_typings` — the typing target — are never spoken.
+fn aloud_text(
+    question: &Question,
+    language: &Language,
+    mode: ReadAloud,
+    order: &[usize],
+) -> String {
+    let reading = DataLoader::get_question_reading_text(question, language);
+    let prose = code_block::prose_text(&code_block::split_fenced(&reading));
+    let mut text = inline_code::strip_and_locate(&prose).0;
+    if mode == ReadAloud::Choices {
+        for (label, choice) in CHOICE_LABELS
+            .iter()
+            .zip(order.iter().filter_map(|&idx| question.choices.get(idx)))
+        {
+            let choice_text = DataLoader::get_choice_text(choice, language);
+            let choice_text = inline_code::strip_and_locate(&choice_text).0;
+            text.push_str(&format!(" {label}: {choice_text}."));
+        }
+    }
+    text
+}
+
+/// Lines for one explanation card: the question (numbered on the
+/// Summary), its answer, then the explanation and source when the
+/// question has them. Inline code is highlighted throughout.
+fn explanation_card(
+    question: &Question,
+    language: &Language,
+    number: Option<usize>,
+) -> Vec<Line<'static>> {
+    let text = DataLoader::get_question_text(question, language);
+    let prose = code_block::prose_text(&code_block::split_fenced(&text));
+    let prefix = number.map(|n| format!("{n}. ")).unwrap_or_default();
+    let mut heading = vec![Span::styled(prefix, theme().section())];
+    heading.extend(spans_from_inline_code(&prose, theme().section()));
+    let answer = question
+        .choices
+        .get(question.correct_answer_index)
+        .map(|choice| DataLoader::get_choice_text(choice, language))
+        .unwrap_or_default();
+    let mut answer_line = vec![Span::styled("   → ", theme().dim())];
+    answer_line.extend(spans_from_inline_code(&answer, theme().correct()));
+    let mut lines = vec![Line::from(heading), Line::from(answer_line)];
+    if let Some(explanation) = DataLoader::get_explanation_text(question, language) {
+        let mut spans = vec![Span::raw("   ")];
+        spans.extend(spans_from_inline_code(&explanation, theme().text()));
+        lines.push(Line::from(spans));
+    }
+    let source = match (&question.source, &question.reference) {
+        (Some(source), Some(reference)) => Some(format!("{source} <{reference}>")),
+        (Some(only), None) | (None, Some(only)) => Some(only.clone()),
+        (None, None) => None,
+    };
+    if let Some(source) = source {
+        lines.push(Line::from(Span::styled(
+            format!("   Source: {source}"),
+            theme().dim(),
+        )));
+    }
+    lines
+}
+
+/// "eliminate, length (-50)" — one question's hints for the summary.
+fn hint_summary(log: &HintLog) -> String {
+    let names: Vec<&str> = log.hints.iter().map(Hint::name).collect();
+    format!("{} (-{})", names.join(", "), log.cost())
+}
+
 /// Build a `Vec<Span>` for `text`, stripping Markdown inline-code
-/// backticks and styling each code span with `INLINE_CODE_COLOR` + Bold
+/// backticks and styling each code span with the theme's inline-code style
 /// while keeping non-code text styled with `base_style` (Issue #97).
 /// Used by both the question fallback path (no active reveal) and the
 /// choices renderer.
@@ -1035,12 +1858,7 @@ fn spans_from_inline_code(text: &str, base_style: Style) -> Vec<Span<'static>> {
         .into_iter()
         .map(|seg| {
             if seg.is_code {
-                Span::styled(
-                    seg.text,
-                    Style::new()
-                        .fg(INLINE_CODE_COLOR)
-                        .add_modifier(Modifier::BOLD),
-                )
+                Span::styled(seg.text, theme().inline_code())
             } else {
                 Span::styled(seg.text, base_style)
             }
@@ -1048,14 +1866,6 @@ fn spans_from_inline_code(text: &str, base_style: Style) -> Vec<Span<'static>> {
         .collect()
 }
 
-/// Interpolate two `Rgb` triples and return a ratatui `Color`. Thin
-/// wrapper over `jiwa::lerp_rgb` so the choices fade-in (Issue #72)
-/// uses the same channel math as the question text reveal.
-fn lerp_rgb_color(from: Rgb, to: Rgb, t: f32) -> Color {
-    let Rgb(r, g, b) = lerp_rgb(from, to, t);
-    Color::Rgb(r, g, b)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1063,7 +1873,7 @@ mod tests {
     #[test]
     fn spans_from_inline_code_strips_backticks() {
         // Issue #97: backticks must not leak into the rendered spans.
-        let spans = spans_from_inline_code("HTMLの `alt` 属性", STYLE_NORMAL);
+        let spans = spans_from_inline_code("HTMLの `alt` 属性", theme().text());
         let all_text: String = spans.iter().map(|s| s.content.as_ref()).collect();
         assert!(
             !all_text.contains('`'),
@@ -1072,27 +1882,27 @@ mod tests {
         assert_eq!(all_text, "HTMLの alt 属性");
         // The code segment must carry the inline-code style.
         let code_span = spans.iter().find(|s| s.content.as_ref() == "alt").unwrap();
-        assert_eq!(code_span.style.fg, Some(INLINE_CODE_COLOR));
+        assert_eq!(code_span.style.fg, theme().inline_code().fg);
         assert!(code_span.style.add_modifier.contains(Modifier::BOLD));
     }
 
     #[test]
     fn spans_from_inline_code_plain_text_unchanged() {
-        let spans = spans_from_inline_code("just text", STYLE_NORMAL);
+        let spans = spans_from_inline_code("just text", theme().text());
         let all_text: String = spans.iter().map(|s| s.content.as_ref()).collect();
         assert_eq!(all_text, "just text");
-        assert!(spans.iter().all(|s| s.style.fg == STYLE_NORMAL.fg));
+        assert!(spans.iter().all(|s| s.style.fg == theme().text().fg));
     }
 
     #[test]
     fn spans_from_inline_code_multiple_spans() {
-        let spans = spans_from_inline_code("use `let` and `mut` here", STYLE_NORMAL);
+        let spans = spans_from_inline_code("use `let` and `mut` here", theme().text());
         let all_text: String = spans.iter().map(|s| s.content.as_ref()).collect();
         assert!(!all_text.contains('`'));
         assert_eq!(all_text, "use let and mut here");
         let code_spans: Vec<_> = spans
             .iter()
-            .filter(|s| s.style.fg == Some(INLINE_CODE_COLOR))
+            .filter(|s| s.style.fg == theme().inline_code().fg)
             .collect();
         assert_eq!(code_spans.len(), 2);
     }
@@ -1106,7 +1916,16 @@ mod tests {
         ja_typings: Vec<String>,
         language: Language,
     ) -> QuizUI {
-        use crate::types::{Choice, Question};
+        let question = make_question_with_choice(ja_label, en_label, ja_typings);
+        QuizUI::from_pool_with_count(&[question], language, "/tmp/records-demo.yaml".into(), 1)
+    }
+
+    fn make_question_with_choice(
+        ja_label: &str,
+        en_label: &str,
+        ja_typings: Vec<String>,
+    ) -> Question {
+        use crate::types::Choice;
         use std::collections::HashMap;
 
         let mut labels = HashMap::new();
@@ -1130,7 +1949,7 @@ mod tests {
         question_text.insert("ja".to_string(), "テスト".to_string());
         question_text.insert("en".to_string(), "test".to_string());
 
-        let question = Question {
+        Question {
             id: "q-demo-fallback".into(),
             genre: "test".into(),
             question_text,
@@ -1139,9 +1958,10 @@ mod tests {
             correct_answer_index: 0,
             image_path: None,
             ja_reviewed: false,
-        };
-
-        QuizUI::from_pool_with_count(&[question], language, "/tmp/records-demo.yaml".into(), 1)
+            explanation: HashMap::new(),
+            source: None,
+            reference: None,
+        }
     }
 
     #[test]
@@ -1172,6 +1992,141 @@ mod tests {
         );
     }
 
+    #[test]
+    fn timed_question_auto_skips_when_the_countdown_runs_out() {
+        let mut ui = make_quiz_ui_with_choice("", "Tokyo", Vec::new(), Language::English);
+        let question = ui
+            .quiz_game
+            .get_current_question()
+            .cloned()
+            .expect("question");
+        ui.quiz_game = QuizGame::for_variant(&[question], Language::English, &QuizVariant::Timed);
+        ui.quiz_game.start();
+        ui.ensure_reveal_for_current_question();
+
+        let (limit, deadline) = ui.countdown.expect("Timed questions get a countdown");
+        assert!(
+            deadline > Instant::now() + limit,
+            "countdown waits for the reveal"
+        );
+        let (left, max) = ui.countdown_seconds(Instant::now()).expect("seconds");
+        assert_eq!(left, max);
+
+        ui.expire_countdown(deadline - Duration::from_millis(1));
+        assert_eq!(ui.phase, Phase::Playing);
+        ui.expire_countdown(deadline);
+        assert_eq!(ui.phase, Phase::Summary);
+        assert_eq!(ui.quiz_game.get_correct_count(), 0);
+    }
+
+    #[test]
+    fn countdown_ticks_once_per_final_second() {
+        let mut ui = make_quiz_ui_with_choice("", "Tokyo", Vec::new(), Language::English);
+        let question = ui
+            .quiz_game
+            .get_current_question()
+            .cloned()
+            .expect("question");
+        ui.quiz_game = QuizGame::for_variant(&[question], Language::English, &QuizVariant::Timed);
+        ui.quiz_game.start();
+        ui.ensure_reveal_for_current_question();
+        let (_, deadline) = ui.countdown.expect("countdown");
+
+        ui.tick_countdown(deadline - Duration::from_secs(5));
+        assert_eq!(ui.last_tick, None, "no tick before the last three seconds");
+        ui.tick_countdown(deadline - Duration::from_millis(2_500));
+        assert_eq!(ui.last_tick, Some(3));
+        ui.tick_countdown(deadline - Duration::from_millis(900));
+        assert_eq!(ui.last_tick, Some(1));
+    }
+
+    #[test]
+    fn a_skip_breaks_the_streak() {
+        let mut ui = make_quiz_ui_with_choice("", "Tokyo", Vec::new(), Language::English);
+        ui.streak = COMBO_MIN;
+        ui.skip_current_question();
+        assert_eq!(ui.streak, 0);
+    }
+
+    #[test]
+    fn practice_explains_misses_and_never_reaches_naming() {
+        let mut ui = make_quiz_ui_with_choice("", "Tokyo", Vec::new(), Language::English)
+            .with_practice(true);
+        ui.ensure_reveal_for_current_question();
+        // "d" heads for the "dummy" choice (its en label is missing, so
+        // it types as its ja label), which the input layer rejects.
+        ui.handle_playing_char('d');
+        assert_eq!(ui.wrong_attempt, Some(1));
+        ui.skip_current_question();
+        assert_eq!(
+            ui.reviews,
+            [Review {
+                question: "test".into(),
+                went_for: Some("dummy".into()),
+                answer: "Tokyo".into(),
+                skipped: true,
+            }]
+        );
+        assert_eq!(ui.phase, Phase::Playing, "the skipped question comes back");
+
+        for c in "tokyo".chars() {
+            ui.handle_playing_char(c);
+        }
+        assert_eq!(ui.phase, Phase::Summary);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(ui.handle_key(enter), "Enter leaves a practice summary");
+        assert_eq!(ui.phase, Phase::Summary);
+        assert!(!ui.saved);
+    }
+
+    #[test]
+    fn explanation_cards_list_each_played_question_once() {
+        let mut question = make_question_with_choice("", "Tokyo", Vec::new());
+        question
+            .explanation
+            .insert("en".into(), "Capital since `1868`.".into());
+        question.source = Some("Almanac".into());
+        question.reference = Some("https://example.org".into());
+        let mut ui =
+            QuizUI::from_pool_with_count(&[question], Language::English, "/tmp/r.yaml".into(), 1)
+                .with_practice(true);
+        ui.ensure_reveal_for_current_question();
+        assert!(ui.practice_card().is_none(), "nothing played yet");
+        ui.skip_current_question();
+        let card = ui.practice_card().expect("card for the skipped question");
+        let text: Vec<String> = card
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            text,
+            [
+                "test",
+                "   → Tokyo",
+                "   Capital since 1868.",
+                "   Source: Almanac <https://example.org>",
+            ]
+        );
+
+        for c in "tokyo".chars() {
+            ui.handle_playing_char(c);
+        }
+        assert_eq!(ui.phase, Phase::Summary);
+        let lines = ui.explanation_lines();
+        assert_eq!(lines.len(), 4, "the retried question has one card");
+
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        assert!(!ui.handle_key(down));
+        assert_eq!(ui.summary_scroll, 1);
+    }
+
+    #[test]
+    fn untimed_questions_have_no_countdown() {
+        let mut ui = make_quiz_ui_with_choice("", "Tokyo", Vec::new(), Language::English);
+        ui.ensure_reveal_for_current_question();
+        assert!(ui.countdown.is_none());
+    }
+
     #[test]
     fn handle_key_esc_sets_user_aborted_flag() {
         // S-7: pressing Esc must flag `user_aborted` so run_with_demo
@@ -1207,6 +2162,82 @@ mod tests {
         assert!(ui.user_aborted, "Ctrl+C must record user abort");
     }
 
+    #[test]
+    fn boss_key_swallows_typing_until_dismissed() {
+        let mut ui = make_quiz_ui_with_choice(
+            "東京",
+            "Tokyo",
+            vec!["toukyou".to_string()],
+            Language::Japanese,
+        );
+        let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert!(!ui.handle_key(press(KeyCode::F(12))));
+        assert!(!ui.handle_key(press(KeyCode::Char('t'))));
+        assert!(
+            ui.input_buffer.is_empty(),
+            "boss screen must not leak input"
+        );
+        assert!(!ui.handle_key(press(KeyCode::F(12))));
+        assert!(!ui.handle_key(press(KeyCode::Char('t'))));
+        assert_eq!(ui.input_buffer, "t");
+    }
+
+    #[test]
+    fn countdown_stands_still_behind_the_boss_screen() {
+        let mut ui = make_quiz_ui_with_choice("", "Tokyo", Vec::new(), Language::English);
+        let question = ui
+            .quiz_game
+            .get_current_question()
+            .cloned()
+            .expect("question");
+        ui.quiz_game = QuizGame::for_variant(&[question], Language::English, &QuizVariant::Timed);
+        ui.quiz_game.start();
+        ui.ensure_reveal_for_current_question();
+        let (_, deadline) = ui.countdown.expect("countdown");
+        let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        let hidden_at = deadline - Duration::from_secs(2);
+        ui.handle_key(press(KeyCode::F(12)));
+        ui.hold_countdown_for_boss(hidden_at);
+        ui.tick_countdown(deadline);
+        ui.expire_countdown(deadline + Duration::from_secs(5));
+        assert_eq!(ui.phase, Phase::Playing, "no skip behind the boss screen");
+        assert_eq!(ui.last_tick, None, "no ticks behind the boss screen");
+
+        let back_at = hidden_at + Duration::from_secs(10);
+        ui.handle_key(press(KeyCode::F(12)));
+        ui.hold_countdown_for_boss(back_at);
+        let (_, resumed) = ui.countdown.expect("countdown survives");
+        assert_eq!(resumed, deadline + Duration::from_secs(10));
+        ui.expire_countdown(back_at + Duration::from_secs(1));
+        assert_eq!(ui.phase, Phase::Playing, "two seconds were left");
+        ui.expire_countdown(resumed);
+        assert_eq!(ui.phase, Phase::Summary);
+    }
+
+    #[test]
+    fn stealth_screen_only_shows_revealed_text() {
+        let mut ui = make_quiz_ui_with_choice(
+            "東京",
+            "Tokyo",
+            vec!["toukyou".to_string()],
+            Language::Japanese,
+        )
+        .with_stealth(true);
+        ui.ensure_reveal_for_current_question();
+        // Reveal just started: the query line exists but the choices
+        // haven't faded in yet.
+        let screen = ui.stealth_screen();
+        assert!(screen.events[0].message.starts_with("req=q-demo-fallback"));
+        assert!(!screen.events.iter().any(|e| e.component == "route"));
+        ui.choices_reveal_starts_at = Some(Instant::now() - Duration::from_secs(5));
+        let screen = ui.stealth_screen();
+        assert!(screen
+            .events
+            .iter()
+            .any(|e| e.component == "route" && e.message.ends_with("東京")));
+    }
+
     #[test]
     fn demo_target_uses_ja_typings_when_registered() {
         // ja_typings が登録されていれば通常パスでそれを採用する。
@@ -1240,4 +2271,81 @@ mod tests {
             assert!(ch.is_ascii_digit(), "non-digit at pos {pos} in {ts}");
         }
     }
+
+    #[test]
+    fn read_aloud_speaks_the_reading_but_never_the_typings() {
+        use crate::audio::mock::MockSpeech;
+
+        let mut question = make_question_with_choice("東京", "Tokyo", vec!["toukyou".to_string()]);
+        question.question_text_reading.insert(
+            "ja".to_string(),
+            "`alt` ぞくせいは？\n```html\n<img alt=\"x\">\n```".to_string(),
+        );
+        let (mock, log) = MockSpeech::new();
+        let mut ui = QuizUI::from_pool_with_count(
+            &[question],
+            Language::Japanese,
+            "/tmp/records-aloud.yaml".into(),
+            1,
+        )
+        .with_read_aloud(ReadAloud::Choices, Some(Box::new(mock)));
+        ui.ensure_reveal_for_current_question();
+
+        let spoken = log.spoken();
+        assert_eq!(spoken.len(), 1);
+        assert_eq!(spoken[0].id, "q-demo-fallback");
+        assert_eq!(spoken[0].language, "ja");
+        let text = &spoken[0].text;
+        assert!(text.starts_with("alt ぞくせいは？"), "{text}");
+        assert!(!text.contains('`') && !text.contains("<img"), "{text}");
+        assert!(!text.contains("toukyou"), "typing target spoken: {text}");
+        // Choices follow the on-screen letters, in the shuffled order.
+        let labels = ["東京", "dummy"];
+        let expected: String = ui
+            .choice_order
+            .iter()
+            .zip(CHOICE_LABELS)
+            .map(|(&idx, label)| format!(" {label}: {}.", labels[idx]))
+            .collect();
+        assert!(text.ends_with(&expected), "{text}");
+
+        // Same question, same frame: no second utterance.
+        ui.ensure_reveal_for_current_question();
+        assert_eq!(log.spoken().len(), 1);
+    }
+
+    #[test]
+    fn read_aloud_key_repeats_and_a_failure_turns_it_off() {
+        use crate::audio::mock::MockSpeech;
+
+        let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let (mock, log) = MockSpeech::new();
+        let mut ui = make_quiz_ui_with_choice("", "Tokyo", Vec::new(), Language::English)
+            .with_read_aloud(ReadAloud::Question, Some(Box::new(mock)));
+        ui.ensure_reveal_for_current_question();
+        assert!(!ui.handle_key(press(KeyCode::F(4))));
+        let spoken = log.spoken();
+        assert_eq!(spoken.len(), 2);
+        assert_eq!(spoken[1].text, "test", "question mode skips the choices");
+        assert!(ui.input_buffer.is_empty());
+
+        log.set_failing(true);
+        assert!(!ui.handle_key(press(KeyCode::F(4))));
+        assert!(ui.speech.is_none());
+        assert_eq!(ui.pending_warnings.len(), 1);
+        assert!(!ui.handle_key(press(KeyCode::F(4))));
+        assert_eq!(ui.pending_warnings.len(), 1, "reported once");
+    }
+
+    #[test]
+    fn read_aloud_off_drops_the_backend() {
+        use crate::audio::mock::MockSpeech;
+
+        let (mock, log) = MockSpeech::new();
+        let mut ui = make_quiz_ui_with_choice("", "Tokyo", Vec::new(), Language::English)
+            .with_read_aloud(ReadAloud::Off, Some(Box::new(mock)));
+        ui.ensure_reveal_for_current_question();
+        assert!(ui.speech.is_none());
+        assert!(log.spoken().is_empty());
+    }
 }
@@ -1,17 +1,23 @@
 //! Records browse screen (Issue #40).
 //!
 //! Loads `records_<lang>.json` and renders the local self-best list across
-//! Quiz, Time Attack 25, and Listening RPG sections. Strictly read-only —
-//! writing happens at the end of a Quiz run via `QuizUI::persist_record`.
+//! Quiz, Time Attack 25, and Listening RPG sections, plus one section per
+//! Quiz variant (`--count N`, survival, marathon genre) that has entries.
+//! Strictly read-only — writing happens at the end of a Quiz run via
+//! `ui::quiz::save_record`, from both the TUI and plain mode. In plain
+//! mode (#47) the same sections are printed as lines instead.
 //!
 //! Per the kako-jun rule pinned in `docs/spec.md`, this screen displays
 //! **Records** (offline self-bests). World ordering — "Ranking" — is
 //! reserved for the v0.3.0+ Nostralgic Ranking integration in
 //! `type-globe-online` and is not surfaced here.
 
+use crate::game::QuizVariant;
 use crate::io::Storage;
 use crate::types::{Records, ScoreEntry, TimeEntry};
-use crate::ui::{HelpEntry, HelpLine};
+use crate::ui::keymap::{keymap, RecordsAction};
+use crate::ui::theme::theme;
+use crate::ui::HelpLine;
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
@@ -20,20 +26,13 @@ use crossterm::{
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Padding, Paragraph},
     Frame, Terminal,
 };
-use std::io;
+use std::io::{self, Write};
 use std::time::Duration;
 
-const STYLE_TITLE: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-const STYLE_SECTION: Style = Style::new().fg(Color::Cyan);
-const STYLE_NORMAL: Style = Style::new().fg(Color::White);
-const STYLE_DIM: Style = Style::new().fg(Color::DarkGray);
-const STYLE_HIGHLIGHT: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
-
 pub struct RecordsUI {
     records: Records,
     /// `ts` (RFC3339) of the latest entry in each section. Each
@@ -54,9 +53,9 @@ impl RecordsUI {
     }
 
     fn from_records(records: Records) -> Self {
-        let latest_quiz_ts = records.quiz_mode.iter().map(|e| e.ts.clone()).max();
+        let latest_quiz_ts = latest_ts(&records.quiz_mode);
         let latest_ta25_ts = records.time_attack_25.iter().map(|e| e.ts.clone()).max();
-        let latest_rpg_ts = records.rpg.iter().map(|e| e.ts.clone()).max();
+        let latest_rpg_ts = latest_ts(&records.rpg);
         Self {
             records,
             latest_quiz_ts,
@@ -102,11 +101,72 @@ impl RecordsUI {
         Ok(())
     }
 
+    /// Print every section as plain lines, for `--plain` (#47). The row
+    /// the TUI highlights is marked `(latest)` instead.
+    pub fn print_plain(&self, out: &mut impl Write) -> io::Result<()> {
+        for line in self.plain_lines() {
+            writeln!(out, "{line}")?;
+        }
+        Ok(())
+    }
+
+    fn plain_lines(&self) -> Vec<String> {
+        let mut lines = vec!["type-globe - Records".to_string()];
+        let mut section = |title: &str, rows: Vec<(String, bool)>| {
+            lines.push(String::new());
+            lines.push(format!("{title}:"));
+            if rows.is_empty() {
+                lines.push("  (no records yet)".to_string());
+            }
+            for (row, latest) in rows {
+                let row = row.trim_end().to_string();
+                lines.push(if latest {
+                    format!("{row}  (latest)")
+                } else {
+                    row
+                });
+            }
+        };
+        let score_rows = |entries: &[ScoreEntry], latest: Option<&str>| {
+            entries
+                .iter()
+                .enumerate()
+                .map(|(i, e)| (score_row(i, e), latest == Some(e.ts.as_str())))
+                .collect::<Vec<_>>()
+        };
+        section(
+            "Quiz (single-run)",
+            score_rows(&self.records.quiz_mode, self.latest_quiz_ts.as_deref()),
+        );
+        section(
+            "Time Attack 25",
+            self.records
+                .time_attack_25
+                .iter()
+                .enumerate()
+                .map(|(i, e)| {
+                    (
+                        time_row(i, e),
+                        self.latest_ta25_ts.as_deref() == Some(e.ts.as_str()),
+                    )
+                })
+                .collect(),
+        );
+        section(
+            "Listening RPG",
+            score_rows(&self.records.rpg, self.latest_rpg_ts.as_deref()),
+        );
+        for (title, entries) in self.variant_sections() {
+            let latest = latest_ts(entries);
+            section(&title, score_rows(entries, latest.as_deref()));
+        }
+        lines
+    }
+
     fn is_quit(key: KeyEvent) -> bool {
-        matches!(key.code, KeyCode::Esc | KeyCode::Enter)
+        keymap().records.action_for(&key) == Some(RecordsAction::Close)
             || (matches!(key.code, KeyCode::Char('c'))
                 && key.modifiers.contains(KeyModifiers::CONTROL))
-            || matches!(key.code, KeyCode::Char('q'))
     }
 
     fn ui(&self, f: &mut Frame) {
@@ -127,20 +187,49 @@ impl RecordsUI {
 
     fn render_title(&self, f: &mut Frame, area: Rect) {
         let title = Paragraph::new("type-globe - Records")
-            .style(STYLE_TITLE)
+            .style(theme().title())
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
         f.render_widget(title, area);
     }
 
+    /// Non-empty Quiz variant lists, in a stable order: counts ascending,
+    /// survival, timed, then marathon genres alphabetically.
+    fn variant_sections(&self) -> Vec<(String, &[ScoreEntry])> {
+        let counts = self
+            .records
+            .quiz_count
+            .iter()
+            .map(|(n, entries)| (QuizVariant::Count(*n), entries));
+        let singles = [
+            (QuizVariant::Survival, &self.records.survival),
+            (QuizVariant::Timed, &self.records.timed),
+        ];
+        let marathons = self
+            .records
+            .marathon
+            .iter()
+            .map(|(g, entries)| (QuizVariant::Marathon(g.clone()), entries));
+        counts
+            .chain(singles)
+            .chain(marathons)
+            .filter(|(_, entries)| !entries.is_empty())
+            .map(|(variant, entries)| (variant.label(), entries.as_slice()))
+            .collect()
+    }
+
     fn render_sections(&self, f: &mut Frame, area: Rect) {
+        // 4:3:3 for the fixed sections (the old 40/30/30 split); each
+        // variant section gets the same share as Time Attack / RPG.
+        let variants = self.variant_sections();
+        let constraints: Vec<Constraint> = [4, 3, 3]
+            .into_iter()
+            .chain(variants.iter().map(|_| 3))
+            .map(Constraint::Fill)
+            .collect();
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(40),
-                Constraint::Percentage(30),
-                Constraint::Percentage(30),
-            ])
+            .constraints(constraints)
             .split(area);
 
         self.render_score_section(
@@ -164,6 +253,10 @@ impl RecordsUI {
             &self.records.rpg,
             self.latest_rpg_ts.as_deref(),
         );
+        for (i, (title, entries)) in variants.iter().enumerate() {
+            let latest = latest_ts(entries);
+            self.render_score_section(f, chunks[3 + i], title, entries, latest.as_deref());
+        }
     }
 
     fn render_score_section(
@@ -175,35 +268,29 @@ impl RecordsUI {
         highlight_ts: Option<&str>,
     ) {
         let lines = if entries.is_empty() {
-            vec![Line::from(Span::styled("  (no records yet)", STYLE_DIM))]
+            vec![Line::from(Span::styled(
+                "  (no records yet)",
+                theme().dim(),
+            ))]
         } else {
             entries
                 .iter()
                 .enumerate()
                 .map(|(i, e)| {
-                    let style = if highlight_ts.is_some() && e.ts.as_str() == highlight_ts.unwrap() {
-                        STYLE_HIGHLIGHT
+                    let style = if highlight_ts.is_some() && e.ts.as_str() == highlight_ts.unwrap()
+                    {
+                        theme().selected()
                     } else {
-                        STYLE_NORMAL
+                        theme().text()
                     };
-                    Line::from(Span::styled(
-                        format!(
-                            "  {rank:>2}. {name:<16}  Score {score:>6}   CPM {cpm:>4}   WPM {wpm:>3}",
-                            rank = i + 1,
-                            name = truncate_padded(&e.name, 16),
-                            score = e.score,
-                            cpm = e.cpm,
-                            wpm = e.wpm,
-                        ),
-                        style,
-                    ))
+                    Line::from(Span::styled(score_row(i, e), style))
                 })
                 .collect()
         };
 
         let body = Paragraph::new(lines).alignment(Alignment::Left).block(
             Block::default()
-                .title(Span::styled(format!(" {title} "), STYLE_SECTION))
+                .title(Span::styled(format!(" {title} "), theme().section()))
                 .borders(Borders::ALL)
                 .padding(Padding::uniform(1)),
         );
@@ -219,7 +306,10 @@ impl RecordsUI {
         highlight_ts: Option<&str>,
     ) {
         let lines = if entries.is_empty() {
-            vec![Line::from(Span::styled("  (no records yet)", STYLE_DIM))]
+            vec![Line::from(Span::styled(
+                "  (no records yet)",
+                theme().dim(),
+            ))]
         } else {
             entries
                 .iter()
@@ -227,27 +317,18 @@ impl RecordsUI {
                 .map(|(i, e)| {
                     let style = if highlight_ts.is_some() && e.ts.as_str() == highlight_ts.unwrap()
                     {
-                        STYLE_HIGHLIGHT
+                        theme().selected()
                     } else {
-                        STYLE_NORMAL
+                        theme().text()
                     };
-                    let mins = e.time_seconds / 60;
-                    let secs = e.time_seconds % 60;
-                    Line::from(Span::styled(
-                        format!(
-                            "  {rank:>2}. {name:<16}  Time {mins}:{secs:02}",
-                            rank = i + 1,
-                            name = truncate_padded(&e.name, 16),
-                        ),
-                        style,
-                    ))
+                    Line::from(Span::styled(time_row(i, e), style))
                 })
                 .collect()
         };
 
         let body = Paragraph::new(lines).alignment(Alignment::Left).block(
             Block::default()
-                .title(Span::styled(format!(" {title} "), STYLE_SECTION))
+                .title(Span::styled(format!(" {title} "), theme().section()))
                 .borders(Borders::ALL)
                 .padding(Padding::uniform(1)),
         );
@@ -255,14 +336,38 @@ impl RecordsUI {
     }
 
     fn help_line(&self) -> HelpLine {
-        HelpLine::new(vec![
-            HelpEntry::new("Esc", "Menu"),
-            HelpEntry::new("Enter", "Menu"),
-            HelpEntry::new("q", "Menu"),
-        ])
+        HelpLine::new(vec![keymap().records.help(RecordsAction::Close)])
     }
 }
 
+/// One row of a score section; `i` is the 0-based rank.
+fn score_row(i: usize, e: &ScoreEntry) -> String {
+    format!(
+        "  {rank:>2}. {name:<16}  Score {score:>6}   CPM {cpm:>4}   WPM {wpm:>3}",
+        rank = i + 1,
+        name = truncate_padded(&e.name, 16),
+        score = e.score,
+        cpm = e.cpm,
+        wpm = e.wpm,
+    )
+}
+
+/// One row of the Time Attack 25 section; `i` is the 0-based rank.
+fn time_row(i: usize, e: &TimeEntry) -> String {
+    let mins = e.time_seconds / 60;
+    let secs = e.time_seconds % 60;
+    format!(
+        "  {rank:>2}. {name:<16}  Time {mins}:{secs:02}",
+        rank = i + 1,
+        name = truncate_padded(&e.name, 16),
+    )
+}
+
+/// Newest `ts` among `entries`, for the variant section's ordering.
+fn latest_ts(entries: &[ScoreEntry]) -> Option<String> {
+    entries.iter().map(|e| e.ts.clone()).max()
+}
+
 /// Pad / truncate `s` so the displayed width is exactly `width` characters
 /// (UTF-8 char count, not bytes — wide-character widths are still rough,
 /// but the records list does not promise pixel-perfect alignment).
@@ -317,6 +422,52 @@ mod tests {
         assert_eq!(ui.latest_rpg_ts, None);
     }
 
+    #[test]
+    fn variant_sections_list_only_non_empty_lists_in_order() {
+        let mut records = Records::default();
+        records.push_marathon("capitals", entry("m", 1, 1));
+        records.push_quiz_count(50, entry("c50", 1, 1));
+        records.push_quiz_count(20, entry("c20", 1, 1));
+        records.marathon.insert("empty".into(), Vec::new());
+        let ui = RecordsUI::from_records(records);
+        let titles: Vec<String> = ui.variant_sections().into_iter().map(|(t, _)| t).collect();
+        assert_eq!(
+            titles,
+            [
+                "Quiz (20 questions)",
+                "Quiz (50 questions)",
+                "Marathon: capitals"
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_listing_prints_every_section_and_marks_the_latest_run() {
+        let mut records = Records::default();
+        records.quiz_mode.push(entry("old", 200, 10));
+        records.quiz_mode.push(entry("new", 100, 20));
+        records.push_quiz_count(20, entry("c20", 1, 1));
+        let mut out = Vec::new();
+        RecordsUI::from_records(records)
+            .print_plain(&mut out)
+            .expect("write");
+        let text = String::from_utf8(out).expect("utf-8");
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines.contains(&"Quiz (single-run):"));
+        assert!(lines.contains(&"Quiz (20 questions):"));
+        assert!(lines
+            .iter()
+            .any(|l| l.contains("new") && l.ends_with("(latest)")));
+        assert!(lines
+            .iter()
+            .any(|l| l.contains("old") && !l.ends_with("(latest)")));
+        assert!(
+            lines.contains(&"  (no records yet)"),
+            "Time Attack is empty"
+        );
+        assert!(!text.contains('│') && !text.contains('\u{1b}'));
+    }
+
     #[test]
     fn truncate_padded_pads_short_strings() {
         assert_eq!(truncate_padded("ab", 5), "ab   ");
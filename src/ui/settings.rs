@@ -0,0 +1,545 @@
+//! Settings screen, reached from the menu's mode list.
+//!
+//! Edits the values stored in `settings.yaml` (see [`crate::settings`])
+//! and writes them back on save. Most rows step through a fixed list of
+//! values with the decrease / increase keys; the TTS voices are free
+//! text because the installed voices differ per machine (`type-globe
+//! voices` lists them). `--set` and
+//! `TYPE_GLOBE_*` overrides are not shown here and still apply on top
+//! of whatever gets saved.
+
+use crate::audio::cues::SoundTheme;
+use crate::settings::{ReadAloud, RevealSpeed, Settings, MAX_RUN_LENGTH};
+use crate::ui::keymap::{keymap, SettingsAction};
+use crate::ui::theme::{theme, PRESET_NAMES};
+use crate::ui::HelpLine;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph},
+    Frame, Terminal,
+};
+use std::io;
+use std::time::Duration;
+
+const RUN_LENGTHS: [usize; 8] = [5, 10, 15, 20, 25, 30, 50, MAX_RUN_LENGTH];
+const VOLUMES: [u8; 11] = [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
+const TTS_RATES: [f32; 7] = [0.5, 0.75, 0.9, 1.0, 1.1, 1.25, 1.5];
+const TTS_PITCHES: [f32; 7] = [0.5, 0.75, 0.9, 1.0, 1.1, 1.25, 1.5];
+const LANGUAGES: [Option<&str>; 3] = [None, Some("ja"), Some("en")];
+/// Longest voice name the text field accepts.
+const VOICE_MAX_CHARS: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Language,
+    RunLength,
+    Sound,
+    Volume,
+    RevealSpeed,
+    Hints,
+    Theme,
+    TtsVoice,
+    TtsVoiceJa,
+    TtsVoiceEn,
+    TtsRate,
+    TtsPitch,
+    TtsVolume,
+    Speech,
+    ReadAloud,
+    SoundTheme,
+    MasterVolume,
+    KeystrokeVolume,
+}
+
+const ROWS: [Row; 18] = [
+    Row::Language,
+    Row::RunLength,
+    Row::MasterVolume,
+    Row::Sound,
+    Row::Volume,
+    Row::KeystrokeVolume,
+    Row::SoundTheme,
+    Row::RevealSpeed,
+    Row::Hints,
+    Row::Theme,
+    Row::TtsVoice,
+    Row::TtsVoiceJa,
+    Row::TtsVoiceEn,
+    Row::TtsRate,
+    Row::TtsPitch,
+    Row::TtsVolume,
+    Row::Speech,
+    Row::ReadAloud,
+];
+
+impl Row {
+    fn label(self) -> &'static str {
+        match self {
+            Row::Language => "Language",
+            Row::RunLength => "Questions per run",
+            Row::Sound => "Sound effects",
+            Row::Volume => "Cue volume",
+            Row::RevealSpeed => "Reveal speed",
+            Row::Hints => "Hints in Records runs",
+            Row::Theme => "Theme",
+            Row::TtsVoice => "TTS voice",
+            Row::TtsVoiceJa => "TTS voice (ja)",
+            Row::TtsVoiceEn => "TTS voice (en)",
+            Row::TtsRate => "TTS rate",
+            Row::TtsPitch => "TTS pitch",
+            Row::TtsVolume => "TTS volume",
+            Row::Speech => "Speech order",
+            Row::ReadAloud => "Read Quiz aloud",
+            Row::SoundTheme => "Sound theme",
+            Row::MasterVolume => "Master volume",
+            Row::KeystrokeVolume => "Keystroke volume",
+        }
+    }
+}
+
+/// What a key press did to the screen.
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Stay,
+    Saved,
+    Cancelled,
+}
+
+pub struct SettingsUI {
+    settings: Settings,
+    file_path: String,
+    selected: usize,
+    /// Text being typed into a TTS voice row; `None` when not editing.
+    editing: Option<String>,
+    /// Last save error, shown under the rows until the next key press.
+    error: Option<String>,
+}
+
+impl SettingsUI {
+    /// Edit `settings` (the file values, without overrides) and save
+    /// them to `file_path`.
+    pub fn new(settings: Settings, file_path: String) -> Self {
+        Self {
+            settings,
+            file_path,
+            selected: 0,
+            editing: None,
+            error: None,
+        }
+    }
+
+    /// Run until the player saves or backs out. Returns the saved
+    /// settings, or `None` when nothing was written.
+    pub fn run(&mut self) -> Result<Option<Settings>, Box<dyn std::error::Error>> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+
+        let result = self.run_app(&mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        result
+    }
+
+    fn run_app(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    ) -> Result<Option<Settings>, Box<dyn std::error::Error>> {
+        // Same poll cadence as Records so terminal-resize redraws stay snappy.
+        const TICK: Duration = Duration::from_millis(250);
+
+        loop {
+            terminal.draw(|f| self.ui(f))?;
+
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    match self.handle_key(key) {
+                        Outcome::Stay => {}
+                        Outcome::Saved => return Ok(Some(self.settings.clone())),
+                        Outcome::Cancelled => return Ok(None),
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome {
+        if matches!(key.code, KeyCode::Char('c')) && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Outcome::Cancelled;
+        }
+        self.error = None;
+        let action = keymap().settings.action_for(&key);
+        if self.editing.is_some() {
+            self.handle_key_editing(key, action);
+            return Outcome::Stay;
+        }
+
+        match action {
+            Some(SettingsAction::Up) => self.selected = self.selected.saturating_sub(1),
+            Some(SettingsAction::Down) if self.selected + 1 < ROWS.len() => self.selected += 1,
+            Some(SettingsAction::Decrease) => self.adjust(-1),
+            Some(SettingsAction::Increase) => self.adjust(1),
+            Some(SettingsAction::Edit) => {
+                let row = ROWS[self.selected];
+                match voice_field(&mut self.settings, row) {
+                    Some(voice) => self.editing = Some(voice.clone().unwrap_or_default()),
+                    None => self.adjust(1),
+                }
+            }
+            Some(SettingsAction::Save) => match self.settings.save(&self.file_path) {
+                Ok(()) => return Outcome::Saved,
+                Err(e) => self.error = Some(format!("could not save {}: {e}", self.file_path)),
+            },
+            Some(SettingsAction::Back) => return Outcome::Cancelled,
+            Some(SettingsAction::Down) | None => {}
+        }
+        Outcome::Stay
+    }
+
+    /// While the voice field is open every printable key is text; only
+    /// the edit (done) and back (cancel) actions keep their meaning.
+    fn handle_key_editing(&mut self, key: KeyEvent, action: Option<SettingsAction>) {
+        let Some(buffer) = self.editing.as_mut() else {
+            return;
+        };
+        match (action, key.code) {
+            (Some(SettingsAction::Edit), _) => {
+                let name = buffer.trim();
+                let name = (!name.is_empty()).then(|| name.to_string());
+                if let Some(voice) = voice_field(&mut self.settings, ROWS[self.selected]) {
+                    *voice = name;
+                }
+                self.editing = None;
+            }
+            (Some(SettingsAction::Back), _) => self.editing = None,
+            (_, KeyCode::Backspace) => {
+                buffer.pop();
+            }
+            (_, KeyCode::Char(c))
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                    && buffer.chars().count() < VOICE_MAX_CHARS =>
+            {
+                buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    /// Step the selected row one value down (`-1`) or up (`1`).
+    fn adjust(&mut self, delta: i32) {
+        let s = &mut self.settings;
+        match ROWS[self.selected] {
+            Row::Language => {
+                let current = LANGUAGES
+                    .iter()
+                    .position(|l| *l == s.language.as_deref())
+                    .unwrap_or(0);
+                s.language =
+                    LANGUAGES[step_index(LANGUAGES.len(), current, delta)].map(str::to_string);
+            }
+            Row::RunLength => s.run_length = step_through(&RUN_LENGTHS, s.run_length, delta),
+            Row::Sound => s.sound = !s.sound,
+            Row::Volume => s.volume = step_through(&VOLUMES, s.volume, delta),
+            Row::MasterVolume => s.master_volume = step_through(&VOLUMES, s.master_volume, delta),
+            Row::KeystrokeVolume => {
+                s.keystroke_volume = step_through(&VOLUMES, s.keystroke_volume, delta)
+            }
+            Row::RevealSpeed => {
+                let all = RevealSpeed::ALL;
+                let current = all.iter().position(|r| *r == s.reveal_speed).unwrap_or(1);
+                s.reveal_speed = all[step_index(all.len(), current, delta)];
+            }
+            Row::Hints => s.hints_in_records = !s.hints_in_records,
+            Row::Theme => {
+                // Index 0 is "unset"; presets follow in `PRESET_NAMES` order.
+                let current = s
+                    .theme
+                    .as_deref()
+                    .and_then(|t| PRESET_NAMES.iter().position(|p| *p == t))
+                    .map_or(0, |i| i + 1);
+                let next = step_index(PRESET_NAMES.len() + 1, current, delta);
+                s.theme = next.checked_sub(1).map(|i| PRESET_NAMES[i].to_string());
+            }
+            Row::TtsVoice | Row::TtsVoiceJa | Row::TtsVoiceEn => {}
+            Row::TtsRate => s.tts_rate = step_through(&TTS_RATES, s.tts_rate, delta),
+            Row::TtsPitch => s.tts_pitch = step_through(&TTS_PITCHES, s.tts_pitch, delta),
+            Row::TtsVolume => s.tts_volume = step_through(&VOLUMES, s.tts_volume, delta),
+            // Rotate rather than replace, so a trimmed list stays trimmed.
+            Row::Speech if s.speech.is_empty() => {}
+            Row::Speech if delta > 0 => s.speech.rotate_left(1),
+            Row::Speech => s.speech.rotate_right(1),
+            Row::ReadAloud => {
+                let all = ReadAloud::ALL;
+                let current = all.iter().position(|r| *r == s.read_aloud).unwrap_or(0);
+                s.read_aloud = all[step_index(all.len(), current, delta)];
+            }
+            Row::SoundTheme => {
+                let all = SoundTheme::ALL;
+                let current = all.iter().position(|t| *t == s.sound_theme).unwrap_or(0);
+                s.sound_theme = all[step_index(all.len(), current, delta)];
+            }
+        }
+    }
+
+    fn value(&self, row: Row) -> String {
+        let s = &self.settings;
+        match row {
+            Row::Language => s.language.clone().unwrap_or_else(|| "(ask)".into()),
+            Row::RunLength => s.run_length.to_string(),
+            Row::Sound => if s.sound { "on" } else { "off" }.into(),
+            Row::Volume => format!("{}%", s.volume),
+            Row::MasterVolume => format!("{}%", s.master_volume),
+            Row::KeystrokeVolume => format!("{}%", s.keystroke_volume),
+            Row::RevealSpeed => s.reveal_speed.name().into(),
+            Row::Hints => if s.hints_in_records { "on" } else { "off" }.into(),
+            Row::Theme => s.theme.clone().unwrap_or_else(|| "(theme.yaml)".into()),
+            Row::TtsVoice => self.voice_value(&s.tts_voice, row, "(auto)"),
+            Row::TtsVoiceJa => self.voice_value(&s.tts_voice_ja, row, "(TTS voice)"),
+            Row::TtsVoiceEn => self.voice_value(&s.tts_voice_en, row, "(TTS voice)"),
+            Row::TtsRate => format!("{:.2}x", s.tts_rate),
+            Row::TtsPitch => format!("{:.2}x", s.tts_pitch),
+            Row::TtsVolume => format!("{}%", s.tts_volume),
+            Row::Speech => {
+                let names: Vec<&str> = s.speech.iter().map(|k| k.name()).collect();
+                names.join(", ")
+            }
+            Row::ReadAloud => s.read_aloud.name().into(),
+            Row::SoundTheme => s.sound_theme.name().into(),
+        }
+    }
+
+    /// A voice row's value: the edit buffer while that row is open.
+    fn voice_value(&self, voice: &Option<String>, row: Row, unset: &str) -> String {
+        match &self.editing {
+            Some(buffer) if ROWS[self.selected] == row => format!("{buffer}_"),
+            _ => voice.clone().unwrap_or_else(|| unset.into()),
+        }
+    }
+
+    fn ui(&self, f: &mut Frame) {
+        let outer = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(ROWS.len() as u16 + 4),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(f.area());
+
+        self.render_title(f, outer[0]);
+        self.render_rows(f, outer[1]);
+        self.render_note(f, outer[2]);
+        self.help_line().render(f, outer[3]);
+    }
+
+    fn render_title(&self, f: &mut Frame, area: Rect) {
+        let title = Paragraph::new("type-globe - Settings")
+            .style(theme().title())
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(title, area);
+    }
+
+    fn render_rows(&self, f: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = ROWS
+            .iter()
+            .enumerate()
+            .map(|(i, &row)| {
+                let style = if i == self.selected {
+                    theme().selected()
+                } else {
+                    theme().text()
+                };
+                Line::from(vec![
+                    Span::styled(format!("  {:<18}", row.label()), style),
+                    Span::styled(self.value(row), style),
+                ])
+            })
+            .collect();
+
+        let body = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", self.file_path),
+                    theme().section(),
+                ))
+                .borders(Borders::ALL)
+                .padding(Padding::uniform(1)),
+        );
+        f.render_widget(body, area);
+    }
+
+    fn render_note(&self, f: &mut Frame, area: Rect) {
+        let note = match &self.error {
+            Some(error) => Span::styled(error.clone(), theme().issue()),
+            None => Span::styled(
+                "--set and TYPE_GLOBE_* overrides still apply on top of the saved values.",
+                theme().dim(),
+            ),
+        };
+        f.render_widget(Paragraph::new(Line::from(note)), area);
+    }
+
+    fn help_line(&self) -> HelpLine {
+        let keys = &keymap().settings;
+        if self.editing.is_some() {
+            return HelpLine::new(vec![
+                keys.help_as(SettingsAction::Edit, "Done"),
+                keys.help_as(SettingsAction::Back, "Cancel"),
+            ]);
+        }
+        HelpLine::new(vec![
+            keys.help_pair(SettingsAction::Up, SettingsAction::Down, "Select"),
+            keys.help_pair(SettingsAction::Decrease, SettingsAction::Increase, "Change"),
+            keys.help(SettingsAction::Edit),
+            keys.help(SettingsAction::Save),
+            keys.help(SettingsAction::Back),
+        ])
+    }
+}
+
+/// Move `delta` steps from `current` within `0..len`, stopping at the ends.
+fn step_index(len: usize, current: usize, delta: i32) -> usize {
+    (current as i64 + i64::from(delta)).clamp(0, len as i64 - 1) as usize
+}
+
+/// The setting a free-text voice row edits; `None` for the other rows.
+fn voice_field(settings: &mut Settings, row: Row) -> Option<&mut Option<String>> {
+    match row {
+        Row::TtsVoice => Some(&mut settings.tts_voice),
+        Row::TtsVoiceJa => Some(&mut settings.tts_voice_ja),
+        Row::TtsVoiceEn => Some(&mut settings.tts_voice_en),
+        _ => None,
+    }
+}
+
+/// The next value of `steps` above (`delta > 0`) or below `current`.
+/// Values from the file that sit between steps snap to the neighbour in
+/// the pressed direction; the ends are sticky.
+fn step_through<T: PartialOrd + Copy>(steps: &[T], current: T, delta: i32) -> T {
+    if delta > 0 {
+        steps
+            .iter()
+            .copied()
+            .find(|v| *v > current)
+            .unwrap_or(steps[steps.len() - 1])
+    } else {
+        steps
+            .iter()
+            .rev()
+            .copied()
+            .find(|v| *v < current)
+            .unwrap_or(steps[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn select(ui: &mut SettingsUI, row: Row) {
+        ui.selected = ROWS.iter().position(|r| *r == row).expect("row");
+    }
+
+    #[test]
+    fn step_through_snaps_and_sticks_at_the_ends() {
+        assert_eq!(step_through(&RUN_LENGTHS, 10, 1), 15);
+        assert_eq!(step_through(&RUN_LENGTHS, 12, -1), 10);
+        assert_eq!(step_through(&RUN_LENGTHS, 12, 1), 15);
+        assert_eq!(step_through(&RUN_LENGTHS, 5, -1), 5);
+        assert_eq!(step_through(&VOLUMES, 100, 1), 100);
+        assert_eq!(step_through(&TTS_RATES, 1.0, 1), 1.1);
+    }
+
+    #[test]
+    fn arrows_move_and_change_rows() {
+        let mut ui = SettingsUI::new(Settings::default(), String::new());
+        ui.handle_key(press(KeyCode::Right));
+        assert_eq!(ui.settings.language.as_deref(), Some("ja"));
+        ui.handle_key(press(KeyCode::Down));
+        ui.handle_key(press(KeyCode::Left));
+        assert_eq!(ui.settings.run_length, 5);
+        select(&mut ui, Row::Theme);
+        ui.handle_key(press(KeyCode::Right));
+        assert_eq!(ui.settings.theme.as_deref(), Some("dark"));
+        ui.handle_key(press(KeyCode::Left));
+        assert_eq!(ui.settings.theme, None);
+        select(&mut ui, Row::RevealSpeed);
+        ui.handle_key(press(KeyCode::Right));
+        ui.handle_key(press(KeyCode::Right));
+        assert_eq!(ui.settings.reveal_speed, RevealSpeed::Fast);
+        select(&mut ui, Row::Speech);
+        ui.handle_key(press(KeyCode::Right));
+        assert_eq!(
+            ui.value(Row::Speech),
+            "tts, espeak-ng, open-jtalk, piper, files"
+        );
+    }
+
+    #[test]
+    fn voice_field_takes_bound_letters_as_text() {
+        let mut ui = SettingsUI::new(Settings::default(), String::new());
+        select(&mut ui, Row::TtsVoice);
+        ui.handle_key(press(KeyCode::Enter));
+        for c in "kjs".chars() {
+            ui.handle_key(press(KeyCode::Char(c)));
+        }
+        ui.handle_key(press(KeyCode::Backspace));
+        ui.handle_key(press(KeyCode::Enter));
+        assert_eq!(ui.settings.tts_voice.as_deref(), Some("kj"));
+        assert_eq!(ROWS[ui.selected], Row::TtsVoice);
+
+        ui.handle_key(press(KeyCode::Enter));
+        ui.handle_key(press(KeyCode::Char('x')));
+        ui.handle_key(press(KeyCode::Esc));
+        assert_eq!(ui.settings.tts_voice.as_deref(), Some("kj"));
+
+        select(&mut ui, Row::TtsVoiceJa);
+        ui.handle_key(press(KeyCode::Enter));
+        ui.handle_key(press(KeyCode::Char('k')));
+        assert_eq!(ui.value(Row::TtsVoiceJa), "k_");
+        assert_eq!(ui.value(Row::TtsVoice), "kj");
+        ui.handle_key(press(KeyCode::Enter));
+        assert_eq!(ui.settings.tts_voice_ja.as_deref(), Some("k"));
+        assert_eq!(ui.settings.tts_voice.as_deref(), Some("kj"));
+    }
+
+    #[test]
+    fn save_writes_the_file_and_back_does_not() {
+        let path = temp_dir().join("type_globe_test_settings_ui.yaml");
+        let path = path.to_str().expect("utf-8 temp path").to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let mut ui = SettingsUI::new(Settings::default(), path.clone());
+        select(&mut ui, Row::Sound);
+        ui.handle_key(press(KeyCode::Enter));
+        assert_eq!(ui.handle_key(press(KeyCode::Esc)), Outcome::Cancelled);
+        assert!(!std::path::Path::new(&path).exists());
+
+        assert_eq!(ui.handle_key(press(KeyCode::Char('s'))), Outcome::Saved);
+        let saved = Settings::load(&path).expect("load");
+        assert!(!saved.sound);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -6,22 +6,19 @@
 //! `QuizGame` (or future `HackGame`) and calls `render`.
 //!
 //! Per `docs/spec.md`:
-//! - Quiz modes show **Score / Time / CPM / WPM**.
+//! - Quiz modes show **Score / Time / CPM / WPM**, plus a **Limit** bar
+//!   counting down the current question in Timed runs.
 //! - Hack-and-slash shows **Lv / EXP / HP / Floor**.
 
+use crate::ui::theme::theme;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Gauge, Padding, Paragraph},
     Frame,
 };
 use std::time::Duration;
 
-const STYLE_LABEL: Style = Style::new().fg(Color::DarkGray);
-const STYLE_VALUE: Style = Style::new().fg(Color::Cyan);
-const STYLE_TITLE: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-
 /// A simple progress bar specification.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProgressBar {
@@ -47,9 +44,7 @@ pub enum StatusItem {
         label: String,
         value: String,
     },
-    /// Used by RPG mode; quiz mode never produces bars.
-    /// TODO(#11): drop this allow when rpg UI lands.
-    #[allow(dead_code)]
+    /// RPG EXP / HP, and the Timed quiz countdown.
     Bar(ProgressBar),
 }
 
@@ -61,8 +56,6 @@ impl StatusItem {
         }
     }
 
-    /// TODO(#11): drop this `allow(dead_code)` once the rpg UI wires it up.
-    #[allow(dead_code)]
     pub fn bar(label: impl Into<String>, current: u32, max: u32) -> Self {
         Self::Bar(ProgressBar {
             label: label.into(),
@@ -137,7 +130,7 @@ impl StatusPane {
         // breathing room inside the border so the labels and values
         // don't crash into the frame.
         let block = Block::default()
-            .title(Span::styled(format!(" {} ", self.title), STYLE_TITLE))
+            .title(Span::styled(format!(" {} ", self.title), theme().title()))
             .borders(Borders::ALL)
             .padding(Padding::uniform(1));
         let inner = block.inner(area);
@@ -173,8 +166,8 @@ fn render_item(f: &mut Frame, area: Rect, item: &StatusItem) {
     match item {
         StatusItem::Value { label, value } => {
             let line = Line::from(vec![
-                Span::styled(format!("{label} "), STYLE_LABEL),
-                Span::styled(value.clone(), STYLE_VALUE),
+                Span::styled(format!("{label} "), theme().dim()),
+                Span::styled(value.clone(), theme().value()),
             ]);
             f.render_widget(Paragraph::new(line).alignment(Alignment::Left), area);
         }
@@ -182,7 +175,7 @@ fn render_item(f: &mut Frame, area: Rect, item: &StatusItem) {
             let label = format!("{} {}/{}", bar.label, bar.current, bar.max);
             let gauge = Gauge::default()
                 .block(Block::default().borders(Borders::ALL))
-                .gauge_style(Style::default().fg(Color::Blue))
+                .gauge_style(theme().gauge())
                 .ratio(bar.ratio())
                 .label(label);
             f.render_widget(gauge, area);
@@ -0,0 +1,533 @@
+//! Stealth mode (roadmap: ステルスモード).
+//!
+//! An alternative renderer for `QuizUI` and `ListenUI` that makes the game
+//! look like someone tailing a service log ("データ監視中"). The question,
+//! choices and typed input are embedded in plausible log lines at the
+//! bottom of a scrolling tail; everything above them is generated noise.
+//!
+//! - The toggle key (`F8` by default) switches between the normal panes
+//!   and the log view (listed in the help line).
+//! - The boss key (`F12` by default): it swaps instantly to a fake `htop` screen and
+//!   swallows every key except quit until pressed again, so nothing typed
+//!   while someone looks over your shoulder leaks into the answer.
+//!
+//! Only the presentation changes. The owning UI keeps running its reveal,
+//! timers and input handling; it hands this module a [`LogScreen`] built
+//! from whatever is visible in the normal view (the revealed part of the
+//! question, the choices once they fade in), so stealth never shows more
+//! than the regular renderer would.
+//!
+//! Noise is a pure function of a line counter (splitmix64), so the tail
+//! scrolls at a steady pace without keeping a buffer.
+
+use crate::ui::keymap::{keymap, StealthAction};
+use crate::ui::theme::theme;
+use crate::ui::HelpEntry;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// A new noise line scrolls in this often.
+const NOISE_INTERVAL_MS: u128 = 700;
+
+/// Severity column of a log line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO ",
+            Self::Warn => "WARN ",
+            Self::Error => "ERROR",
+        }
+    }
+
+    fn style(&self) -> Style {
+        match self {
+            Self::Debug => theme().dim(),
+            Self::Info => theme().ok(),
+            Self::Warn => theme().help_key(),
+            Self::Error => theme().incorrect(),
+        }
+    }
+}
+
+/// One game-state line embedded in the log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEvent {
+    pub level: Level,
+    pub component: &'static str,
+    pub message: String,
+}
+
+impl LogEvent {
+    pub fn new(level: Level, component: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            component,
+            message: message.into(),
+        }
+    }
+}
+
+/// Everything the owning UI wants shown in the log view.
+#[derive(Debug, Clone, Default)]
+pub struct LogScreen {
+    /// Right side of the header, e.g. `データ監視中 3/10`.
+    pub status: String,
+    /// Lines pinned to the bottom of the tail, oldest first.
+    pub events: Vec<LogEvent>,
+    /// The player's input, shown as the filter prompt.
+    pub prompt: String,
+    /// Help-line label of the owning screen's quit key, for the footer.
+    pub quit_key: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Normal,
+    Log,
+    Boss,
+}
+
+/// Per-UI stealth state.
+pub struct Stealth {
+    view: View,
+    /// View to restore when the boss screen is dismissed.
+    before_boss: View,
+    started_at: Instant,
+    /// Wall-clock seconds at `started_at`, for log timestamps.
+    started_epoch: u64,
+}
+
+impl Stealth {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            view: if enabled { View::Log } else { View::Normal },
+            before_boss: View::Normal,
+            started_at: Instant::now(),
+            started_epoch: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+
+    pub fn is_log(&self) -> bool {
+        self.view == View::Log
+    }
+
+    pub fn is_boss(&self) -> bool {
+        self.view == View::Boss
+    }
+
+    /// Handle the stealth keys. Returns `true` when the key was consumed.
+    /// Quit keys are checked by the caller *before* this, so Esc still
+    /// leaves from the boss screen.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let action = keymap().stealth.action_for(key);
+        if action == Some(StealthAction::Boss) {
+            if self.view == View::Boss {
+                self.view = self.before_boss;
+            } else {
+                self.before_boss = self.view;
+                self.view = View::Boss;
+            }
+            return true;
+        }
+        if self.view == View::Boss {
+            return true;
+        }
+        if action == Some(StealthAction::Toggle) {
+            self.view = if self.view == View::Log {
+                View::Normal
+            } else {
+                View::Log
+            };
+            return true;
+        }
+        false
+    }
+
+    /// Help-line entry advertising the toggle.
+    pub fn help_entry() -> HelpEntry {
+        keymap().stealth.help(StealthAction::Toggle)
+    }
+
+    /// Draw the log-tail disguise over the whole of `area`.
+    pub fn render_log(&self, f: &mut Frame, area: Rect, screen: &LogScreen) {
+        if area.height < 3 {
+            return;
+        }
+        let body_rows = (area.height - 3) as usize;
+        let now_ms = self.started_at.elapsed().as_millis();
+        let tick = (now_ms / NOISE_INTERVAL_MS) as u64;
+        let now_secs = self.started_epoch + (now_ms / 1000) as u64;
+
+        let mut lines: Vec<Line<'static>> = Vec::with_capacity(area.height as usize);
+        lines.push(header_line(&screen.status, area.width));
+
+        let events: Vec<&LogEvent> = screen.events.iter().rev().take(body_rows).rev().collect();
+        let noise_rows = body_rows - events.len();
+        // Noise line `n` was "written" at tick `n`; the newest ones sit
+        // right above the pinned events.
+        for n in (tick + 1).saturating_sub(noise_rows as u64)..=tick {
+            let secs = self.started_epoch + n * NOISE_INTERVAL_MS as u64 / 1000;
+            let event = noise_event(n);
+            lines.push(log_line(secs, &event));
+        }
+        while lines.len() < 1 + noise_rows {
+            lines.insert(1, Line::default());
+        }
+        for event in events {
+            lines.push(log_line(now_secs, event));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("filter> ", theme().text()),
+            Span::styled(screen.prompt.clone(), theme().text()),
+            Span::styled("█", theme().text()),
+        ]));
+        let keys = &keymap().stealth;
+        lines.push(Line::from(Span::styled(
+            format!(
+                "-- follow --  {} normal view  {} top  {} quit",
+                keys.label(StealthAction::Toggle),
+                keys.label(StealthAction::Boss),
+                screen.quit_key
+            ),
+            theme().dim(),
+        )));
+        f.render_widget(Paragraph::new(lines), area);
+    }
+
+    /// Draw the fake `htop` screen over the whole of `area`.
+    pub fn render_boss(&self, f: &mut Frame, area: Rect) {
+        let secs = self.started_at.elapsed().as_secs();
+        let uptime = 1_036_447 + secs;
+        let half = (area.width / 2).max(20) as usize;
+        let bar_width = half.saturating_sub(8).max(10);
+        let mut lines: Vec<Line<'static>> = Vec::new();
+
+        let cpus: Vec<f64> = (0..4)
+            .map(|cpu| 2.0 + (mix(secs / 2 * 4 + cpu) % 300) as f64 / 10.0)
+            .collect();
+        let load = cpus.iter().sum::<f64>() / 100.0;
+        let right_column = [
+            format!("Tasks: 142, 611 thr; {} running", 1 + mix(secs) % 3),
+            format!(
+                "Load average: {load:.2} {:.2} {:.2}",
+                load + 0.07,
+                load + 0.12
+            ),
+            format!(
+                "Uptime: {} days, {:02}:{:02}:{:02}",
+                uptime / 86_400,
+                uptime / 3600 % 24,
+                uptime / 60 % 60,
+                uptime % 60
+            ),
+            String::new(),
+        ];
+        for (cpu, pct) in cpus.iter().enumerate() {
+            let mut spans = meter(&format!("{:>3}", cpu + 1), *pct, 100.0, bar_width, "%");
+            spans.push(Span::raw("   "));
+            spans.push(Span::styled(right_column[cpu].clone(), theme().value()));
+            lines.push(Line::from(spans));
+        }
+        let mem = 3.1 + (mix(secs / 5) % 40) as f64 / 100.0;
+        lines.push(Line::from(meter("Mem", mem, 15.5, bar_width, "G")));
+        lines.push(Line::from(meter("Swp", 0.0, 2.0, bar_width, "G")));
+        lines.push(Line::default());
+
+        let header = format!(
+            "{:>7} {:<9} {:>3} {:>3} {:>6} {:>6} {:>6} S {:>5} {:>5} {:>9}  Command",
+            "PID", "USER", "PRI", "NI", "VIRT", "RES", "SHR", "CPU%", "MEM%", "TIME+"
+        );
+        lines.push(Line::from(Span::styled(
+            format!("{header:<width$}", width = area.width as usize),
+            theme().ok().add_modifier(Modifier::REVERSED),
+        )));
+        let table_rows = (area.height as usize).saturating_sub(lines.len() + 1);
+        let mut procs: Vec<(u64, &str, &str, f64)> = PROCESSES
+            .iter()
+            .enumerate()
+            .map(|(i, (user, cmd))| {
+                let cpu = (mix(secs / 2 * 31 + i as u64) % 90) as f64 / 10.0;
+                (1000 + mix(i as u64) % 30_000, *user, *cmd, cpu)
+            })
+            .collect();
+        procs.sort_by(|a, b| b.3.total_cmp(&a.3));
+        for (pid, user, cmd, cpu) in procs.into_iter().take(table_rows) {
+            let mem = (mix(pid) % 40) as f64 / 10.0;
+            let time = secs + mix(pid) % 50_000;
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{pid:>7} {user:<9} {:>3} {:>3} {:>5}M {:>5}M {:>5}M {} {cpu:>5.1} {mem:>5.1} {:>3}:{:02}.{:02}  {cmd}",
+                    20,
+                    0,
+                    200 + mix(pid + 1) % 900,
+                    20 + mix(pid + 2) % 200,
+                    5 + mix(pid + 3) % 40,
+                    if cpu > 5.0 { 'R' } else { 'S' },
+                    time / 60,
+                    time % 60,
+                    mix(pid + secs) % 100,
+                ),
+                theme().text(),
+            )));
+        }
+        while lines.len() + 1 < area.height as usize {
+            lines.push(Line::default());
+        }
+        let mut fkeys: Vec<Span<'static>> = Vec::new();
+        for (key, label) in [
+            ("F1", "Help  "),
+            ("F2", "Setup "),
+            ("F3", "Search"),
+            ("F4", "Filter"),
+            ("F5", "Tree  "),
+            ("F6", "SortBy"),
+            ("F7", "Nice -"),
+            ("F8", "Nice +"),
+            ("F9", "Kill  "),
+            ("F10", "Quit  "),
+        ] {
+            fkeys.push(Span::styled(key, theme().text()));
+            fkeys.push(Span::styled(
+                label,
+                theme().value().add_modifier(Modifier::REVERSED),
+            ));
+        }
+        lines.push(Line::from(fkeys));
+        f.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+/// Fake process list for the boss screen: (user, command).
+const PROCESSES: [(&str, &str); 14] = [
+    ("root", "/usr/lib/systemd/systemd --switched-root --system"),
+    ("root", "/usr/bin/containerd"),
+    ("postgres", "postgres: checkpointer"),
+    ("postgres", "postgres: walwriter"),
+    ("www-data", "nginx: worker process"),
+    ("www-data", "nginx: worker process"),
+    ("deploy", "node /srv/api/dist/server.js"),
+    ("deploy", "python3 -m celery worker -Q reports"),
+    ("redis", "/usr/bin/redis-server 127.0.0.1:6379"),
+    ("root", "/usr/sbin/sshd -D"),
+    ("root", "/usr/lib/systemd/systemd-journald"),
+    ("deploy", "/usr/bin/java -Xmx2g -jar etl-runner.jar"),
+    (
+        "prometh+",
+        "/usr/bin/prometheus --config.file=/etc/prometheus.yml",
+    ),
+    ("root", "/usr/sbin/cron -f"),
+];
+
+/// splitmix64 — cheap, stateless, good enough for plausible noise.
+fn mix(n: u64) -> u64 {
+    let mut z = n.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Noise line number `n`.
+fn noise_event(n: u64) -> LogEvent {
+    let r = mix(n);
+    let a = mix(r);
+    let b = mix(a);
+    match r % 12 {
+        0..=3 => LogEvent::new(
+            Level::Info,
+            "sync.worker",
+            format!(
+                "batch={:05} rows={} latency={}ms",
+                n % 100_000,
+                a % 5000,
+                8 + b % 120
+            ),
+        ),
+        4..=6 => LogEvent::new(
+            Level::Info,
+            "http.access",
+            format!(
+                "GET /api/v2/metrics/{} 200 {}ms",
+                ["cpu", "disk", "net", "queue"][(a % 4) as usize],
+                2 + b % 60
+            ),
+        ),
+        7 | 8 => LogEvent::new(
+            Level::Debug,
+            "db.pool",
+            format!("active={} idle={} waiting=0", a % 12, 4 + b % 16),
+        ),
+        9 => LogEvent::new(
+            Level::Debug,
+            "cache",
+            format!("hit_ratio=0.{:02} evictions={}", 80 + a % 20, b % 40),
+        ),
+        10 => LogEvent::new(
+            Level::Info,
+            "scheduler",
+            format!(
+                "job=rollup-{} state=done elapsed={}ms",
+                a % 64,
+                100 + b % 4000
+            ),
+        ),
+        _ => LogEvent::new(
+            Level::Warn,
+            "http.access",
+            format!("GET /api/v2/export 429 retry_after={}s", 1 + a % 30),
+        ),
+    }
+}
+
+fn clock(secs: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+fn log_line(secs: u64, event: &LogEvent) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{}Z ", clock(secs)), theme().dim()),
+        Span::styled(event.level.label(), event.level.style()),
+        Span::styled(format!(" {:<12} ", event.component), theme().value()),
+        Span::styled(event.message.clone(), theme().text()),
+    ])
+}
+
+fn header_line(status: &str, width: u16) -> Line<'static> {
+    let left = " tail -F /var/log/globe-sync/worker.log";
+    let right = format!("{status} ");
+    let used = left.chars().count() + unicode_width::UnicodeWidthStr::width(right.as_str());
+    let pad = (width as usize).saturating_sub(used);
+    Line::from(Span::styled(
+        format!("{left}{}{right}", " ".repeat(pad)),
+        theme().text().add_modifier(Modifier::REVERSED),
+    ))
+}
+
+/// htop-style `label[|||||    12.3%]` meter.
+fn meter(label: &str, value: f64, max: f64, width: usize, unit: &str) -> Vec<Span<'static>> {
+    let text = if unit == "%" {
+        format!("{value:.1}%")
+    } else {
+        format!("{value:.2}{unit}/{max:.1}{unit}")
+    };
+    let inner = width.saturating_sub(text.len());
+    let filled = ((value / max).clamp(0.0, 1.0) * inner as f64).round() as usize;
+    let low = filled.min(inner * 2 / 3);
+    vec![
+        Span::styled(label.to_string(), theme().value()),
+        Span::styled("[", theme().text().add_modifier(Modifier::BOLD)),
+        Span::styled("|".repeat(low), theme().ok()),
+        Span::styled("|".repeat(filled - low), theme().issue()),
+        Span::raw(" ".repeat(inner - filled)),
+        Span::styled(text, theme().text()),
+        Span::styled("]", theme().text().add_modifier(Modifier::BOLD)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn dump(terminal: &Terminal<TestBackend>) -> String {
+        let buffer = terminal.backend().buffer();
+        let mut out = String::new();
+        for y in 0..buffer.area.height {
+            for x in 0..buffer.area.width {
+                out.push_str(buffer[(x, y)].symbol());
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    #[test]
+    fn toggle_and_boss_keys() {
+        let mut stealth = Stealth::new(false);
+        assert!(!stealth.is_log());
+        assert!(!stealth.handle_key(&key(KeyCode::Char('a'))));
+        assert!(stealth.handle_key(&key(KeyCode::F(8))));
+        assert!(stealth.is_log());
+        assert!(stealth.handle_key(&key(KeyCode::F(12))));
+        assert!(stealth.is_boss());
+        // Typing is swallowed while the boss screen is up.
+        assert!(stealth.handle_key(&key(KeyCode::Char('a'))));
+        assert!(stealth.handle_key(&key(KeyCode::F(8))));
+        assert!(stealth.is_boss());
+        // Dismissing returns to the view the boss key interrupted.
+        assert!(stealth.handle_key(&key(KeyCode::F(12))));
+        assert!(stealth.is_log());
+    }
+
+    #[test]
+    fn log_view_pins_events_above_the_prompt() {
+        let stealth = Stealth::new(true);
+        let screen = LogScreen {
+            status: "データ監視中 1/10".into(),
+            events: vec![
+                LogEvent::new(Level::Info, "query", "q=\"capital of France?\""),
+                LogEvent::new(Level::Debug, "route", "A -> paris"),
+            ],
+            prompt: "par".into(),
+            quit_key: "Esc".into(),
+        };
+        let mut terminal = Terminal::new(TestBackend::new(80, 12)).expect("terminal");
+        terminal
+            .draw(|f| stealth.render_log(f, f.area(), &screen))
+            .expect("draw");
+        let text = dump(&terminal);
+        let rows: Vec<&str> = text.lines().collect();
+        assert!(rows[0].contains("tail -F"));
+        assert!(rows[0].trim_end().ends_with("1/10"));
+        assert!(rows[8].contains("capital of France?"));
+        assert!(rows[9].contains("A -> paris"));
+        assert!(rows[10].starts_with("filter> par"));
+    }
+
+    #[test]
+    fn boss_screen_looks_like_htop() {
+        let stealth = Stealth::new(false);
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).expect("terminal");
+        terminal
+            .draw(|f| stealth.render_boss(f, f.area()))
+            .expect("draw");
+        let text = dump(&terminal);
+        assert!(text.contains("Load average:"));
+        assert!(text.contains("Mem["));
+        assert!(text.contains("PID USER"));
+        assert!(text.lines().last().unwrap_or("").starts_with("F1Help"));
+    }
+
+    #[test]
+    fn noise_is_deterministic() {
+        assert_eq!(noise_event(42), noise_event(42));
+    }
+}
@@ -0,0 +1,784 @@
+//! Colour theme shared by every screen.
+//!
+//! Each UI module used to carry its own `STYLE_*` constants plus a few
+//! hard-coded RGB triples (the inline-code amber, the soft green question
+//! reveal, the choices fade-in). They all read from one [`Theme`] now, so
+//! a light terminal or a 16-colour console can be served by swapping the
+//! theme instead of editing every screen.
+//!
+//! The theme is picked at startup from `theme.yaml` in the data dir:
+//!
+//! ```yaml
+//! preset: light          # dark (default) | light | high-contrast | 16-color
+//! colors:
+//!   inline_code: "#b35900"
+//!   correct: green
+//! fades:
+//!   question: { from: "#dddddd", to: "#206020" }
+//! ```
+//!
+//! `preset` selects the base palette and the optional `colors` / `fades`
+//! maps override single roles. The `theme` entry of `settings.yaml` (and
+//! the settings screen) picks the preset instead when set. Colours
+//! accept ratatui's names (`green`, `lightblue`, …) or `#rrggbb`. Fades
+//! are the jiwa endpoints (reveal, choices fade-in, menu detail,
+//! listening pulse) and therefore need colours that resolve to RGB. A
+//! missing file means the dark preset.
+//!
+//! The terminal's colour support ([`Palette`]) is detected from
+//! `NO_COLOR` / `COLORTERM` / `TERM` or forced with `--color`, and caps
+//! the theme: RGB is mapped to the xterm-256 palette, to the nearest of
+//! the 16 ANSI colours, or dropped. Without RGB the fades turn into
+//! dim → normal steps. The `16-color` preset caps itself at 16 colours
+//! regardless of what the terminal reports.
+
+use jiwa::{lerp_rgb, PulseOpts, RevealOpts, Rgb};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Names accepted by the `preset:` key, in the order `--help`-style
+/// listings show them.
+pub const PRESET_NAMES: [&str; 4] = ["dark", "light", "high-contrast", "16-color"];
+
+/// Start and end colour of one jiwa fade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fade {
+    pub from: Rgb,
+    pub to: Rgb,
+}
+
+impl Fade {
+    const fn new(from: Rgb, to: Rgb) -> Self {
+        Self { from, to }
+    }
+}
+
+/// How many colours the terminal can show, weakest first so `min` picks
+/// the stricter of the theme's and the terminal's limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Palette {
+    /// `NO_COLOR` or `TERM=dumb`: no foreground colours at all; fades
+    /// become dim → normal steps.
+    NoColor,
+    /// Nearest of the 16 ANSI colours; fades become dim → normal steps
+    /// on their end colour.
+    Ansi16,
+    /// Nearest xterm-256 index (colour cube or grey ramp).
+    Ansi256,
+    TrueColor,
+}
+
+impl Palette {
+    /// Names accepted by `--color` (besides `auto`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "truecolor" | "24bit" => Some(Self::TrueColor),
+            "256" => Some(Self::Ansi256),
+            "16" => Some(Self::Ansi16),
+            "none" | "no-color" => Some(Self::NoColor),
+            _ => None,
+        }
+    }
+
+    pub fn detect() -> Self {
+        Self::from_env(|key| std::env::var(key).ok())
+    }
+
+    /// Decide from environment variables (injected for tests).
+    /// `NO_COLOR` (any non-empty value, per no-color.org) wins, then
+    /// `COLORTERM=truecolor|24bit`, then a `256color` `TERM`. tmux and
+    /// screen only advertise truecolor through `COLORTERM`, so a
+    /// `tmux-256color` session without RGB passthrough lands on 256.
+    pub fn from_env(get: impl Fn(&str) -> Option<String>) -> Self {
+        if get("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return Self::NoColor;
+        }
+        let colorterm = get("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return Self::TrueColor;
+        }
+        let term = get("TERM").unwrap_or_default();
+        match term.as_str() {
+            "dumb" => Self::NoColor,
+            t if t.contains("256color") => Self::Ansi256,
+            t if t.contains("direct") => Self::TrueColor,
+            _ => Self::Ansi16,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub palette: Palette,
+    /// Screen titles and pane headings.
+    pub title: Color,
+    pub text: Color,
+    pub dim: Color,
+    /// Labels and values that should stand out from body text.
+    pub accent: Color,
+    /// Selection and the player's own typing.
+    pub highlight: Color,
+    pub correct: Color,
+    pub incorrect: Color,
+    /// `backtick` spans inside question text.
+    pub inline_code: Color,
+    /// Action captions in the help line (keys use `highlight`).
+    pub help_action: Color,
+    pub gauge: Color,
+    pub code_keyword: Color,
+    pub code_string: Color,
+    pub code_comment: Color,
+    pub code_number: Color,
+    /// Question text typewriter reveal.
+    pub question_fade: Fade,
+    /// Choice label (`1.`, `2.`, …) fade-in.
+    pub choice_label_fade: Fade,
+    /// Choice text fade-in.
+    pub choice_text_fade: Fade,
+    /// Menu detail pane fade-in.
+    pub menu_detail_fade: Fade,
+    /// Listening "♪" breathing pulse (dim → bright).
+    pub pulse: Fade,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// The original look, tuned for a dark background.
+    pub fn dark() -> Self {
+        Self {
+            palette: Palette::TrueColor,
+            title: Color::Cyan,
+            text: Color::White,
+            dim: Color::DarkGray,
+            accent: Color::Cyan,
+            highlight: Color::Yellow,
+            correct: Color::Green,
+            incorrect: Color::Red,
+            // Amber, deliberately distinct from the yellow input echo.
+            inline_code: Color::Rgb(255, 200, 60),
+            help_action: Color::Gray,
+            gauge: Color::Blue,
+            code_keyword: Color::Magenta,
+            code_string: Color::Green,
+            code_comment: Color::DarkGray,
+            code_number: Color::Cyan,
+            question_fade: Fade::new(Rgb(60, 60, 60), Rgb(160, 220, 160)),
+            choice_label_fade: Fade::new(Rgb(20, 60, 80), Rgb(80, 200, 255)),
+            choice_text_fade: Fade::new(Rgb(20, 20, 20), Rgb(255, 255, 255)),
+            menu_detail_fade: Fade::new(Rgb(60, 60, 60), Rgb(220, 220, 220)),
+            pulse: Fade::new(Rgb(40, 60, 80), Rgb(80, 200, 255)),
+        }
+    }
+
+    /// Dark text on a light background; fades start near white.
+    pub fn light() -> Self {
+        Self {
+            palette: Palette::TrueColor,
+            title: Color::Rgb(0, 90, 160),
+            text: Color::Rgb(20, 20, 20),
+            dim: Color::Rgb(120, 120, 120),
+            accent: Color::Rgb(0, 110, 150),
+            highlight: Color::Rgb(170, 85, 0),
+            correct: Color::Rgb(0, 130, 0),
+            incorrect: Color::Rgb(190, 0, 0),
+            inline_code: Color::Rgb(140, 40, 140),
+            help_action: Color::Rgb(80, 80, 80),
+            gauge: Color::Rgb(0, 90, 160),
+            code_keyword: Color::Rgb(140, 40, 140),
+            code_string: Color::Rgb(0, 120, 0),
+            code_comment: Color::Rgb(130, 130, 130),
+            code_number: Color::Rgb(0, 100, 140),
+            question_fade: Fade::new(Rgb(225, 225, 225), Rgb(30, 100, 30)),
+            choice_label_fade: Fade::new(Rgb(220, 230, 240), Rgb(0, 90, 160)),
+            choice_text_fade: Fade::new(Rgb(235, 235, 235), Rgb(0, 0, 0)),
+            menu_detail_fade: Fade::new(Rgb(235, 235, 235), Rgb(40, 40, 40)),
+            pulse: Fade::new(Rgb(170, 200, 220), Rgb(0, 110, 170)),
+        }
+    }
+
+    /// Saturated colours on black, short fades that never start invisible.
+    pub fn high_contrast() -> Self {
+        Self {
+            palette: Palette::TrueColor,
+            title: Color::Rgb(255, 255, 255),
+            text: Color::Rgb(255, 255, 255),
+            dim: Color::Rgb(190, 190, 190),
+            accent: Color::Rgb(0, 255, 255),
+            highlight: Color::Rgb(255, 255, 0),
+            correct: Color::Rgb(0, 255, 0),
+            incorrect: Color::Rgb(255, 80, 80),
+            inline_code: Color::Rgb(255, 170, 0),
+            help_action: Color::Rgb(255, 255, 255),
+            gauge: Color::Rgb(0, 255, 255),
+            code_keyword: Color::Rgb(255, 128, 255),
+            code_string: Color::Rgb(0, 255, 0),
+            code_comment: Color::Rgb(190, 190, 190),
+            code_number: Color::Rgb(0, 255, 255),
+            question_fade: Fade::new(Rgb(128, 128, 128), Rgb(255, 255, 255)),
+            choice_label_fade: Fade::new(Rgb(0, 128, 128), Rgb(0, 255, 255)),
+            choice_text_fade: Fade::new(Rgb(128, 128, 128), Rgb(255, 255, 255)),
+            menu_detail_fade: Fade::new(Rgb(128, 128, 128), Rgb(255, 255, 255)),
+            pulse: Fade::new(Rgb(0, 128, 128), Rgb(0, 255, 255)),
+        }
+    }
+
+    /// Named ANSI colours only. Fade endpoints sit on ANSI palette
+    /// entries so quantisation steps cleanly between them.
+    pub fn ansi16() -> Self {
+        Self {
+            palette: Palette::Ansi16,
+            inline_code: Color::LightMagenta,
+            question_fade: Fade::new(Rgb(127, 127, 127), Rgb(0, 205, 0)),
+            choice_label_fade: Fade::new(Rgb(127, 127, 127), Rgb(0, 205, 205)),
+            choice_text_fade: Fade::new(Rgb(127, 127, 127), Rgb(255, 255, 255)),
+            menu_detail_fade: Fade::new(Rgb(127, 127, 127), Rgb(229, 229, 229)),
+            pulse: Fade::new(Rgb(0, 0, 0), Rgb(0, 205, 205)),
+            ..Self::dark()
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" | "high_contrast" => Some(Self::high_contrast()),
+            "16-color" | "16color" | "ansi16" => Some(Self::ansi16()),
+            _ => None,
+        }
+    }
+
+    /// Load `path`. A missing file is the base preset, not an error.
+    ///
+    /// `preset` is the `theme` picked in `settings.yaml`; when set it
+    /// replaces the file's own `preset:` while the file's `colors` /
+    /// `fades` still apply on top.
+    pub fn load(path: &str, preset: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        if !Path::new(path).exists() {
+            return Self::from_yaml("", preset);
+        }
+        Self::from_yaml(&std::fs::read_to_string(path)?, preset)
+    }
+
+    pub fn from_yaml(yaml: &str, preset: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        // An empty file deserialises as `null`; treat it as "no overrides".
+        let file: ThemeFile = serde_yaml::from_str::<Option<ThemeFile>>(yaml)?.unwrap_or_default();
+        let mut theme = match preset.or(file.preset.as_deref()) {
+            Some(name) => Self::preset(name).ok_or_else(|| {
+                format!(
+                    "unknown theme preset '{name}' (expected one of: {})",
+                    PRESET_NAMES.join(", ")
+                )
+            })?,
+            None => Self::default(),
+        };
+        for (key, value) in &file.colors {
+            let color = parse_color(value)?;
+            *theme
+                .color_slot(key)
+                .ok_or_else(|| format!("unknown theme color '{key}'"))? = color;
+        }
+        for (key, fade) in &file.fades {
+            let parsed = Fade::new(parse_rgb(&fade.from)?, parse_rgb(&fade.to)?);
+            *theme
+                .fade_slot(key)
+                .ok_or_else(|| format!("unknown theme fade '{key}'"))? = parsed;
+        }
+        Ok(theme)
+    }
+
+    fn color_slot(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+            "title" => &mut self.title,
+            "text" => &mut self.text,
+            "dim" => &mut self.dim,
+            "accent" => &mut self.accent,
+            "highlight" => &mut self.highlight,
+            "correct" => &mut self.correct,
+            "incorrect" => &mut self.incorrect,
+            "inline_code" => &mut self.inline_code,
+            "help_action" => &mut self.help_action,
+            "gauge" => &mut self.gauge,
+            "code_keyword" => &mut self.code_keyword,
+            "code_string" => &mut self.code_string,
+            "code_comment" => &mut self.code_comment,
+            "code_number" => &mut self.code_number,
+            _ => return None,
+        })
+    }
+
+    fn fade_slot(&mut self, key: &str) -> Option<&mut Fade> {
+        Some(match key {
+            "question" => &mut self.question_fade,
+            "choice_label" => &mut self.choice_label_fade,
+            "choice_text" => &mut self.choice_text_fade,
+            "menu_detail" => &mut self.menu_detail_fade,
+            "pulse" => &mut self.pulse,
+            _ => return None,
+        })
+    }
+
+    /// Limit the theme to what the terminal can show. Never raises the
+    /// palette, so the `16-color` preset stays 16 colours on a truecolor
+    /// terminal.
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = self.palette.min(palette);
+        self
+    }
+
+    /// Map a role colour through the palette. `Color::Reset` under
+    /// [`Palette::NoColor`].
+    pub fn color(&self, color: Color) -> Color {
+        match (self.palette, color) {
+            (Palette::NoColor, _) => Color::Reset,
+            (Palette::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi16(Rgb(r, g, b)),
+            (Palette::Ansi256, Color::Rgb(r, g, b)) => {
+                Color::Indexed(nearest_ansi256(Rgb(r, g, b)))
+            }
+            _ => color,
+        }
+    }
+
+    /// Style `t` (0.0–1.0) of the way along `fade`. RGB palettes lerp;
+    /// 16-colour and no-colour terminals can't show the in-between
+    /// shades, so they step from dim to normal on the end colour instead.
+    pub fn fade_style(&self, fade: Fade, t: f32) -> Style {
+        match self.palette {
+            Palette::TrueColor | Palette::Ansi256 => {
+                let Rgb(r, g, b) = lerp_rgb(fade.from, fade.to, t);
+                self.fg(Color::Rgb(r, g, b))
+            }
+            Palette::Ansi16 | Palette::NoColor => {
+                let Rgb(r, g, b) = fade.to;
+                let style = self.fg(Color::Rgb(r, g, b));
+                if t < 0.5 {
+                    style.add_modifier(Modifier::DIM)
+                } else {
+                    style
+                }
+            }
+        }
+    }
+
+    /// Style for a jiwa frame colour (a reveal grapheme or pulse frame)
+    /// somewhere along `fade`. jiwa hands out the lerped RGB, not the
+    /// progress, so the progress is read back off the channel that
+    /// changes most.
+    pub fn frame_style(&self, fade: Fade, rgb: Rgb) -> Style {
+        self.fade_style(fade, fade_progress(fade, rgb))
+    }
+
+    /// jiwa's `soft_green` timing with this theme's question fade.
+    pub fn reveal_opts(&self) -> RevealOpts {
+        RevealOpts {
+            fade_from: self.question_fade.from,
+            fade_to: self.question_fade.to,
+            ..RevealOpts::soft_green()
+        }
+    }
+
+    /// jiwa's `cyan_breath` timing with this theme's pulse colours.
+    pub fn pulse_opts(&self) -> PulseOpts {
+        PulseOpts {
+            color_dim: self.pulse.from,
+            color_bright: self.pulse.to,
+            ..PulseOpts::cyan_breath()
+        }
+    }
+
+    fn fg(&self, color: Color) -> Style {
+        match self.palette {
+            Palette::NoColor => Style::new(),
+            _ => Style::new().fg(self.color(color)),
+        }
+    }
+
+    fn bold(&self, color: Color) -> Style {
+        self.fg(color).add_modifier(Modifier::BOLD)
+    }
+
+    pub fn title(&self) -> Style {
+        self.bold(self.title)
+    }
+
+    pub fn section(&self) -> Style {
+        self.fg(self.title)
+    }
+
+    pub fn text(&self) -> Style {
+        self.fg(self.text)
+    }
+
+    pub fn dim(&self) -> Style {
+        self.fg(self.dim)
+    }
+
+    pub fn label(&self) -> Style {
+        self.bold(self.accent)
+    }
+
+    pub fn value(&self) -> Style {
+        self.fg(self.accent)
+    }
+
+    /// Selected menu rows and list items.
+    pub fn selected(&self) -> Style {
+        self.bold(self.highlight)
+    }
+
+    /// What the player is typing right now.
+    pub fn input_echo(&self) -> Style {
+        self.bold(self.highlight)
+    }
+
+    pub fn correct(&self) -> Style {
+        self.bold(self.correct)
+    }
+
+    pub fn incorrect(&self) -> Style {
+        self.bold(self.incorrect)
+    }
+
+    /// Correct without the weight, for status words inside a sentence.
+    pub fn ok(&self) -> Style {
+        self.fg(self.correct)
+    }
+
+    /// Incorrect without the weight, for issue lists and warnings.
+    pub fn issue(&self) -> Style {
+        self.fg(self.incorrect)
+    }
+
+    pub fn inline_code(&self) -> Style {
+        self.bold(self.inline_code)
+    }
+
+    pub fn help_key(&self) -> Style {
+        self.fg(self.highlight)
+    }
+
+    pub fn help_action(&self) -> Style {
+        self.fg(self.help_action)
+    }
+
+    pub fn gauge(&self) -> Style {
+        self.fg(self.gauge)
+    }
+
+    pub fn code_keyword(&self) -> Style {
+        self.bold(self.code_keyword)
+    }
+
+    pub fn code_string(&self) -> Style {
+        self.fg(self.code_string)
+    }
+
+    pub fn code_comment(&self) -> Style {
+        self.fg(self.code_comment).add_modifier(Modifier::ITALIC)
+    }
+
+    pub fn code_number(&self) -> Style {
+        self.fg(self.code_number)
+    }
+}
+
+static THEME: RwLock<Option<&'static Theme>> = RwLock::new(None);
+
+/// Install the theme for this process, replacing the current one. The
+/// settings screen re-installs it when the player picks another preset;
+/// the replaced theme is leaked so earlier `&'static` borrows stay valid,
+/// which costs one small struct per change.
+pub fn install(theme: Theme) {
+    let leaked: &'static Theme = Box::leak(Box::new(theme));
+    *THEME.write().unwrap_or_else(|e| e.into_inner()) = Some(leaked);
+}
+
+/// The active theme, or the dark default before any [`install`].
+pub fn theme() -> &'static Theme {
+    if let Some(theme) = *THEME.read().unwrap_or_else(|e| e.into_inner()) {
+        return theme;
+    }
+    let mut slot = THEME.write().unwrap_or_else(|e| e.into_inner());
+    slot.get_or_insert_with(|| Box::leak(Box::new(Theme::default())))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    preset: Option<String>,
+    colors: BTreeMap<String, String>,
+    fades: BTreeMap<String, FadeFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FadeFile {
+    from: String,
+    to: String,
+}
+
+/// The xterm defaults for the 16 ANSI colours, used both to quantise RGB
+/// and to give named colours an RGB value for fade endpoints.
+const ANSI16: [(Color, Rgb); 16] = [
+    (Color::Black, Rgb(0, 0, 0)),
+    (Color::Red, Rgb(205, 0, 0)),
+    (Color::Green, Rgb(0, 205, 0)),
+    (Color::Yellow, Rgb(205, 205, 0)),
+    (Color::Blue, Rgb(0, 0, 238)),
+    (Color::Magenta, Rgb(205, 0, 205)),
+    (Color::Cyan, Rgb(0, 205, 205)),
+    (Color::Gray, Rgb(229, 229, 229)),
+    (Color::DarkGray, Rgb(127, 127, 127)),
+    (Color::LightRed, Rgb(255, 0, 0)),
+    (Color::LightGreen, Rgb(0, 255, 0)),
+    (Color::LightYellow, Rgb(255, 255, 0)),
+    (Color::LightBlue, Rgb(92, 92, 255)),
+    (Color::LightMagenta, Rgb(255, 0, 255)),
+    (Color::LightCyan, Rgb(0, 255, 255)),
+    (Color::White, Rgb(255, 255, 255)),
+];
+
+fn nearest_ansi16(rgb: Rgb) -> Color {
+    let distance = |other: Rgb| {
+        let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+        d(rgb.0, other.0) + d(rgb.1, other.1) + d(rgb.2, other.2)
+    };
+    ANSI16
+        .iter()
+        .min_by_key(|(_, candidate)| distance(*candidate))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::White)
+}
+
+/// The 6 levels of each channel in the xterm-256 colour cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn nearest_ansi256(rgb: Rgb) -> u8 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+    let nearest_level = |c: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| d(c, CUBE_LEVELS[i]))
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (
+        nearest_level(rgb.0),
+        nearest_level(rgb.1),
+        nearest_level(rgb.2),
+    );
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+    // Grey ramp 232..=255 runs 8, 18, …, 238.
+    let mean = (u32::from(rgb.0) + u32::from(rgb.1) + u32::from(rgb.2)) / 3;
+    let step = (mean.saturating_sub(3) / 10).min(23) as u8;
+    let grey = 8 + 10 * step;
+    let distance = |(r, g, b): (u8, u8, u8)| d(rgb.0, r) + d(rgb.1, g) + d(rgb.2, b);
+    if distance((grey, grey, grey)) < distance(cube) {
+        232 + step
+    } else {
+        cube_index as u8
+    }
+}
+
+/// How far (0.0–1.0) `rgb` sits along `fade`, measured on the channel
+/// with the largest swing.
+fn fade_progress(fade: Fade, rgb: Rgb) -> f32 {
+    let channels = [
+        (fade.from.0, fade.to.0, rgb.0),
+        (fade.from.1, fade.to.1, rgb.1),
+        (fade.from.2, fade.to.2, rgb.2),
+    ];
+    channels
+        .iter()
+        .map(|&(from, to, at)| (f32::from(from), f32::from(to), f32::from(at)))
+        .max_by(|a, b| (a.1 - a.0).abs().total_cmp(&(b.1 - b.0).abs()))
+        .filter(|(from, to, _)| from != to)
+        .map(|(from, to, at)| ((at - from) / (to - from)).clamp(0.0, 1.0))
+        .unwrap_or(1.0)
+}
+
+fn parse_color(value: &str) -> Result<Color, Box<dyn std::error::Error>> {
+    Color::from_str(value.trim()).map_err(|_| format!("invalid theme color '{value}'").into())
+}
+
+fn parse_rgb(value: &str) -> Result<Rgb, Box<dyn std::error::Error>> {
+    match parse_color(value)? {
+        Color::Rgb(r, g, b) => Ok(Rgb(r, g, b)),
+        named => ANSI16
+            .iter()
+            .find(|(color, _)| *color == named)
+            .map(|(_, rgb)| *rgb)
+            .ok_or_else(|| format!("theme fade color '{value}' has no RGB value").into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_preset_name_resolves() {
+        for name in PRESET_NAMES {
+            assert!(Theme::preset(name).is_some(), "{name}");
+        }
+        assert_eq!(Theme::preset("dark"), Some(Theme::default()));
+        assert!(Theme::preset("solarized").is_none());
+    }
+
+    #[test]
+    fn yaml_overrides_apply_on_top_of_the_preset() {
+        let theme = Theme::from_yaml(
+            "preset: light\ncolors:\n  inline_code: \"#102030\"\n  correct: lightgreen\nfades:\n  question: { from: black, to: \"#00ff00\" }\n",
+            None,
+        )
+        .expect("parse");
+        assert_eq!(theme.text, Theme::light().text);
+        assert_eq!(theme.inline_code, Color::Rgb(0x10, 0x20, 0x30));
+        assert_eq!(theme.correct, Color::LightGreen);
+        assert_eq!(theme.question_fade, Fade::new(Rgb(0, 0, 0), Rgb(0, 255, 0)));
+        assert_eq!(theme.reveal_opts().fade_to, Rgb(0, 255, 0));
+    }
+
+    #[test]
+    fn settings_preset_replaces_the_file_preset_only() {
+        let yaml = "preset: light\ncolors:\n  correct: lightgreen\n";
+        let theme = Theme::from_yaml(yaml, Some("high-contrast")).expect("parse");
+        assert_eq!(theme.text, Theme::high_contrast().text);
+        assert_eq!(theme.correct, Color::LightGreen);
+        let missing = Theme::load("/nonexistent/type-globe/theme.yaml", Some("light"));
+        assert_eq!(missing.expect("load"), Theme::light());
+        assert!(Theme::from_yaml("", Some("sepia")).is_err());
+    }
+
+    #[test]
+    fn empty_yaml_is_the_dark_preset() {
+        assert_eq!(Theme::from_yaml("", None).expect("parse"), Theme::dark());
+    }
+
+    #[test]
+    fn unknown_keys_and_values_are_errors() {
+        assert!(Theme::from_yaml("preset: sepia\n", None).is_err());
+        assert!(Theme::from_yaml("colors:\n  background: red\n", None).is_err());
+        assert!(Theme::from_yaml("colors:\n  text: not-a-color\n", None).is_err());
+        assert!(Theme::from_yaml("fades:\n  reveal: { from: red, to: blue }\n", None).is_err());
+        assert!(Theme::from_yaml("fades:\n  pulse: { from: reset, to: blue }\n", None).is_err());
+        assert!(Theme::from_yaml("palette: 256\n", None).is_err());
+    }
+
+    #[test]
+    fn missing_theme_file_is_the_default() {
+        let theme = Theme::load("/nonexistent/type-globe/theme.yaml", None).expect("load");
+        assert_eq!(theme, Theme::default());
+    }
+
+    #[test]
+    fn ansi16_quantises_rgb_and_steps_fades() {
+        let theme = Theme::ansi16();
+        assert_eq!(theme.color(Color::Rgb(250, 10, 10)), Color::LightRed);
+        assert_eq!(theme.color(Color::Yellow), Color::Yellow);
+        let settled = theme.fade_style(theme.question_fade, 1.0);
+        assert_eq!(settled.fg, Some(Color::Green));
+        assert!(!settled.add_modifier.contains(Modifier::DIM));
+        let early = theme.fade_style(theme.question_fade, 0.0);
+        assert_eq!(early.fg, Some(Color::Green));
+        assert!(early.add_modifier.contains(Modifier::DIM));
+        // Truecolor presets pass RGB straight through.
+        let dark = Theme::dark();
+        assert_eq!(
+            dark.fade_style(dark.question_fade, 1.0).fg,
+            Some(Color::Rgb(160, 220, 160))
+        );
+    }
+
+    #[test]
+    fn palette_detection_follows_no_color_colorterm_and_term() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(Palette::from_env(env(&[])), Palette::Ansi16);
+        assert_eq!(
+            Palette::from_env(env(&[
+                ("TERM", "xterm-256color"),
+                ("COLORTERM", "truecolor")
+            ])),
+            Palette::TrueColor
+        );
+        assert_eq!(
+            Palette::from_env(env(&[("TERM", "tmux-256color")])),
+            Palette::Ansi256
+        );
+        assert_eq!(
+            Palette::from_env(env(&[("TERM", "dumb")])),
+            Palette::NoColor
+        );
+        assert_eq!(
+            Palette::from_env(env(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")])),
+            Palette::NoColor
+        );
+        // An empty NO_COLOR does not count.
+        assert_eq!(
+            Palette::from_env(env(&[("NO_COLOR", ""), ("TERM", "xterm-256color")])),
+            Palette::Ansi256
+        );
+        assert_eq!(Palette::from_name("256"), Some(Palette::Ansi256));
+        assert_eq!(Palette::from_name("auto"), None);
+    }
+
+    #[test]
+    fn with_palette_never_raises_the_preset_limit() {
+        assert_eq!(
+            Theme::ansi16().with_palette(Palette::TrueColor).palette,
+            Palette::Ansi16
+        );
+        assert_eq!(
+            Theme::dark().with_palette(Palette::Ansi256).palette,
+            Palette::Ansi256
+        );
+    }
+
+    #[test]
+    fn ansi256_maps_to_cube_and_grey_ramp() {
+        assert_eq!(nearest_ansi256(Rgb(255, 0, 0)), 196);
+        assert_eq!(nearest_ansi256(Rgb(0, 0, 0)), 16);
+        assert_eq!(nearest_ansi256(Rgb(128, 128, 128)), 244);
+        let theme = Theme::dark().with_palette(Palette::Ansi256);
+        assert_eq!(theme.color(Color::Rgb(255, 255, 255)), Color::Indexed(231));
+        assert_eq!(theme.color(Color::Cyan), Color::Cyan);
+    }
+
+    #[test]
+    fn no_color_drops_foregrounds_but_keeps_weight() {
+        let theme = Theme::dark().with_palette(Palette::NoColor);
+        assert_eq!(theme.correct().fg, None);
+        assert!(theme.correct().add_modifier.contains(Modifier::BOLD));
+        let early = theme.fade_style(theme.choice_text_fade, 0.2);
+        assert_eq!(early.fg, None);
+        assert!(early.add_modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn frame_style_reads_progress_back_from_rgb() {
+        let theme = Theme::ansi16();
+        let fade = theme.question_fade;
+        assert!(theme
+            .frame_style(fade, fade.from)
+            .add_modifier
+            .contains(Modifier::DIM));
+        assert!(!theme
+            .frame_style(fade, fade.to)
+            .add_modifier
+            .contains(Modifier::DIM));
+    }
+}